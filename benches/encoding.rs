@@ -0,0 +1,79 @@
+//! Benchmarks for `IrcEncode` byte serialization.
+//!
+//! Baseline (x86_64, release, criterion defaults):
+//! - `to_bytes/simple_ping`: ~45 ns
+//! - `to_bytes/with_prefix`: ~47 ns
+//! - `to_bytes/with_tags`: ~277 ns
+//! - `encode_reused_buffer/simple_ping`: ~26 ns
+//! - `encode_reused_buffer/with_tags`: ~243 ns
+//! - `message_ref_to_bytes/with_tags`: ~50 ns
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use slirc_proto::{IrcEncode, Message, MessageRef};
+
+const SIMPLE_MESSAGE: &str = "PING :irc.example.com";
+
+const PREFIX_MESSAGE: &str = ":nick!user@host PRIVMSG #channel :Hello, world!";
+
+const TAGGED_MESSAGE: &str = "@time=2023-01-01T00:00:00.000Z;msgid=abc123;+example/tag=value :nick!user@host PRIVMSG #channel :Hello with tags!";
+
+const MESSAGES: [(&str, &str); 3] = [
+    ("simple_ping", SIMPLE_MESSAGE),
+    ("with_prefix", PREFIX_MESSAGE),
+    ("with_tags", TAGGED_MESSAGE),
+];
+
+fn benchmark_to_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_bytes");
+
+    for (name, raw) in MESSAGES {
+        let msg: Message = raw.parse().unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &msg, |b, msg| {
+            b.iter(|| black_box(black_box(msg).to_bytes()))
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_encode_reused_buffer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode_reused_buffer");
+
+    // The transports encode into a long-lived buffer; this isolates the
+    // encoder from the allocation `to_bytes` performs.
+    for (name, raw) in MESSAGES {
+        let msg: Message = raw.parse().unwrap();
+        let mut buf = Vec::with_capacity(512);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &msg, |b, msg| {
+            b.iter(|| {
+                buf.clear();
+                black_box(msg).encode(&mut buf).unwrap();
+                black_box(buf.len())
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_message_ref_to_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("message_ref_to_bytes");
+
+    for (name, raw) in MESSAGES {
+        let msg = MessageRef::parse(raw).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &msg, |b, msg| {
+            b.iter(|| black_box(black_box(msg).to_bytes()))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_to_bytes,
+    benchmark_encode_reused_buffer,
+    benchmark_message_ref_to_bytes,
+);
+
+criterion_main!(benches);
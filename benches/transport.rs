@@ -0,0 +1,61 @@
+//! Throughput benchmarks for `ZeroCopyTransport`.
+//!
+//! Reads a large in-memory buffer of CRLF-terminated lines through the
+//! transport, exercising line framing, validation and parsing together.
+//!
+//! Baseline (x86_64, release, criterion defaults), 10 000 lines:
+//! - `zero_copy_read/ping`: ~2.2 ms (~100 MiB/s)
+//! - `zero_copy_read/tagged_privmsg`: ~6.7 ms (~136 MiB/s)
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use slirc_proto::ZeroCopyTransport;
+
+const LINES: usize = 10_000;
+
+fn build_buffer(line: &str) -> Vec<u8> {
+    let mut buf = Vec::with_capacity((line.len() + 2) * LINES);
+    for _ in 0..LINES {
+        buf.extend_from_slice(line.as_bytes());
+        buf.extend_from_slice(b"\r\n");
+    }
+    buf
+}
+
+fn benchmark_zero_copy_read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("zero_copy_read");
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let inputs = [
+        ("ping", "PING :irc.example.com"),
+        (
+            "tagged_privmsg",
+            "@time=2023-01-01T00:00:00.000Z;msgid=abc123 :nick!user@host PRIVMSG #channel :Hello with tags!",
+        ),
+    ];
+
+    for (name, line) in inputs {
+        let buf = build_buffer(line);
+        group.throughput(Throughput::Bytes(buf.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &buf, |b, buf| {
+            b.iter(|| {
+                runtime.block_on(async {
+                    let mut transport = ZeroCopyTransport::new(black_box(buf.as_slice()));
+                    let mut count = 0;
+                    while let Some(result) = transport.next().await {
+                        black_box(result.unwrap());
+                        count += 1;
+                    }
+                    assert_eq!(count, LINES);
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_zero_copy_read);
+
+criterion_main!(benches);
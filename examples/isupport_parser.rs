@@ -57,7 +57,7 @@ impl ServerCapabilities {
         }
 
         // Case mapping (ascii, rfc1459, strict-rfc1459)
-        if let Some(casemap) = isupport.casemapping() {
+        if let Some(casemap) = isupport.casemapping_name() {
             self.casemapping = Some(casemap.to_string());
         }
 
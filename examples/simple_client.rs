@@ -115,20 +115,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         println!("→ {}", pong);
                         transport.write_message(&pong).await?;
                     }
-                    Command::PRIVMSG(target, text) => {
-                        if text.contains("hello") {
-                            // Respond to greetings
-                            let response = Message {
-                                tags: None,
-                                prefix: None,
-                                command: Command::PRIVMSG(
-                                    target.clone(),
-                                    "Hello there! 👋".to_string(),
-                                ),
-                            };
-                            println!("→ {}", response);
-                            transport.write_message(&response).await?;
-                        }
+                    Command::PRIVMSG(target, text) if text.contains("hello") => {
+                        // Respond to greetings
+                        let response = Message {
+                            tags: None,
+                            prefix: None,
+                            command: Command::PRIVMSG(
+                                target.clone(),
+                                "Hello there! 👋".to_string(),
+                            ),
+                        };
+                        println!("→ {}", response);
+                        transport.write_message(&response).await?;
                     }
                     _ => {}
                 }
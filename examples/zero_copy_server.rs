@@ -55,13 +55,11 @@ impl MessageProcessor {
             "PING" => {
                 // In a real server, you'd respond with PONG here
             }
-            "PRIVMSG" | "NOTICE" => {
+            "PRIVMSG" | "NOTICE" if msg.command.args.len() >= 2 => {
                 // Access args without allocation
-                if msg.command.args.len() >= 2 {
-                    let _target = msg.command.args[0];
-                    let _text = msg.command.args[1];
-                    // Route to appropriate channel/user handler
-                }
+                let _target = msg.command.args[0];
+                let _text = msg.command.args[1];
+                // Route to appropriate channel/user handler
             }
             "JOIN" | "PART" | "QUIT" => {
                 // Membership changes - update internal state
@@ -0,0 +1,157 @@
+//! Parsing of `CAP LS` replies, including CAP 302 capability values.
+
+use super::Capability;
+use crate::message::MessageRef;
+
+/// A capability as advertised by the server, with its optional value.
+///
+/// With CAP 302 servers may attach a value to a capability, such as
+/// `sasl=PLAIN,EXTERNAL` or `draft/multiline=max-bytes=4096`. Only the first
+/// `=` separates the name from the value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapabilityRef<'a> {
+    name: &'a str,
+    value: Option<&'a str>,
+}
+
+impl<'a> CapabilityRef<'a> {
+    /// Parse a single `name` or `name=value` token.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::caps::CapabilityRef;
+    ///
+    /// let cap = CapabilityRef::parse("draft/multiline=max-bytes=4096");
+    /// assert_eq!(cap.name(), "draft/multiline");
+    /// assert_eq!(cap.value(), Some("max-bytes=4096"));
+    /// ```
+    pub fn parse(token: &'a str) -> Self {
+        match token.split_once('=') {
+            Some((name, value)) => CapabilityRef {
+                name,
+                value: Some(value),
+            },
+            None => CapabilityRef {
+                name: token,
+                value: None,
+            },
+        }
+    }
+
+    /// Get the capability name.
+    #[must_use]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Get the capability value, if one was advertised.
+    ///
+    /// An empty value (`name=`) is returned as `Some("")`.
+    #[must_use]
+    pub fn value(&self) -> Option<&'a str> {
+        self.value
+    }
+
+    /// Get the known [`Capability`] for this name.
+    #[must_use]
+    pub fn capability(&self) -> Capability {
+        Capability::from(self.name)
+    }
+}
+
+/// One `CAP LS` reply line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapLs<'a> {
+    /// Capabilities listed on this line, in order.
+    pub caps: Vec<CapabilityRef<'a>>,
+    /// Whether the line ended with the `*` continuation marker, meaning more
+    /// `CAP LS` lines follow.
+    pub more: bool,
+}
+
+/// Parse a `CAP LS` reply line such as
+/// `:irc.example.com CAP * LS * :multi-prefix sasl=PLAIN,EXTERNAL`.
+///
+/// Capability values are preserved. In CAP 302 a server splits long lists
+/// over several lines, marking all but the last with `*` before the list;
+/// this is reported as [`CapLs::more`]. Returns `None` if the line is not a
+/// `CAP LS` reply.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::caps::parse_cap_ls;
+///
+/// let ls = parse_cap_ls(":irc.example.com CAP * LS :sasl=PLAIN,EXTERNAL away-notify").unwrap();
+/// assert!(!ls.more);
+/// assert_eq!(ls.caps[0].name(), "sasl");
+/// assert_eq!(ls.caps[0].value(), Some("PLAIN,EXTERNAL"));
+/// assert_eq!(ls.caps[1].value(), None);
+/// ```
+pub fn parse_cap_ls(line: &str) -> Option<CapLs<'_>> {
+    let msg = MessageRef::parse(line).ok()?;
+    if !msg.command.name.eq_ignore_ascii_case("CAP") {
+        return None;
+    }
+    let (more, list) = match msg.command.args.as_slice() {
+        [_, sub, "*", list] if sub.eq_ignore_ascii_case("LS") => (true, *list),
+        [_, sub, list] if sub.eq_ignore_ascii_case("LS") => (false, *list),
+        _ => return None,
+    };
+    Some(CapLs {
+        caps: list
+            .split_ascii_whitespace()
+            .map(CapabilityRef::parse)
+            .collect(),
+        more,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_bearing_caps() {
+        let ls = parse_cap_ls(
+            ":irc.example.com CAP * LS :sasl=PLAIN,EXTERNAL draft/multiline=max-bytes=4096,max-lines=100\r\n",
+        )
+        .unwrap();
+        assert!(!ls.more);
+        assert_eq!(ls.caps.len(), 2);
+        assert_eq!(ls.caps[0].capability(), Capability::Sasl);
+        assert_eq!(ls.caps[0].value(), Some("PLAIN,EXTERNAL"));
+        assert_eq!(ls.caps[1].capability(), Capability::Multiline);
+        assert_eq!(ls.caps[1].value(), Some("max-bytes=4096,max-lines=100"));
+    }
+
+    #[test]
+    fn test_valueless_caps() {
+        let ls = parse_cap_ls(":irc.example.com CAP alice LS :multi-prefix  away-notify").unwrap();
+        let names: Vec<_> = ls.caps.iter().map(CapabilityRef::name).collect();
+        assert_eq!(names, ["multi-prefix", "away-notify"]);
+        assert!(ls.caps.iter().all(|c| c.value().is_none()));
+
+        // Empty value is distinct from no value
+        assert_eq!(CapabilityRef::parse("sts=").value(), Some(""));
+    }
+
+    #[test]
+    fn test_continuation() {
+        let first = parse_cap_ls(":irc.example.com CAP * LS * :multi-prefix sasl=PLAIN").unwrap();
+        assert!(first.more);
+        assert_eq!(first.caps.len(), 2);
+
+        let last = parse_cap_ls(":irc.example.com CAP * LS :server-time").unwrap();
+        assert!(!last.more);
+        assert_eq!(last.caps, [CapabilityRef::parse("server-time")]);
+    }
+
+    #[test]
+    fn test_not_cap_ls() {
+        assert_eq!(parse_cap_ls(":irc.example.com CAP * ACK :sasl"), None);
+        assert_eq!(parse_cap_ls(":irc.example.com PRIVMSG * LS :sasl"), None);
+        assert_eq!(parse_cap_ls(""), None);
+    }
+}
@@ -7,11 +7,17 @@
 //! - IRCv3 Capability Negotiation: <https://ircv3.net/specs/extensions/capability-negotiation>
 //! - Individual capability specifications: <https://ircv3.net/irc/>
 
+mod ls;
 mod negotiation;
 mod registry;
+mod set;
 
-pub use negotiation::{apply_changes, format_cap_del, format_cap_new, parse_request};
+pub use ls::{parse_cap_ls, CapLs, CapabilityRef};
+pub use negotiation::{
+    apply_changes, build_cap_req, format_cap_del, format_cap_new, negotiate, parse_request,
+};
 pub use registry::{get_all_names, get_cap_list, is_supported, CapabilityDef, CAPABILITIES};
+pub use set::CapabilitySet;
 
 /// Known IRCv3 capability types.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -3,6 +3,9 @@
 use std::collections::HashSet;
 
 use super::registry::is_supported;
+use super::Capability;
+use crate::command::{CapSubCommand, Command};
+use crate::Message;
 
 /// Parse a CAP REQ request and separate accepted from rejected capabilities.
 ///
@@ -56,6 +59,35 @@ pub fn apply_changes(capabilities: &mut HashSet<String>, changes: &[String]) ->
     modified
 }
 
+/// Compute the capabilities to request from the server.
+///
+/// Returns the desired capabilities the server advertises, in the order they
+/// were desired. Unavailable and duplicate entries are dropped.
+pub fn negotiate(desired: &[&str], available: &[Capability]) -> Vec<String> {
+    let mut request: Vec<String> = Vec::new();
+
+    for &cap in desired {
+        let offered = available.iter().any(|a| a.as_ref() == cap);
+        if offered && !request.iter().any(|r| r == cap) {
+            request.push(cap.to_string());
+        }
+    }
+
+    request
+}
+
+/// Build a `CAP REQ` message for the given capabilities.
+///
+/// Returns `None` when there is nothing to request.
+pub fn build_cap_req<S: AsRef<str>>(caps: &[S]) -> Option<Message> {
+    if caps.is_empty() {
+        return None;
+    }
+
+    let list = caps.iter().map(AsRef::as_ref).collect::<Vec<_>>().join(" ");
+    Some(Command::CAP(None, CapSubCommand::REQ, None, Some(list)).into())
+}
+
 /// Format a CAP NEW message for notifying clients of new capabilities.
 pub fn format_cap_new(nickname: &str, server_name: &str, new_caps: &[&str]) -> String {
     format!(
@@ -107,4 +139,35 @@ mod tests {
         assert!(apply_changes(&mut caps, &removal));
         assert!(!caps.contains("sasl"));
     }
+
+    #[test]
+    fn test_negotiate_skips_unavailable() {
+        let available = [
+            Capability::Sasl,
+            Capability::MultiPrefix,
+            Capability::ServerTime,
+        ];
+        let desired = [
+            "server-time",
+            "draft/multiline",
+            "multi-prefix",
+            "server-time",
+        ];
+        let request = negotiate(&desired, &available);
+        assert_eq!(request, vec!["server-time", "multi-prefix"]);
+    }
+
+    #[test]
+    fn test_negotiate_custom_capability() {
+        let available = [Capability::from("vendor/thing")];
+        assert_eq!(negotiate(&["vendor/thing"], &available), ["vendor/thing"]);
+        assert!(negotiate(&["sasl"], &available).is_empty());
+    }
+
+    #[test]
+    fn test_build_cap_req() {
+        let msg = build_cap_req(&["server-time", "multi-prefix"]).unwrap();
+        assert_eq!(msg.to_string(), "CAP REQ :server-time multi-prefix\r\n");
+        assert!(build_cap_req::<&str>(&[]).is_none());
+    }
 }
@@ -0,0 +1,165 @@
+//! Tracking of available and enabled capabilities across a connection.
+
+use std::collections::{HashMap, HashSet};
+
+use super::CapabilityRef;
+
+/// The capabilities a server offers and those enabled on the connection.
+///
+/// Feed it the server's `CAP LS`, `ACK`, `NAK`, `NEW` and `DEL` replies to
+/// keep both sets current. Capability values from CAP 302 are kept for
+/// available capabilities.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::caps::CapabilitySet;
+///
+/// let mut caps = CapabilitySet::new();
+/// caps.offer("sasl", Some("PLAIN"));
+/// caps.ack("sasl");
+/// assert!(caps.is_enabled("sasl"));
+///
+/// caps.ack("-sasl");
+/// assert!(!caps.is_enabled("sasl"));
+/// assert_eq!(caps.value("sasl"), Some("PLAIN"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CapabilitySet {
+    available: HashMap<String, Option<String>>,
+    enabled: HashSet<String>,
+}
+
+impl CapabilitySet {
+    /// Create an empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a capability offered in `CAP LS`, replacing any previous value.
+    pub fn offer(&mut self, name: &str, value: Option<&str>) {
+        self.available
+            .insert(name.to_string(), value.map(str::to_string));
+    }
+
+    /// Apply a capability from `CAP ACK`.
+    ///
+    /// A `-` prefix confirms that the capability was disabled; otherwise it
+    /// is enabled.
+    pub fn ack(&mut self, name: &str) {
+        match name.strip_prefix('-') {
+            Some(name) => {
+                self.enabled.remove(name);
+            }
+            None => {
+                self.enabled.insert(name.to_string());
+            }
+        }
+    }
+
+    /// Apply a capability from `CAP NAK`.
+    ///
+    /// A rejected request changes nothing, so the set is left as it was;
+    /// this exists so ACK and NAK replies can be handled alike.
+    pub fn nak(&mut self, _name: &str) {}
+
+    /// Record capabilities announced with `CAP NEW`.
+    pub fn handle_new(&mut self, caps: &[CapabilityRef<'_>]) {
+        for cap in caps {
+            self.offer(cap.name(), cap.value());
+        }
+    }
+
+    /// Remove capabilities withdrawn with `CAP DEL`.
+    ///
+    /// They are no longer available and no longer enabled.
+    pub fn handle_del(&mut self, names: &[&str]) {
+        for name in names {
+            self.available.remove(*name);
+            self.enabled.remove(*name);
+        }
+    }
+
+    /// Check whether a capability is enabled.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.enabled.contains(name)
+    }
+
+    /// Check whether the server offers a capability.
+    #[must_use]
+    pub fn is_available(&self, name: &str) -> bool {
+        self.available.contains_key(name)
+    }
+
+    /// Get the value advertised for an available capability, if any.
+    #[must_use]
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.available.get(name)?.as_deref()
+    }
+
+    /// Iterate over the names of enabled capabilities.
+    pub fn enabled(&self) -> impl Iterator<Item = &str> + '_ {
+        self.enabled.iter().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::caps::parse_cap_ls;
+
+    #[test]
+    fn test_negotiation() {
+        let mut caps = CapabilitySet::new();
+        let ls =
+            parse_cap_ls(":irc.example.com CAP * LS :sasl=PLAIN,EXTERNAL server-time").unwrap();
+        for cap in &ls.caps {
+            caps.offer(cap.name(), cap.value());
+        }
+        assert!(caps.is_available("sasl"));
+        assert_eq!(caps.value("sasl"), Some("PLAIN,EXTERNAL"));
+        assert!(!caps.is_enabled("sasl"));
+
+        // CAP REQ :sasl server-time -> CAP ACK :sasl server-time
+        for name in "sasl server-time".split(' ') {
+            caps.ack(name);
+        }
+        assert!(caps.is_enabled("sasl"));
+        assert!(caps.is_enabled("server-time"));
+
+        // CAP DEL :sasl
+        caps.handle_del(&["sasl"]);
+        assert!(!caps.is_enabled("sasl"));
+        assert!(!caps.is_available("sasl"));
+        assert_eq!(caps.enabled().collect::<Vec<_>>(), ["server-time"]);
+    }
+
+    #[test]
+    fn test_disable_and_nak() {
+        let mut caps = CapabilitySet::new();
+        caps.offer("echo-message", None);
+        caps.ack("echo-message");
+
+        caps.nak("-echo-message");
+        assert!(caps.is_enabled("echo-message"));
+
+        caps.ack("-echo-message");
+        assert!(!caps.is_enabled("echo-message"));
+        assert!(caps.is_available("echo-message"));
+    }
+
+    #[test]
+    fn test_new() {
+        let mut caps = CapabilitySet::new();
+        caps.handle_new(&[
+            CapabilityRef::parse("draft/multiline=max-bytes=4096"),
+            CapabilityRef::parse("away-notify"),
+        ]);
+        assert_eq!(caps.value("draft/multiline"), Some("max-bytes=4096"));
+        assert!(caps.is_available("away-notify"));
+        assert_eq!(caps.value("away-notify"), None);
+        assert!(!caps.is_enabled("away-notify"));
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! IRC uses a special case-insensitive comparison where some characters
 //! are considered equivalent (e.g., `[` and `{`). This implements the
-//! `rfc1459` case mapping which is the most common.
+//! `rfc1459` case mapping which is the most common; [`CaseMapping`] selects
+//! between the mappings servers advertise in `ISUPPORT CASEMAPPING`.
 
 /// Convert a single character to IRC lowercase using RFC 1459 case mapping.
 ///
@@ -30,21 +31,81 @@ pub const fn irc_lower_char(c: char) -> char {
 /// - `]` → `}`
 /// - `\` → `|`
 /// - `~` → `^`
+///
+/// Use [`irc_to_lower_with`] to follow the server's advertised mapping.
 pub fn irc_to_lower(s: &str) -> String {
-    s.chars().map(irc_lower_char).collect()
+    irc_to_lower_with(s, CaseMapping::Rfc1459)
+}
+
+/// Convert a string to IRC lowercase using the given case mapping.
+pub fn irc_to_lower_with(s: &str, mapping: CaseMapping) -> String {
+    mapping.to_lower(s)
 }
 
 /// Compare two strings using IRC case-insensitive comparison.
 ///
 /// Uses the RFC 1459 case mapping where certain characters are equivalent.
+/// Use [`irc_eq_with`] to follow the server's advertised mapping.
 pub fn irc_eq(a: &str, b: &str) -> bool {
-    if a.len() != b.len() {
-        return false;
+    irc_eq_with(a, b, CaseMapping::Rfc1459)
+}
+
+/// Compare two strings case-insensitively using the given case mapping.
+pub fn irc_eq_with(a: &str, b: &str, mapping: CaseMapping) -> bool {
+    mapping.eq(a, b)
+}
+
+/// A case mapping advertised in the `CASEMAPPING` ISUPPORT token.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CaseMapping {
+    /// `ascii`: only `A-Z` map to `a-z`.
+    Ascii,
+    /// `rfc1459`: ASCII plus `[]\~` map to `{}|^`.
+    #[default]
+    Rfc1459,
+    /// `strict-rfc1459`: ASCII plus `[]\` map to `{}|` (not `~`).
+    StrictRfc1459,
+}
+
+impl CaseMapping {
+    /// Parse a `CASEMAPPING` value such as `rfc1459` or `ascii`.
+    ///
+    /// Returns `None` for unsupported mappings (e.g. `rfc7613`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ascii" => Some(Self::Ascii),
+            "rfc1459" => Some(Self::Rfc1459),
+            // `rfc1459-strict` is a non-standard spelling seen in the wild
+            "strict-rfc1459" | "rfc1459-strict" => Some(Self::StrictRfc1459),
+            _ => None,
+        }
     }
 
-    a.chars()
-        .zip(b.chars())
-        .all(|(ca, cb)| irc_lower_char(ca) == irc_lower_char(cb))
+    /// Convert a single character to lowercase under this mapping.
+    #[inline]
+    pub const fn lower_char(self, c: char) -> char {
+        match self {
+            Self::Ascii => c.to_ascii_lowercase(),
+            Self::Rfc1459 => irc_lower_char(c),
+            Self::StrictRfc1459 => match c {
+                '~' => '~',
+                _ => irc_lower_char(c),
+            },
+        }
+    }
+
+    /// Convert a string to lowercase under this mapping.
+    pub fn to_lower(self, s: &str) -> String {
+        s.chars().map(|c| self.lower_char(c)).collect()
+    }
+
+    /// Compare two strings case-insensitively under this mapping.
+    pub fn eq(self, a: &str, b: &str) -> bool {
+        a.len() == b.len()
+            && a.chars()
+                .zip(b.chars())
+                .all(|(ca, cb)| self.lower_char(ca) == self.lower_char(cb))
+    }
 }
 
 #[cfg(test)]
@@ -92,4 +153,45 @@ mod tests {
         assert!(!irc_eq("hello", "world"));
         assert!(!irc_eq("short", "longer"));
     }
+
+    #[test]
+    fn test_case_mapping() {
+        assert_eq!(CaseMapping::parse("ascii"), Some(CaseMapping::Ascii));
+        assert_eq!(CaseMapping::parse("rfc1459"), Some(CaseMapping::Rfc1459));
+        assert_eq!(CaseMapping::parse("rfc7613"), None);
+        for name in ["strict-rfc1459", "rfc1459-strict"] {
+            assert_eq!(CaseMapping::parse(name), Some(CaseMapping::StrictRfc1459));
+        }
+
+        assert!(CaseMapping::Rfc1459.eq("Nick[a]~", "nick{a}^"));
+        assert!(!CaseMapping::Ascii.eq("nick[a]", "nick{a}"));
+        assert!(CaseMapping::Ascii.eq("NICK", "nick"));
+        assert!(CaseMapping::StrictRfc1459.eq("[x]", "{x}"));
+        assert!(!CaseMapping::StrictRfc1459.eq("x~", "x^"));
+        assert_eq!(CaseMapping::StrictRfc1459.to_lower("A[~"), "a{~");
+    }
+
+    #[test]
+    fn test_irc_eq_with_mapping() {
+        assert!(irc_eq_with("Nick[]", "nick{}", CaseMapping::Rfc1459));
+        assert!(irc_eq_with("Nick[]", "nick{}", CaseMapping::StrictRfc1459));
+        assert!(!irc_eq_with("Nick[]", "nick{}", CaseMapping::Ascii));
+        assert!(irc_eq_with("Nick[]", "nick[]", CaseMapping::Ascii));
+
+        assert!(irc_eq_with("Nick~", "nick^", CaseMapping::Rfc1459));
+        assert!(!irc_eq_with("Nick~", "nick^", CaseMapping::StrictRfc1459));
+
+        assert_eq!(
+            irc_to_lower_with("Nick[]\\~", CaseMapping::Ascii),
+            "nick[]\\~"
+        );
+        assert_eq!(
+            irc_to_lower_with("Nick[]\\~", CaseMapping::Rfc1459),
+            "nick{}|^"
+        );
+        assert_eq!(
+            irc_to_lower_with("Nick[]\\~", CaseMapping::StrictRfc1459),
+            "nick{}|~"
+        );
+    }
 }
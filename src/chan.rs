@@ -4,6 +4,7 @@
 //!
 //! # Reference
 //! - RFC 2812 Section 1.3: Channel names
+//! - RFC 2811 Section 3.2: Safe channels
 
 /// Extension trait for checking if a string is a valid IRC channel name.
 pub trait ChannelExt {
@@ -53,6 +54,83 @@ impl ChannelExt for String {
     }
 }
 
+/// Length of the server-assigned channel ID of a safe channel.
+pub const SAFE_CHANNEL_ID_LEN: usize = 5;
+
+/// A safe (`!`) channel name split into its ID and short name.
+///
+/// Safe channels are named `!<channel id><short name>`, where the channel ID
+/// is five characters from `A-Z0-9` assigned by the server when the channel
+/// is created. Only networks listing `!` in `CHANTYPES` support them.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::chan::SafeChannel;
+///
+/// let chan = SafeChannel::parse("!ABCDEchan").unwrap();
+/// assert_eq!(chan.id, "ABCDE");
+/// assert_eq!(chan.short_name, "chan");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SafeChannel<'a> {
+    /// The five-character channel ID.
+    pub id: &'a str,
+    /// The short name chosen by the creator.
+    pub short_name: &'a str,
+}
+
+impl<'a> SafeChannel<'a> {
+    /// Parse a full safe channel name like `!ABCDEchan`.
+    ///
+    /// Returns `None` if the name is not a valid channel name, lacks the `!`
+    /// prefix, has a malformed channel ID, or has an empty short name.
+    pub fn parse(name: &'a str) -> Option<Self> {
+        if !name.is_channel_name() {
+            return None;
+        }
+        let rest = name.strip_prefix('!')?;
+        let id = rest.get(..SAFE_CHANNEL_ID_LEN)?;
+        if !id
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        {
+            return None;
+        }
+        let short_name = &rest[SAFE_CHANNEL_ID_LEN..];
+        if short_name.is_empty() {
+            return None;
+        }
+        Some(Self { id, short_name })
+    }
+
+    /// Build the `!!<short name>` form used to ask the server to create a
+    /// new safe channel.
+    ///
+    /// The server replies with a JOIN for the full name, which carries the
+    /// assigned ID and can be read back with [`SafeChannel::parse`].
+    pub fn creation_request(short_name: &str) -> Option<String> {
+        let name = format!("!!{}", short_name);
+        let valid = !short_name.is_empty()
+            && !short_name.starts_with('!')
+            && name.chars().count() + SAFE_CHANNEL_ID_LEN - 1 <= 50
+            && name.as_str().is_channel_name();
+        valid.then_some(name)
+    }
+
+    /// Check whether `name` is a `!!<short name>` creation request.
+    pub fn is_creation_request(name: &str) -> bool {
+        name.strip_prefix("!!")
+            .is_some_and(|short| !short.is_empty() && !short.starts_with('!'))
+    }
+}
+
+impl std::fmt::Display for SafeChannel<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "!{}{}", self.id, self.short_name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,4 +150,38 @@ mod tests {
         assert!(!"#chan,nel".is_channel_name()); // comma
         assert!(!"".is_channel_name()); // empty
     }
+
+    #[test]
+    fn test_safe_channel_parse() {
+        let chan = SafeChannel::parse("!ABCDEchan").unwrap();
+        assert_eq!(chan.id, "ABCDE");
+        assert_eq!(chan.short_name, "chan");
+        assert_eq!(chan.to_string(), "!ABCDEchan");
+
+        let chan = SafeChannel::parse("!1A2B3rust").unwrap();
+        assert_eq!(chan.id, "1A2B3");
+    }
+
+    #[test]
+    fn test_safe_channel_invalid() {
+        assert!(SafeChannel::parse("#ABCDEchan").is_none()); // wrong prefix
+        assert!(SafeChannel::parse("!ABCDE").is_none()); // no short name
+        assert!(SafeChannel::parse("!ABCD").is_none()); // short ID
+        assert!(SafeChannel::parse("!abcdechan").is_none()); // lowercase ID
+        assert!(SafeChannel::parse("!ABCDEch an").is_none()); // space
+        assert!(SafeChannel::parse("!!chan").is_none()); // creation request
+    }
+
+    #[test]
+    fn test_safe_channel_creation_request() {
+        assert_eq!(
+            SafeChannel::creation_request("chan"),
+            Some("!!chan".to_string())
+        );
+        assert!(SafeChannel::is_creation_request("!!chan"));
+        assert!(!SafeChannel::is_creation_request("!ABCDEchan"));
+        assert!(SafeChannel::creation_request("").is_none());
+        assert!(SafeChannel::creation_request("bad,name").is_none());
+        assert!(SafeChannel::creation_request(&"x".repeat(45)).is_none());
+    }
 }
@@ -0,0 +1,319 @@
+//! Events produced by [`IrcClient`](super::IrcClient).
+//!
+//! An [`Event`] is a semantic view of an incoming [`Message`]: consumers
+//! match on what happened (someone joined, a CTCP arrived) rather than on
+//! raw [`Command`] variants.
+
+use crate::command::Command;
+use crate::ctcp::CtcpOwned;
+use crate::mode::{ChannelMode, Mode, UserMode};
+use crate::prefix::Prefix;
+use crate::response::Response;
+use crate::Message;
+
+/// An event received by [`IrcClient`](super::IrcClient).
+///
+/// Decoded from a [`Message`] via [`From`]; anything without a dedicated
+/// variant is returned as [`Event::Raw`].
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::client::Event;
+/// use slirc_proto::Message;
+///
+/// let msg: Message = ":nick!user@host PRIVMSG #rust :hello".parse().unwrap();
+/// match Event::from(msg) {
+///     Event::Privmsg { target, text, .. } => {
+///         assert_eq!(target, "#rust");
+///         assert_eq!(text, "hello");
+///     }
+///     other => panic!("unexpected event: {:?}", other),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Event {
+    /// Registration completed (`RPL_WELCOME` received).
+    Registered,
+    /// The `RPL_ISUPPORT` burst has ended, so
+    /// [`IrcClient::isupport`](super::IrcClient::isupport) is final.
+    IsupportComplete,
+    /// Configured auto-join channels that were not joined because they
+    /// exceed the server's `CHANLIMIT`.
+    AutoJoinSkipped {
+        /// The skipped channels, in configuration order.
+        channels: Vec<String>,
+    },
+    /// A PRIVMSG that is not a CTCP.
+    Privmsg {
+        /// Message source.
+        source: Option<Prefix>,
+        /// Channel or nickname the message was sent to.
+        target: String,
+        /// Message text.
+        text: String,
+    },
+    /// A NOTICE that is not a CTCP reply.
+    Notice {
+        /// Message source.
+        source: Option<Prefix>,
+        /// Channel or nickname the notice was sent to.
+        target: String,
+        /// Notice text.
+        text: String,
+    },
+    /// A user joined a channel.
+    Join {
+        /// The joining user.
+        source: Option<Prefix>,
+        /// The channel joined.
+        channel: String,
+    },
+    /// A user left a channel.
+    Part {
+        /// The departing user.
+        source: Option<Prefix>,
+        /// The channel left.
+        channel: String,
+        /// Optional part message.
+        reason: Option<String>,
+    },
+    /// A user disconnected from the network.
+    Quit {
+        /// The quitting user.
+        source: Option<Prefix>,
+        /// Optional quit message.
+        reason: Option<String>,
+    },
+    /// A user changed nickname.
+    Nick {
+        /// The user, under their old nickname.
+        source: Option<Prefix>,
+        /// The new nickname.
+        new_nick: String,
+    },
+    /// A channel or user mode change.
+    Mode {
+        /// Who changed the mode.
+        source: Option<Prefix>,
+        /// The channel or nickname whose modes changed.
+        target: String,
+        /// The parsed mode changes.
+        modes: ModeChange,
+    },
+    /// A numeric reply from the server.
+    Numeric {
+        /// The server that sent the reply.
+        source: Option<Prefix>,
+        /// The numeric response code.
+        response: Response,
+        /// Reply arguments (the first is usually our nickname).
+        args: Vec<String>,
+    },
+    /// A CTCP request (in PRIVMSG) or reply (in NOTICE).
+    Ctcp {
+        /// Message source.
+        source: Option<Prefix>,
+        /// Channel or nickname the CTCP was sent to.
+        target: String,
+        /// The parsed CTCP payload.
+        ctcp: CtcpOwned,
+        /// `true` if this arrived as a NOTICE (i.e. a CTCP reply).
+        is_reply: bool,
+    },
+    /// Any other message.
+    ///
+    /// Boxed to reduce enum size variance (Message is large).
+    Raw(Box<Message>),
+}
+
+/// Mode changes carried by [`Event::Mode`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModeChange {
+    /// Channel mode changes.
+    Channel(Vec<Mode<ChannelMode>>),
+    /// User mode changes.
+    User(Vec<Mode<UserMode>>),
+}
+
+impl From<Message> for Event {
+    fn from(msg: Message) -> Self {
+        let source = msg.prefix;
+        match msg.command {
+            Command::PRIVMSG(target, text) => match CtcpOwned::parse(&text) {
+                Some(ctcp) => Event::Ctcp {
+                    source,
+                    target,
+                    ctcp,
+                    is_reply: false,
+                },
+                None => Event::Privmsg {
+                    source,
+                    target,
+                    text,
+                },
+            },
+            Command::NOTICE(target, text) => match CtcpOwned::parse(&text) {
+                Some(ctcp) => Event::Ctcp {
+                    source,
+                    target,
+                    ctcp,
+                    is_reply: true,
+                },
+                None => Event::Notice {
+                    source,
+                    target,
+                    text,
+                },
+            },
+            Command::JOIN(channel, _, _) => Event::Join { source, channel },
+            Command::PART(channel, reason) => Event::Part {
+                source,
+                channel,
+                reason,
+            },
+            Command::QUIT(reason) => Event::Quit { source, reason },
+            Command::NICK(new_nick) => Event::Nick { source, new_nick },
+            Command::ChannelMODE(target, modes) => Event::Mode {
+                source,
+                target,
+                modes: ModeChange::Channel(modes),
+            },
+            Command::UserMODE(target, modes) => Event::Mode {
+                source,
+                target,
+                modes: ModeChange::User(modes),
+            },
+            Command::Response(response, args) => Event::Numeric {
+                source,
+                response,
+                args,
+            },
+            command => Event::Raw(Box::new(Message {
+                tags: msg.tags,
+                prefix: source,
+                command,
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ctcp::CtcpKind;
+
+    fn decode(raw: &str) -> Event {
+        Event::from(raw.parse::<Message>().unwrap())
+    }
+
+    #[test]
+    fn test_privmsg() {
+        match decode(":nick!user@host PRIVMSG #rust :hello world") {
+            Event::Privmsg {
+                source,
+                target,
+                text,
+            } => {
+                assert_eq!(source, Some(Prefix::new_from_str("nick!user@host")));
+                assert_eq!(target, "#rust");
+                assert_eq!(text, "hello world");
+            }
+            other => panic!("Expected Privmsg, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_privmsg_ctcp() {
+        match decode(":nick!user@host PRIVMSG #rust :\x01ACTION waves\x01") {
+            Event::Ctcp {
+                target,
+                ctcp,
+                is_reply,
+                ..
+            } => {
+                assert_eq!(target, "#rust");
+                assert_eq!(ctcp.kind, CtcpKind::Action);
+                assert_eq!(ctcp.params.as_deref(), Some("waves"));
+                assert!(!is_reply);
+            }
+            other => panic!("Expected Ctcp, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_notice_ctcp_reply() {
+        match decode(":nick!user@host NOTICE me :\x01VERSION slirc 1.0\x01") {
+            Event::Ctcp { ctcp, is_reply, .. } => {
+                assert_eq!(ctcp.kind, CtcpKind::Version);
+                assert!(is_reply);
+            }
+            other => panic!("Expected Ctcp, got {:?}", other),
+        }
+        assert!(matches!(
+            decode(":server NOTICE * :Looking up your hostname"),
+            Event::Notice { .. }
+        ));
+    }
+
+    #[test]
+    fn test_membership_events() {
+        assert!(matches!(
+            decode(":nick!u@h JOIN #rust"),
+            Event::Join { channel, .. } if channel == "#rust"
+        ));
+        assert!(matches!(
+            decode(":nick!u@h PART #rust :bye"),
+            Event::Part { channel, reason: Some(reason), .. } if channel == "#rust" && reason == "bye"
+        ));
+        assert!(matches!(
+            decode(":nick!u@h QUIT :Quit: leaving"),
+            Event::Quit {
+                reason: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            decode(":old!u@h NICK new"),
+            Event::Nick { new_nick, .. } if new_nick == "new"
+        ));
+    }
+
+    #[test]
+    fn test_mode_events() {
+        assert!(matches!(
+            decode(":op!u@h MODE #rust +o nick"),
+            Event::Mode {
+                modes: ModeChange::Channel(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            decode(":nick MODE nick +i"),
+            Event::Mode {
+                modes: ModeChange::User(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_numeric_and_raw() {
+        match decode(":server 001 nick :Welcome") {
+            Event::Numeric { response, args, .. } => {
+                assert_eq!(response, Response::RPL_WELCOME);
+                assert_eq!(args, vec!["nick", "Welcome"]);
+            }
+            other => panic!("Expected Numeric, got {:?}", other),
+        }
+
+        match decode("@time=2023-01-01T00:00:00Z :server PONG server :token") {
+            Event::Raw(msg) => {
+                assert_eq!(msg.server_time(), Some("2023-01-01T00:00:00Z"));
+                assert!(matches!(msg.command, Command::PONG(..)));
+            }
+            other => panic!("Expected Raw, got {:?}", other),
+        }
+    }
+}
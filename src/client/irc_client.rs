@@ -0,0 +1,212 @@
+//! [`IrcClient`] implementation.
+
+use std::collections::VecDeque;
+
+use tracing::warn;
+
+use crate::command::Command;
+use crate::isupport::{parse_params, Isupport};
+use crate::message::MessageRef;
+use crate::response::Response;
+use crate::state::{
+    ConnectionState, HandshakeAction, HandshakeConfig, HandshakeError, HandshakeMachine,
+};
+use crate::transport::Transport;
+use crate::Message;
+
+use super::{ClientError, Event};
+
+/// High-level IRC client wiring a [`Transport`] to a [`HandshakeMachine`].
+///
+/// Call [`connect`](Self::connect) once to register, then loop on
+/// [`next_event`](Self::next_event). `PING` is answered automatically and
+/// never surfaced; `RPL_ISUPPORT` tokens are accumulated and available
+/// through [`isupport`](Self::isupport).
+pub struct IrcClient {
+    transport: Transport,
+    handshake: HandshakeMachine,
+    /// `KEY[=VALUE]` tokens of all `RPL_ISUPPORT` replies, merged as received.
+    isupport_tokens: Vec<String>,
+    /// Events received or produced but not yet returned.
+    pending: VecDeque<Event>,
+}
+
+impl IrcClient {
+    /// Create a client over an already-connected transport.
+    #[must_use]
+    pub fn new(transport: Transport, config: HandshakeConfig) -> Self {
+        Self {
+            transport,
+            handshake: HandshakeMachine::new(config),
+            isupport_tokens: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Get the current connection state.
+    #[must_use]
+    pub fn state(&self) -> &ConnectionState {
+        self.handshake.state()
+    }
+
+    /// Get the underlying handshake state machine (e.g. to inspect enabled caps).
+    #[must_use]
+    pub fn handshake(&self) -> &HandshakeMachine {
+        &self.handshake
+    }
+
+    /// Get the ISUPPORT tokens accumulated from all `RPL_ISUPPORT` replies.
+    ///
+    /// Each reply is [merged](Isupport::merge) as it arrives, so later
+    /// tokens override earlier ones with the same key and negated tokens such
+    /// as `-NICKLEN` remove them.
+    #[must_use]
+    pub fn isupport(&self) -> Isupport<'_> {
+        let tokens: Vec<&str> = self.isupport_tokens.iter().map(String::as_str).collect();
+        parse_params(&tokens)
+    }
+
+    /// Consume the client and return the underlying transport.
+    ///
+    /// Useful for upgrading to a zero-copy transport after registration.
+    #[must_use]
+    pub fn into_transport(self) -> Transport {
+        self.transport
+    }
+
+    /// Perform the connection handshake (CAP, SASL, NICK/USER) until `RPL_WELCOME`.
+    ///
+    /// Messages received during the handshake are queued and returned by
+    /// subsequent calls to [`next_event`](Self::next_event), followed by
+    /// [`Event::Registered`].
+    pub async fn connect(&mut self) -> Result<(), ClientError> {
+        let actions = self.handshake.start();
+        self.apply(actions).await?;
+
+        loop {
+            let msg = match self.transport.read_message().await? {
+                Some(msg) => msg,
+                None => return Err(ClientError::ClosedDuringRegistration),
+            };
+
+            if self.handle_internal(&msg).await? {
+                continue;
+            }
+
+            if let Command::ERROR(ref reason) = msg.command {
+                return Err(HandshakeError::ServerError(reason.clone()).into());
+            }
+
+            let actions = self.feed_handshake(&msg)?;
+            self.pending.push_back(Event::from(msg));
+
+            if self.apply(actions).await? {
+                self.pending.push_back(Event::Registered);
+                return Ok(());
+            }
+        }
+    }
+
+    /// Send a message to the server.
+    pub async fn send(&mut self, message: Message) -> Result<(), ClientError> {
+        self.transport
+            .write_message(&message)
+            .await
+            .map_err(ClientError::Write)
+    }
+
+    /// Wait for the next event from the server.
+    ///
+    /// Returns `Ok(None)` when the connection is closed.
+    pub async fn next_event(&mut self) -> Result<Option<Event>, ClientError> {
+        if let Some(event) = self.pending.pop_front() {
+            return Ok(Some(event));
+        }
+
+        loop {
+            let msg = match self.transport.read_message().await? {
+                Some(msg) => msg,
+                None => return Ok(None),
+            };
+
+            if self.handle_internal(&msg).await? {
+                continue;
+            }
+
+            // Auto-join and the end of the ISUPPORT burst are only
+            // signalled after registration.
+            let actions = self.feed_handshake(&msg)?;
+            self.pending.push_back(Event::from(msg));
+            self.apply(actions).await?;
+            return Ok(self.pending.pop_front());
+        }
+    }
+
+    /// Handle messages the client takes care of itself.
+    ///
+    /// Returns `true` if the message was consumed and should not be surfaced.
+    async fn handle_internal(&mut self, msg: &Message) -> Result<bool, ClientError> {
+        if let Command::PING(ref server, _) = msg.command {
+            self.send(Message::pong(server.clone())).await?;
+            return Ok(true);
+        }
+
+        if let Command::Response(Response::RPL_ISUPPORT, ref args) = msg.command {
+            self.merge_isupport(args);
+        }
+
+        Ok(false)
+    }
+
+    /// Merge the arguments of an `RPL_ISUPPORT` reply into the stored tokens.
+    fn merge_isupport(&mut self, args: &[String]) {
+        let tokens: Vec<&str> = self.isupport_tokens.iter().map(String::as_str).collect();
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let mut merged = parse_params(&tokens);
+        merged.parse_and_merge(&args);
+        let merged = merged
+            .iter()
+            .map(|entry| match entry.value {
+                Some(value) => format!("{}={}", entry.key, value),
+                None => entry.key.to_string(),
+            })
+            .collect();
+        self.isupport_tokens = merged;
+    }
+
+    /// Feed a message to the handshake machine until it has settled.
+    ///
+    /// The machine works on borrowed messages, so each message is
+    /// re-serialized for it; once it can no longer act, that is skipped.
+    fn feed_handshake(&mut self, msg: &Message) -> Result<Vec<HandshakeAction>, ClientError> {
+        if self.handshake.is_settled() {
+            return Ok(Vec::new());
+        }
+        let raw = msg.to_string();
+        let msg = MessageRef::parse(&raw)?;
+        Ok(self.handshake.feed(&msg))
+    }
+
+    /// Perform handshake actions. Returns `true` once registration is complete.
+    async fn apply(&mut self, actions: Vec<HandshakeAction>) -> Result<bool, ClientError> {
+        let mut complete = false;
+        for action in actions {
+            match action {
+                HandshakeAction::Send(msg) => self.send(*msg).await?,
+                HandshakeAction::Complete => complete = true,
+                HandshakeAction::IsupportComplete => {
+                    self.pending.push_back(Event::IsupportComplete);
+                }
+                HandshakeAction::AutoJoinSkipped(channels) => {
+                    self.pending.push_back(Event::AutoJoinSkipped { channels });
+                }
+                HandshakeAction::Error(HandshakeError::SaslFailed(reason)) => {
+                    // The handshake machine continues without SASL.
+                    warn!("SASL authentication failed: {}", reason);
+                }
+                HandshakeAction::Error(e) => return Err(e.into()),
+            }
+        }
+        Ok(complete)
+    }
+}
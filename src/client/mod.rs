@@ -0,0 +1,64 @@
+//! High-level IRC client built on [`Transport`] and [`HandshakeMachine`].
+//!
+//! This module is a thin integration layer: it drives the sans-IO
+//! [`HandshakeMachine`] over a [`Transport`], answers `PING` automatically,
+//! and accumulates `RPL_ISUPPORT` tokens. Everything else is decoded into an
+//! [`Event`] and passed through to the caller.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use slirc_proto::client::IrcClient;
+//! use slirc_proto::state::HandshakeConfig;
+//! use slirc_proto::{Message, Transport};
+//!
+//! let stream = tokio::net::TcpStream::connect("irc.example.com:6667").await?;
+//! let mut client = IrcClient::new(Transport::tcp(stream)?, config);
+//!
+//! client.connect().await?;
+//! client.send(Message::join("#rust")).await?;
+//!
+//! while let Some(event) = client.next_event().await? {
+//!     println!("{:?}", event);
+//! }
+//! ```
+//!
+//! [`Transport`]: crate::transport::Transport
+//! [`HandshakeMachine`]: crate::state::HandshakeMachine
+
+mod event;
+mod irc_client;
+
+pub use event::{Event, ModeChange};
+pub use irc_client::IrcClient;
+
+use thiserror::Error;
+
+use crate::error::MessageParseError;
+use crate::state::HandshakeError;
+use crate::transport::TransportReadError;
+
+/// Errors returned by [`IrcClient`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ClientError {
+    /// Reading from the transport failed.
+    #[error("client read error: {0}")]
+    Read(#[from] TransportReadError),
+
+    /// Writing to the transport failed.
+    #[error("client write error: {0}")]
+    Write(anyhow::Error),
+
+    /// A received message could not be re-read for the handshake machine.
+    #[error("client parse error: {0}")]
+    Parse(#[from] MessageParseError),
+
+    /// The handshake failed (e.g. nickname in use, server `ERROR`).
+    #[error("handshake failed: {0}")]
+    Handshake(#[from] HandshakeError),
+
+    /// The server closed the connection before registration completed.
+    #[error("connection closed during registration")]
+    ClosedDuringRegistration,
+}
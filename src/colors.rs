@@ -1,13 +1,15 @@
 //! IRC formatting code handling.
 //!
-//! This module provides utilities for detecting and stripping IRC formatting
-//! codes (colors, bold, underline, etc.) from messages.
+//! This module provides utilities for detecting, stripping and parsing IRC
+//! formatting codes (colors, bold, underline, etc.) in messages, and for
+//! rendering them as ANSI terminal escapes or HTML.
 //!
 //! # IRC Format Codes
 //! - 0x02 (^B): Bold
 //! - 0x03 (^C): Color (followed by optional foreground,background)
 //! - 0x0F (^O): Reset all formatting
 //! - 0x16 (^V): Reverse/Inverse
+//! - 0x1D (^]): Italic
 //! - 0x1F (^_): Underline
 
 use std::borrow::Cow;
@@ -31,6 +33,46 @@ pub trait FormattedStringExt<'a> {
     /// Returns `Cow::Borrowed` if no formatting was present,
     /// or `Cow::Owned` with the stripped string otherwise.
     fn strip_formatting(self) -> Cow<'a, str>;
+
+    /// Render IRC formatting codes as ANSI SGR escape sequences.
+    ///
+    /// Bold, italic, underline, strikethrough, reverse, reset and colors are
+    /// converted; the 16 base colors map to the standard ANSI colors, colors
+    /// 16-98 to the 256-color palette and hex colors (`\x04RRGGBB`) to
+    /// 24-bit colors. Attributes still active at the end of the string are
+    /// closed with a reset.
+    ///
+    /// Other control characters in the text, such as an embedded ESC, are
+    /// never passed to the terminal: C0 controls and DEL are shown as their
+    /// Unicode control pictures (`\x1b` becomes `␛`) and C1 controls as `�`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::FormattedStringExt;
+    ///
+    /// assert_eq!("\x02bold\x02 plain".to_ansi(), "\x1b[1mbold\x1b[22m plain");
+    /// ```
+    fn to_ansi(&self) -> String;
+
+    /// Render IRC formatting codes as HTML `<span style="...">` elements.
+    ///
+    /// Text is HTML-escaped. Colors become hex `color`/`background-color`
+    /// values and reverse swaps them. Since IRC codes do not nest, spans are
+    /// never nested either: each run of identically formatted text gets its
+    /// own span, so the output is always balanced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::FormattedStringExt;
+    ///
+    /// assert_eq!(
+    ///     "\x02a\x0Fb < c".to_html(),
+    ///     "<span style=\"font-weight:bold\">a</span>b &lt; c"
+    /// );
+    /// ```
+    fn to_html(&self) -> String;
 }
 
 impl<'a> FormattedStringExt<'a> for &'a str {
@@ -54,6 +96,14 @@ impl<'a> FormattedStringExt<'a> for &'a str {
 
         Cow::Owned(result)
     }
+
+    fn to_ansi(&self) -> String {
+        render_ansi(self)
+    }
+
+    fn to_html(&self) -> String {
+        render_html(self)
+    }
 }
 
 impl FormattedStringExt<'static> for String {
@@ -70,6 +120,476 @@ impl FormattedStringExt<'static> for String {
         self.retain(|c| parser.consume(c));
         Cow::Owned(self)
     }
+
+    fn to_ansi(&self) -> String {
+        render_ansi(self)
+    }
+
+    fn to_html(&self) -> String {
+        render_html(self)
+    }
+}
+
+/// A piece of formatted text, as produced by [`parse_formatting`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormatToken<'a> {
+    /// A run of text containing no formatting codes.
+    Text(&'a str),
+    /// The style in effect after one or more consecutive formatting codes.
+    Control {
+        /// Bold (`\x02`).
+        bold: bool,
+        /// Italic (`\x1D`).
+        italic: bool,
+        /// Underline (`\x1F`).
+        underline: bool,
+        /// Strikethrough (`\x1E`).
+        strikethrough: bool,
+        /// Reverse video (`\x16`).
+        reverse: bool,
+        /// Monospace (`\x11`).
+        monospace: bool,
+        /// Foreground color number, or `None` for the default color.
+        fg: Option<u8>,
+        /// Background color number, or `None` for the default color.
+        bg: Option<u8>,
+    },
+}
+
+/// Split formatted text into runs of text and the style changes between
+/// them.
+///
+/// Each run of consecutive formatting codes yields a single
+/// [`FormatToken::Control`] holding the cumulative style at that point.
+/// A color code takes a one- or two-digit foreground and an optional `,`
+/// followed by a one- or two-digit background; a comma not followed by a
+/// digit is left as text. Color 99 and hex colors (`\x04RRGGBB`) are
+/// reported as the default color.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::colors::{parse_formatting, FormatToken};
+///
+/// let tokens = parse_formatting("\x02hi\x02 there");
+/// assert_eq!(tokens.len(), 4);
+/// assert_eq!(tokens[1], FormatToken::Text("hi"));
+/// assert!(matches!(tokens[2], FormatToken::Control { bold: false, .. }));
+/// ```
+pub fn parse_formatting(s: &str) -> Vec<FormatToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut attrs = Attributes::default();
+    let mut pending = false;
+
+    for token in tokenize(s) {
+        match token {
+            Token::Text(run) => {
+                if pending {
+                    tokens.push(attrs.to_control());
+                    pending = false;
+                }
+                tokens.push(FormatToken::Text(run));
+            }
+            code => {
+                attrs.apply(&code);
+                pending = true;
+            }
+        }
+    }
+    if pending {
+        tokens.push(attrs.to_control());
+    }
+    tokens
+}
+
+/// ANSI SGR reset sequence.
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Closest ANSI foreground codes for the 16 base mIRC colors.
+const ANSI_BASE: [u8; 16] = [
+    97, 30, 34, 32, 91, 31, 35, 33, 93, 92, 36, 96, 94, 95, 90, 37,
+];
+
+/// 256-color palette indices for the extended mIRC colors 16-98.
+const ANSI_EXTENDED: [u8; 83] = [
+    52, 94, 100, 58, 22, 29, 23, 24, 17, 54, 53, 89, // 16-27
+    88, 130, 142, 64, 28, 35, 30, 25, 18, 91, 90, 125, // 28-39
+    124, 166, 184, 106, 34, 49, 37, 33, 19, 129, 127, 161, // 40-51
+    196, 208, 226, 154, 46, 86, 51, 75, 21, 171, 201, 198, // 52-63
+    203, 215, 227, 191, 83, 122, 87, 111, 63, 177, 207, 205, // 64-75
+    217, 223, 229, 193, 157, 158, 159, 153, 147, 183, 219, 212, // 76-87
+    16, 233, 235, 237, 239, 241, 244, 247, 250, 254, 231, // 88-98
+];
+
+/// RGB values of the mIRC colors 0-98.
+const IRC_RGB: [u32; 99] = [
+    0xFFFFFF, 0x000000, 0x00007F, 0x009300, 0xFF0000, 0x7F0000, 0x9C009C, 0xFC7F00, // 0-7
+    0xFFFF00, 0x00FC00, 0x009393, 0x00FFFF, 0x0000FC, 0xFF00FF, 0x7F7F7F, 0xD2D2D2, // 8-15
+    0x470000, 0x472100, 0x474700, 0x324700, 0x004700, 0x00472C, 0x004747, 0x002747, // 16-23
+    0x000047, 0x2E0047, 0x470047, 0x47002A, 0x740000, 0x743A00, 0x747400, 0x517400, // 24-31
+    0x007400, 0x007449, 0x007474, 0x004074, 0x000074, 0x4B0074, 0x740074, 0x740045, // 32-39
+    0xB50000, 0xB56300, 0xB5B500, 0x7DB500, 0x00B500, 0x00B571, 0x00B5B5, 0x0063B5, // 40-47
+    0x0000B5, 0x7500B5, 0xB500B5, 0xB5006B, 0xFF0000, 0xFF8C00, 0xFFFF00, 0xB2FF00, // 48-55
+    0x00FF00, 0x00FFA0, 0x00FFFF, 0x008CFF, 0x0000FF, 0xA500FF, 0xFF00FF, 0xFF0098, // 56-63
+    0xFF5959, 0xFFB459, 0xFFFF71, 0xCFFF60, 0x6FFF6F, 0x65FFC9, 0x6DFFFF, 0x59B4FF, // 64-71
+    0x5959FF, 0xC459FF, 0xFF66FF, 0xFF59BC, 0xFF9C9C, 0xFFD39C, 0xFFFF9C, 0xE2FF9C, // 72-79
+    0x9CFF9C, 0x9CFFDB, 0x9CFFFF, 0x9CD3FF, 0x9C9CFF, 0xDC9CFF, 0xFF9CFF, 0xFF94D3, // 80-87
+    0x000000, 0x131313, 0x282828, 0x363636, 0x4D4D4D, 0x656565, 0x818181, 0x9F9F9F, // 88-95
+    0xBCBCBC, 0xE2E2E2, 0xFFFFFF, // 96-98
+];
+
+/// A color selected by a formatting code.
+#[derive(Clone, Copy, PartialEq)]
+enum Color {
+    /// mIRC color number (`\x03`); 99 means the default color.
+    Irc(u8),
+    /// 24-bit color (`\x04`).
+    Rgb(u32),
+}
+
+impl Color {
+    /// RGB value, or `None` for the default color.
+    fn rgb(self) -> Option<u32> {
+        match self {
+            Color::Irc(code) => IRC_RGB.get(usize::from(code)).copied(),
+            Color::Rgb(rgb) => Some(rgb),
+        }
+    }
+
+    /// SGR parameters selecting this color as foreground or background.
+    fn sgr(self, background: bool) -> String {
+        let offset = if background { 10 } else { 0 };
+        match self {
+            Color::Irc(code @ 0..=15) => (ANSI_BASE[usize::from(code)] + offset).to_string(),
+            Color::Irc(code @ 16..=98) => {
+                format!(
+                    "{};5;{}",
+                    38 + offset,
+                    ANSI_EXTENDED[usize::from(code) - 16]
+                )
+            }
+            Color::Irc(_) => (39 + offset).to_string(),
+            Color::Rgb(rgb) => format!(
+                "{};2;{};{};{}",
+                38 + offset,
+                rgb >> 16,
+                (rgb >> 8) & 0xFF,
+                rgb & 0xFF
+            ),
+        }
+    }
+}
+
+/// Every character starting a formatting code.
+const CODE_CHARS: &[char] = &[
+    '\x02', '\x03', '\x04', '\x0F', '\x11', '\x16', '\x1D', '\x1E', '\x1F',
+];
+
+/// A formatting code or a run of text.
+enum Token<'a> {
+    Text(&'a str),
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+    Reverse,
+    Monospace,
+    /// Color change; a bare color code has no foreground and clears both.
+    Color(Option<Color>, Option<Color>),
+    Reset,
+}
+
+/// Split formatted text into tokens, consuming color code arguments.
+fn tokenize(text: &str) -> impl Iterator<Item = Token<'_>> + '_ {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let c = rest.chars().next()?;
+        if !CODE_CHARS.contains(&c) {
+            let end = rest.find(CODE_CHARS).unwrap_or(rest.len());
+            let (run, tail) = rest.split_at(end);
+            rest = tail;
+            return Some(Token::Text(run));
+        }
+        rest = &rest[c.len_utf8()..];
+        let token = match c {
+            '\x02' => Token::Bold,
+            '\x1D' => Token::Italic,
+            '\x1F' => Token::Underline,
+            '\x1E' => Token::Strikethrough,
+            '\x16' => Token::Reverse,
+            '\x11' => Token::Monospace,
+            '\x0F' => Token::Reset,
+            '\x03' => {
+                let (fg, bg, len) = color_args(rest);
+                rest = &rest[len..];
+                Token::Color(fg.map(Color::Irc), bg.map(Color::Irc))
+            }
+            '\x04' => {
+                let (fg, bg, len) = hex_color_args(rest);
+                rest = &rest[len..];
+                Token::Color(fg.map(Color::Rgb), bg.map(Color::Rgb))
+            }
+            _ => unreachable!(),
+        };
+        Some(token)
+    })
+}
+
+/// Text attributes in effect while rendering.
+#[derive(Clone, Default, PartialEq)]
+struct Attributes {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+    reverse: bool,
+    monospace: bool,
+    fg: Option<Color>,
+    bg: Option<Color>,
+}
+
+impl Attributes {
+    fn any(&self) -> bool {
+        *self != Attributes::default()
+    }
+
+    /// Update the attributes for a formatting code.
+    fn apply(&mut self, token: &Token<'_>) {
+        match *token {
+            Token::Text(_) => {}
+            Token::Bold => self.bold = !self.bold,
+            Token::Italic => self.italic = !self.italic,
+            Token::Underline => self.underline = !self.underline,
+            Token::Strikethrough => self.strikethrough = !self.strikethrough,
+            Token::Reverse => self.reverse = !self.reverse,
+            Token::Monospace => self.monospace = !self.monospace,
+            Token::Reset => *self = Attributes::default(),
+            Token::Color(fg, bg) => self.set_colors(fg, bg),
+        }
+    }
+
+    /// Apply a color code. A new foreground keeps the current background
+    /// unless one is given; a bare code clears both.
+    fn set_colors(&mut self, fg: Option<Color>, bg: Option<Color>) {
+        match fg {
+            Some(fg) => {
+                self.fg = Some(fg);
+                if bg.is_some() {
+                    self.bg = bg;
+                }
+            }
+            None => {
+                self.fg = None;
+                self.bg = None;
+            }
+        }
+    }
+
+    fn to_control(&self) -> FormatToken<'static> {
+        let number = |color: Option<Color>| match color {
+            Some(Color::Irc(code)) if code < 99 => Some(code),
+            _ => None,
+        };
+        FormatToken::Control {
+            bold: self.bold,
+            italic: self.italic,
+            underline: self.underline,
+            strikethrough: self.strikethrough,
+            reverse: self.reverse,
+            monospace: self.monospace,
+            fg: number(self.fg),
+            bg: number(self.bg),
+        }
+    }
+
+    /// Inline CSS for these attributes; empty if nothing is styled.
+    fn css(&self) -> String {
+        let mut fg = self.fg.and_then(Color::rgb);
+        let mut bg = self.bg.and_then(Color::rgb);
+        if self.reverse {
+            // Assume black on white where no color is set
+            (fg, bg) = (Some(bg.unwrap_or(0xFFFFFF)), Some(fg.unwrap_or(0x000000)));
+        }
+
+        let mut style = Vec::new();
+        if let Some(fg) = fg {
+            style.push(format!("color:#{:06X}", fg));
+        }
+        if let Some(bg) = bg {
+            style.push(format!("background-color:#{:06X}", bg));
+        }
+        if self.bold {
+            style.push("font-weight:bold".to_string());
+        }
+        if self.italic {
+            style.push("font-style:italic".to_string());
+        }
+        match (self.underline, self.strikethrough) {
+            (true, true) => style.push("text-decoration:underline line-through".to_string()),
+            (true, false) => style.push("text-decoration:underline".to_string()),
+            (false, true) => style.push("text-decoration:line-through".to_string()),
+            (false, false) => {}
+        }
+        if self.monospace {
+            style.push("font-family:monospace".to_string());
+        }
+        style.join(";")
+    }
+}
+
+/// Append `run`, replacing control characters a terminal could interpret.
+fn push_inert(out: &mut String, run: &str) {
+    for ch in run.chars() {
+        match ch {
+            '\t' => out.push(ch),
+            '\0'..='\x1f' => out.extend(char::from_u32(0x2400 + ch as u32)),
+            '\x7f' => out.push('\u{2421}'),
+            '\u{80}'..='\u{9f}' => out.push(char::REPLACEMENT_CHARACTER),
+            _ => out.push(ch),
+        }
+    }
+}
+
+fn render_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 16);
+    let mut attrs = Attributes::default();
+
+    for token in tokenize(text) {
+        match token {
+            Token::Text(run) => push_inert(&mut out, run),
+            Token::Bold => toggle(&mut out, &mut attrs.bold, "1", "22"),
+            Token::Italic => toggle(&mut out, &mut attrs.italic, "3", "23"),
+            Token::Underline => toggle(&mut out, &mut attrs.underline, "4", "24"),
+            Token::Strikethrough => toggle(&mut out, &mut attrs.strikethrough, "9", "29"),
+            Token::Reverse => toggle(&mut out, &mut attrs.reverse, "7", "27"),
+            // Monospace has no ANSI equivalent
+            Token::Monospace => {}
+            Token::Reset => {
+                if attrs.any() {
+                    out.push_str(ANSI_RESET);
+                }
+                attrs = Attributes::default();
+            }
+            Token::Color(Some(fg), bg) => {
+                let bg_sgr = bg.map(|bg| format!(";{}", bg.sgr(true)));
+                out.push_str(&format!(
+                    "\x1b[{}{}m",
+                    fg.sgr(false),
+                    bg_sgr.unwrap_or_default()
+                ));
+                attrs.set_colors(Some(fg), bg);
+            }
+            Token::Color(None, _) => {
+                if attrs.fg.is_some() {
+                    out.push_str("\x1b[39;49m");
+                }
+                attrs.set_colors(None, None);
+            }
+        }
+    }
+
+    if attrs.any() {
+        out.push_str(ANSI_RESET);
+    }
+    out
+}
+
+/// Flip an attribute and emit the SGR sequence turning it on or off.
+fn toggle(out: &mut String, on: &mut bool, set: &str, unset: &str) {
+    *on = !*on;
+    out.push_str("\x1b[");
+    out.push_str(if *on { set } else { unset });
+    out.push('m');
+}
+
+fn render_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut attrs = Attributes::default();
+    // Attributes of the text written so far, and whether a span is open
+    let mut rendered = Attributes::default();
+    let mut open = false;
+
+    for token in tokenize(text) {
+        match token {
+            Token::Text(run) => {
+                if attrs != rendered {
+                    if open {
+                        out.push_str("</span>");
+                    }
+                    let css = attrs.css();
+                    open = !css.is_empty();
+                    if open {
+                        out.push_str(&format!("<span style=\"{}\">", css));
+                    }
+                    rendered = attrs.clone();
+                }
+                for c in run.chars() {
+                    match c {
+                        '&' => out.push_str("&amp;"),
+                        '<' => out.push_str("&lt;"),
+                        '>' => out.push_str("&gt;"),
+                        '"' => out.push_str("&quot;"),
+                        '\'' => out.push_str("&#39;"),
+                        c => out.push(c),
+                    }
+                }
+            }
+            code => attrs.apply(&code),
+        }
+    }
+
+    if open {
+        out.push_str("</span>");
+    }
+    out
+}
+
+/// Parse `[N[N]][,M[M]]` after `\x03`, returning the colors and the length
+/// consumed.
+fn color_args(s: &str) -> (Option<u8>, Option<u8>, usize) {
+    let number = |from: usize| {
+        let digits = s.as_bytes()[from..]
+            .iter()
+            .take(2)
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        let value = s[from..from + digits].parse().ok();
+        (value, digits)
+    };
+
+    let (fg, mut len) = number(0);
+    if fg.is_none() {
+        return (None, None, 0);
+    }
+    let mut bg = None;
+    if s.as_bytes().get(len) == Some(&b',') {
+        if let (Some(value), digits) = number(len + 1) {
+            bg = Some(value);
+            len += 1 + digits;
+        }
+    }
+    (fg, bg, len)
+}
+
+/// Parse `[RRGGBB[,RRGGBB]]` after `\x04`, returning the colors and the
+/// length consumed.
+fn hex_color_args(s: &str) -> (Option<u32>, Option<u32>, usize) {
+    let hex = |from: usize| {
+        s.get(from..from + 6)
+            .filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|h| u32::from_str_radix(h, 16).ok())
+    };
+
+    let Some(fg) = hex(0) else {
+        return (None, None, 0);
+    };
+    match s.as_bytes().get(6) {
+        Some(b',') => match hex(7) {
+            Some(bg) => (Some(fg), Some(bg), 13),
+            None => (Some(fg), None, 6),
+        },
+        _ => (Some(fg), None, 6),
+    }
 }
 
 /// Parser state for stripping color codes.
@@ -190,6 +710,174 @@ mod tests {
         assert_eq!("\x034,5colored".strip_formatting(), "colored");
     }
 
+    #[test]
+    fn test_ansi_bold_red() {
+        assert_eq!(
+            "\x02\x0304error\x03\x02 ok".to_ansi(),
+            "\x1b[1m\x1b[91merror\x1b[39;49m\x1b[22m ok"
+        );
+        assert_eq!(
+            "\x02\x034red".to_string().to_ansi(),
+            "\x1b[1m\x1b[91mred\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_ansi_colors() {
+        assert_eq!("\x031,15x".to_ansi(), "\x1b[30;47mx\x1b[0m");
+        assert_eq!("\x0352,88x".to_ansi(), "\x1b[38;5;196;48;5;16mx\x1b[0m");
+        assert_eq!("\x0399,99x".to_ansi(), "\x1b[39;49mx\x1b[0m");
+        assert_eq!(
+            "\x04FF8000,000000x".to_ansi(),
+            "\x1b[38;2;255;128;0;48;2;0;0;0mx\x1b[0m"
+        );
+        // A comma not followed by a color is text
+        assert_eq!("\x034,x".to_ansi(), "\x1b[91m,x\x1b[0m");
+        // A bare color code with nothing active emits nothing
+        assert_eq!("\x03plain".to_ansi(), "plain");
+    }
+
+    #[test]
+    fn test_ansi_reset() {
+        assert_eq!("\x1D\x1Fa\x0Fb".to_ansi(), "\x1b[3m\x1b[4ma\x1b[0mb");
+        // Unclosed attributes are reset at the end
+        assert_eq!("\x16rev".to_ansi(), "\x1b[7mrev\x1b[0m");
+        // Nothing to reset
+        assert_eq!("\x0Fplain".to_ansi(), "plain");
+        assert_eq!("plain text".to_ansi(), "plain text");
+    }
+
+    #[test]
+    fn test_ansi_neutralizes_escapes() {
+        assert_eq!(
+            "\x02hi\x1b[2J\x1b]0;pwned\x07".to_ansi(),
+            "\x1b[1mhi\u{241b}[2J\u{241b}]0;pwned\u{2407}\x1b[0m"
+        );
+        assert_eq!("a\x7fb\u{9b}31m\tc".to_ansi(), "a\u{2421}b\u{fffd}31m\tc");
+    }
+
+    #[test]
+    fn test_html_color_with_background() {
+        assert_eq!(
+            "\x0304,01red on black".to_html(),
+            "<span style=\"color:#FF0000;background-color:#000000\">red on black</span>"
+        );
+        assert_eq!(
+            "\x0352x\x0399y".to_html(),
+            "<span style=\"color:#FF0000\">x</span>y"
+        );
+        // Reverse swaps foreground and background
+        assert_eq!(
+            "\x034,2\x16x".to_html(),
+            "<span style=\"color:#00007F;background-color:#FF0000\">x</span>"
+        );
+    }
+
+    #[test]
+    fn test_html_reset_and_overlap() {
+        assert_eq!(
+            "\x02\x0312bold blue\x0Fplain".to_html(),
+            "<span style=\"color:#0000FC;font-weight:bold\">bold blue</span>plain"
+        );
+        // Overlapping codes produce flat, balanced spans
+        assert_eq!(
+            "\x02a\x1Db\x02c\x1D".to_html(),
+            "<span style=\"font-weight:bold\">a</span>\
+             <span style=\"font-weight:bold;font-style:italic\">b</span>\
+             <span style=\"font-style:italic\">c</span>"
+        );
+        assert_eq!("\x02\x0F\x1F".to_html(), "");
+    }
+
+    #[test]
+    fn test_html_escaping() {
+        assert_eq!(
+            "<script>alert('x')</script> & \"co\"".to_html(),
+            "&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; &quot;co&quot;"
+        );
+        assert_eq!(
+            "\x1F<b>".to_string().to_html(),
+            "<span style=\"text-decoration:underline\">&lt;b&gt;</span>"
+        );
+    }
+
+    fn control(fg: Option<u8>, bg: Option<u8>) -> FormatToken<'static> {
+        FormatToken::Control {
+            bold: false,
+            italic: false,
+            underline: false,
+            strikethrough: false,
+            reverse: false,
+            monospace: false,
+            fg,
+            bg,
+        }
+    }
+
+    #[test]
+    fn test_parse_formatting_colors() {
+        assert_eq!(
+            parse_formatting("\x0304,08red on yellow"),
+            [
+                control(Some(4), Some(8)),
+                FormatToken::Text("red on yellow")
+            ]
+        );
+        // A bare color code resets both colors
+        assert_eq!(
+            parse_formatting("\x034,8a\x03b"),
+            [
+                control(Some(4), Some(8)),
+                FormatToken::Text("a"),
+                control(None, None),
+                FormatToken::Text("b"),
+            ]
+        );
+        // A new foreground keeps the background
+        assert_eq!(
+            parse_formatting("\x034,8a\x0312b")[2],
+            control(Some(12), Some(8))
+        );
+    }
+
+    #[test]
+    fn test_parse_formatting_lone_comma() {
+        assert_eq!(
+            parse_formatting("\x034,text"),
+            [control(Some(4), None), FormatToken::Text(",text")]
+        );
+        // At most two digits belong to each color
+        assert_eq!(
+            parse_formatting("\x03123"),
+            [control(Some(12), None), FormatToken::Text("3")]
+        );
+    }
+
+    #[test]
+    fn test_parse_formatting_cumulative() {
+        let tokens = parse_formatting("plain\x02\x1Dboth\x02italic\x0F");
+        assert_eq!(tokens.len(), 6);
+        assert_eq!(tokens[0], FormatToken::Text("plain"));
+        assert!(matches!(
+            tokens[1],
+            FormatToken::Control {
+                bold: true,
+                italic: true,
+                ..
+            }
+        ));
+        assert!(matches!(
+            tokens[3],
+            FormatToken::Control {
+                bold: false,
+                italic: true,
+                ..
+            }
+        ));
+        assert_eq!(tokens[5], control(None, None));
+        assert!(parse_formatting("").is_empty());
+    }
+
     #[test]
     fn test_no_formatting() {
         let s = "plain text";
@@ -0,0 +1,208 @@
+//! Allocation-free structured view of high-traffic commands.
+
+use super::types::CommandRef;
+
+/// A borrowed, structured view of a common IRC command.
+///
+/// Produced by [`CommandRef::parse`] without allocating. Only the commands
+/// that dominate client traffic are decoded; everything else, including
+/// recognized commands with too few arguments, is reported as
+/// [`Raw`](Self::Raw).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommandRefEnum<'a> {
+    /// `PRIVMSG <target> :<text>`
+    Privmsg {
+        /// Message target (nickname or channel).
+        target: &'a str,
+        /// Message text.
+        text: &'a str,
+    },
+    /// `NOTICE <target> :<text>`
+    Notice {
+        /// Notice target (nickname or channel).
+        target: &'a str,
+        /// Notice text.
+        text: &'a str,
+    },
+    /// `JOIN <channels> [<keys>]`
+    Join {
+        /// Comma-separated channel list.
+        channels: &'a str,
+        /// Comma-separated key list, if given.
+        keys: Option<&'a str>,
+    },
+    /// `JOIN <channel> <account> :<realname>`, as relayed with `extended-join`
+    ExtendedJoin {
+        /// The joined channel.
+        channel: &'a str,
+        /// Account name, or `*` if not logged in.
+        account: &'a str,
+        /// Real name / GECOS.
+        realname: &'a str,
+    },
+    /// `PART <channels> [:<reason>]`
+    Part {
+        /// Comma-separated channel list.
+        channels: &'a str,
+        /// Part reason, if given.
+        reason: Option<&'a str>,
+    },
+    /// `PING <token>`
+    Ping {
+        /// Token to echo back in the PONG.
+        token: &'a str,
+    },
+    /// `PONG [<server>] <token>`
+    Pong {
+        /// Token echoed back from the PING (the last argument).
+        token: &'a str,
+    },
+    /// Any other command; the name as it appeared on the wire.
+    Raw(&'a str),
+}
+
+impl<'a> CommandRef<'a> {
+    /// Decode a command name and arguments into a [`CommandRefEnum`].
+    ///
+    /// Command names are matched case-insensitively. No allocation is
+    /// performed; all fields borrow from the input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::command::{CommandRef, CommandRefEnum};
+    ///
+    /// let cmd = CommandRef::parse("PRIVMSG", &["#rust", "hello"]);
+    /// assert_eq!(cmd, CommandRefEnum::Privmsg { target: "#rust", text: "hello" });
+    /// ```
+    pub fn parse(name: &'a str, args: &[&'a str]) -> CommandRefEnum<'a> {
+        let is = |cmd: &str| name.eq_ignore_ascii_case(cmd);
+
+        match args {
+            [target, text] if is("PRIVMSG") => CommandRefEnum::Privmsg { target, text },
+            [target, text] if is("NOTICE") => CommandRefEnum::Notice { target, text },
+            [channels, rest @ ..] if is("JOIN") && rest.len() <= 1 => CommandRefEnum::Join {
+                channels,
+                keys: rest.first().copied(),
+            },
+            [channel, account, realname] if is("JOIN") => CommandRefEnum::ExtendedJoin {
+                channel,
+                account,
+                realname,
+            },
+            [channels, rest @ ..] if is("PART") && rest.len() <= 1 => CommandRefEnum::Part {
+                channels,
+                reason: rest.first().copied(),
+            },
+            [token, ..] if is("PING") => CommandRefEnum::Ping { token },
+            [.., token] if is("PONG") => CommandRefEnum::Pong { token },
+            _ => CommandRefEnum::Raw(name),
+        }
+    }
+
+    /// Decode this command into a [`CommandRefEnum`].
+    ///
+    /// See [`CommandRef::parse`].
+    pub fn parsed(&self) -> CommandRefEnum<'a> {
+        Self::parse(self.name, &self.args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::MessageRef;
+
+    fn parse_line(raw: &str) -> CommandRefEnum<'_> {
+        MessageRef::parse(raw).unwrap().command.parsed()
+    }
+
+    #[test]
+    fn test_privmsg_and_notice() {
+        assert_eq!(
+            parse_line(":a!b@c PRIVMSG #rust :hello world"),
+            CommandRefEnum::Privmsg {
+                target: "#rust",
+                text: "hello world"
+            }
+        );
+        assert_eq!(
+            parse_line("notice bob :hi"),
+            CommandRefEnum::Notice {
+                target: "bob",
+                text: "hi"
+            }
+        );
+        assert_eq!(parse_line("PRIVMSG #rust"), CommandRefEnum::Raw("PRIVMSG"));
+    }
+
+    #[test]
+    fn test_join() {
+        assert_eq!(
+            parse_line(":a!b@c JOIN #rust"),
+            CommandRefEnum::Join {
+                channels: "#rust",
+                keys: None
+            }
+        );
+        assert_eq!(
+            parse_line("JOIN #a,#b key1"),
+            CommandRefEnum::Join {
+                channels: "#a,#b",
+                keys: Some("key1")
+            }
+        );
+        assert_eq!(
+            parse_line(":a!b@c JOIN #rust alice :Alice Smith"),
+            CommandRefEnum::ExtendedJoin {
+                channel: "#rust",
+                account: "alice",
+                realname: "Alice Smith"
+            }
+        );
+        assert_eq!(parse_line("JOIN"), CommandRefEnum::Raw("JOIN"));
+    }
+
+    #[test]
+    fn test_part() {
+        assert_eq!(
+            parse_line(":a!b@c PART #rust"),
+            CommandRefEnum::Part {
+                channels: "#rust",
+                reason: None
+            }
+        );
+        assert_eq!(
+            parse_line(":a!b@c PART #rust :Goodbye all"),
+            CommandRefEnum::Part {
+                channels: "#rust",
+                reason: Some("Goodbye all")
+            }
+        );
+    }
+
+    #[test]
+    fn test_ping_pong() {
+        assert_eq!(
+            parse_line("PING :irc.example.com"),
+            CommandRefEnum::Ping {
+                token: "irc.example.com"
+            }
+        );
+        assert_eq!(
+            parse_line(":srv PONG srv :token123"),
+            CommandRefEnum::Pong { token: "token123" }
+        );
+        assert_eq!(parse_line("PING"), CommandRefEnum::Raw("PING"));
+    }
+
+    #[test]
+    fn test_raw_fallback() {
+        assert_eq!(
+            parse_line(":srv 001 me :Welcome"),
+            CommandRefEnum::Raw("001")
+        );
+        assert_eq!(parse_line("KICK #rust bob"), CommandRefEnum::Raw("KICK"));
+    }
+}
@@ -1,11 +1,16 @@
 //! IRC command types and parsing.
 
+mod borrowed;
 mod parse;
 mod serialize;
 pub(crate) mod util;
-/// Command subcommands (CAP, BATCH, CHATHISTORY).
+/// Command subcommands (CAP, BATCH, CHATHISTORY, MONITOR).
 pub mod subcommands;
 mod types;
 
-pub use subcommands::{BatchSubCommand, CapSubCommand, ChatHistorySubCommand, MessageReference};
-pub use types::{Command, CommandRef};
+pub use borrowed::CommandRefEnum;
+pub use subcommands::{
+    BatchSubCommand, CapSubCommand, ChatHistorySubCommand, MessageReference, MonitorSubCommand,
+    ReadMarker, SilenceChange,
+};
+pub use types::{Command, CommandRef, VENDOR_COMMANDS};
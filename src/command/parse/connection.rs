@@ -68,6 +68,7 @@ pub(super) fn parse(cmd: &str, args: Vec<&str>) -> Result<Command, MessageParseE
                 Command::SQUIT(args[0].to_owned(), args[1].to_owned())
             }
         }
+        "PROTOCTL" => Command::PROTOCTL(args.into_iter().map(|s| s.to_owned()).collect()),
         _ => unreachable!(
             "connection::parse called with non-connection command: {}",
             cmd
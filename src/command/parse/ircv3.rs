@@ -1,3 +1,4 @@
+use super::super::subcommands::ReadMarker;
 use super::super::types::Command;
 use super::connection::raw;
 use crate::error::MessageParseError;
@@ -57,9 +58,15 @@ pub(super) fn parse(cmd: &str, args: Vec<&str>) -> Result<Command, MessageParseE
         }
         "MONITOR" => {
             if args.len() == 2 {
-                Command::MONITOR(args[0].to_owned(), Some(args[1].to_owned()))
+                match args[0].parse() {
+                    Ok(sub) => Command::MONITOR(sub, Some(args[1].to_owned())),
+                    Err(_) => raw(cmd, args),
+                }
             } else if args.len() == 1 {
-                Command::MONITOR(args[0].to_owned(), None)
+                match args[0].parse() {
+                    Ok(sub) => Command::MONITOR(sub, None),
+                    Err(_) => raw(cmd, args),
+                }
             } else {
                 raw(cmd, args)
             }
@@ -130,6 +137,37 @@ pub(super) fn parse(cmd: &str, args: Vec<&str>) -> Result<Command, MessageParseE
                 raw(cmd, args)
             }
         }
+        "REDACT" => match args.as_slice() {
+            [channel, msgid] => Command::REDACT {
+                channel: channel.to_string(),
+                msgid: msgid.to_string(),
+                reason: None,
+            },
+            [channel, msgid, reason] => Command::REDACT {
+                channel: channel.to_string(),
+                msgid: msgid.to_string(),
+                reason: Some(reason.to_string()),
+            },
+            _ => raw(cmd, args),
+        },
+        "MARKREAD" => match args.as_slice() {
+            [target] => Command::MARKREAD {
+                target: target.to_string(),
+                marker: ReadMarker::Query,
+            },
+            [target, "*"] => Command::MARKREAD {
+                target: target.to_string(),
+                marker: ReadMarker::Unset,
+            },
+            [target, param] => match param.strip_prefix("timestamp=") {
+                Some(timestamp) => Command::MARKREAD {
+                    target: target.to_string(),
+                    marker: ReadMarker::Timestamp(timestamp.to_string()),
+                },
+                None => raw(cmd, args),
+            },
+            _ => raw(cmd, args),
+        },
         "CHATHISTORY" => {
             use crate::command::subcommands::{ChatHistorySubCommand, MessageReference};
             if args.len() < 3 {
@@ -25,6 +25,15 @@ pub(super) fn parse(cmd: &str, args: Vec<&str>) -> Result<Command, MessageParseE
                 Command::ACCEPT(args[0].to_owned())
             }
         }
+        // SILENCE [+mask|-mask[,...]]
+        "SILENCE" => match args.as_slice() {
+            [] => Command::SILENCE(Vec::new()),
+            [list] => match list.split(',').map(str::parse).collect() {
+                Ok(changes) => Command::SILENCE(changes),
+                Err(_) => raw(cmd, args),
+            },
+            _ => raw(cmd, args),
+        },
 
         "KILL" => {
             if args.len() != 2 {
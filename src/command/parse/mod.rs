@@ -7,7 +7,7 @@ mod messaging;
 mod server;
 mod user;
 
-use super::types::Command;
+use super::types::{Command, VENDOR_COMMANDS};
 use crate::chan::ChannelExt;
 use crate::error::MessageParseError;
 use crate::mode::Mode;
@@ -42,7 +42,7 @@ impl Command {
         let cmd_str = cmd_upper.as_str();
 
         match cmd_str {
-            "PASS" | "NICK" | "USER" | "OPER" | "SERVICE" | "QUIT" | "SQUIT" => {
+            "PASS" | "NICK" | "USER" | "OPER" | "SERVICE" | "QUIT" | "SQUIT" | "PROTOCTL" => {
                 connection::parse(cmd_str, args)
             }
 
@@ -64,15 +64,22 @@ impl Command {
             | "OPERSERV" | "BOTSERV" | "HOSTSERV" | "MEMOSERV" | "NS" | "CS" | "OS" | "BS"
             | "HS" | "MS" | "KLINE" | "DLINE" | "UNKLINE" | "UNDLINE" | "GLINE" | "UNGLINE"
             | "ZLINE" | "UNZLINE" | "RLINE" | "UNRLINE" | "SHUN" | "UNSHUN" | "KNOCK"
-            | "ACCEPT" => {
+            | "ACCEPT" | "SILENCE" => {
                 messaging::parse(cmd_str, args)
             }
 
-            "CAP" | "AUTHENTICATE" | "ACCOUNT" | "BATCH" | "CHGHOST" | "CHGIDENT" | "SETNAME" | "MONITOR"
-            | "TAGMSG" | "WEBIRC" | "CHATHISTORY" | "ACK" => ircv3::parse(cmd_str, args),
+            "CAP" | "AUTHENTICATE" | "ACCOUNT" | "BATCH" | "CHGHOST" | "CHGIDENT" | "SETNAME"
+            | "MONITOR" | "TAGMSG" | "WEBIRC" | "CHATHISTORY" | "ACK" | "REDACT" | "MARKREAD" => {
+                ircv3::parse(cmd_str, args)
+            }
 
             "MODE" => parse_mode_command(cmd, args),
 
+            _ if VENDOR_COMMANDS.contains(&cmd_str) => Ok(Command::Vendor {
+                name: cmd_upper,
+                args: args.into_iter().map(|s| s.to_owned()).collect(),
+            }),
+
             _ => {
                 if let Ok(resp) = cmd.parse() {
                     Ok(Command::Response(
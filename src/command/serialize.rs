@@ -1,5 +1,6 @@
 use std::fmt::{self, Write};
 
+use super::subcommands::ReadMarker;
 use super::types::Command;
 use super::util::{
     write_args_with_trailing, write_cmd, write_cmd_freeform, write_collapsed_mode_flags,
@@ -34,6 +35,10 @@ impl fmt::Display for Command {
             Command::QUIT(Some(m)) => write_cmd(f, "QUIT", &[m]).map(|_| ()),
             Command::QUIT(None) => write_cmd(f, "QUIT", &[]).map(|_| ()),
             Command::SQUIT(s, c) => write_cmd_freeform(f, "SQUIT", &[s, c]).map(|_| ()),
+            Command::PROTOCTL(tokens) => {
+                let args: Vec<&str> = tokens.iter().map(|s| s.as_str()).collect();
+                write_cmd(f, "PROTOCTL", &args).map(|_| ())
+            }
             Command::JOIN(c, Some(k), Some(n)) => write_cmd(f, "JOIN", &[c, k, n]).map(|_| ()),
             Command::JOIN(c, Some(k), None) => write_cmd(f, "JOIN", &[c, k]).map(|_| ()),
             Command::JOIN(c, None, Some(n)) => write_cmd(f, "JOIN", &[c, n]).map(|_| ()),
@@ -73,6 +78,13 @@ impl fmt::Display for Command {
             Command::PRIVMSG(t, m) => write_cmd_freeform(f, "PRIVMSG", &[t, m]).map(|_| ()),
             Command::NOTICE(t, m) => write_cmd_freeform(f, "NOTICE", &[t, m]).map(|_| ()),
             Command::ACCEPT(n) => write_cmd(f, "ACCEPT", &[n]).map(|_| ()),
+            Command::SILENCE(changes) if changes.is_empty() => {
+                write_cmd(f, "SILENCE", &[]).map(|_| ())
+            }
+            Command::SILENCE(changes) => {
+                let list: Vec<String> = changes.iter().map(ToString::to_string).collect();
+                write_cmd(f, "SILENCE", &[&list.join(",")]).map(|_| ())
+            }
             Command::MOTD(Some(t)) => write_cmd(f, "MOTD", &[t]).map(|_| ()),
             Command::MOTD(None) => write_cmd(f, "MOTD", &[]).map(|_| ()),
             Command::LUSERS(Some(m), Some(t)) => write_cmd(f, "LUSERS", &[m, t]).map(|_| ()),
@@ -241,8 +253,8 @@ impl fmt::Display for Command {
             Command::CAP(Some(k), s, Some(c), None) => write_cmd(f, "CAP", &[k, s.to_str(), c]).map(|_| ()),
             Command::AUTHENTICATE(d) => write_cmd(f, "AUTHENTICATE", &[d]).map(|_| ()),
             Command::ACCOUNT(a) => write_cmd(f, "ACCOUNT", &[a]).map(|_| ()),
-            Command::MONITOR(c, Some(t)) => write_cmd(f, "MONITOR", &[c, t]).map(|_| ()),
-            Command::MONITOR(c, None) => write_cmd(f, "MONITOR", &[c]).map(|_| ()),
+            Command::MONITOR(c, Some(t)) => write_cmd(f, "MONITOR", &[c.as_str(), t]).map(|_| ()),
+            Command::MONITOR(c, None) => write_cmd(f, "MONITOR", &[c.as_str()]).map(|_| ()),
             Command::BATCH(t, Some(c), Some(a)) => {
                 f.write_str("BATCH ")?;
                 f.write_str(t)?;
@@ -301,6 +313,28 @@ impl fmt::Display for Command {
                     }
                 }
             }
+            Command::REDACT {
+                channel,
+                msgid,
+                reason: Some(reason),
+            } => write_cmd_freeform(f, "REDACT", &[channel, msgid, reason]).map(|_| ()),
+            Command::REDACT {
+                channel,
+                msgid,
+                reason: None,
+            } => write_cmd(f, "REDACT", &[channel, msgid]).map(|_| ()),
+            Command::MARKREAD {
+                target,
+                marker: ReadMarker::Query,
+            } => write_cmd(f, "MARKREAD", &[target]).map(|_| ()),
+            Command::MARKREAD {
+                target,
+                marker: ReadMarker::Timestamp(ts),
+            } => write_cmd(f, "MARKREAD", &[target, &format!("timestamp={}", ts)]).map(|_| ()),
+            Command::MARKREAD {
+                target,
+                marker: ReadMarker::Unset,
+            } => write_cmd(f, "MARKREAD", &[target, "*"]).map(|_| ()),
             Command::FAIL(command, code, context) => {
                 write_standard_reply(f, "FAIL", command.as_str(), code.as_str(), context).map(|_| ())
             }
@@ -327,7 +361,7 @@ impl fmt::Display for Command {
                 }
                 Ok(())
             }
-            Command::Raw(c, a) => {
+            Command::Raw(c, a) | Command::Vendor { name: c, args: a } => {
                 f.write_str(c)?;
                 write_args_with_trailing(f, a.iter().map(String::as_str)).map(|_| ())
             }
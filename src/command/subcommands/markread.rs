@@ -0,0 +1,28 @@
+//! Read marker states for the IRCv3 `MARKREAD` command.
+//!
+//! # Reference
+//! - IRCv3 read-marker specification: <https://ircv3.net/specs/extensions/read-marker>
+
+/// The marker carried by a MARKREAD message.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReadMarker {
+    /// No parameter: a client asking the server for the stored marker.
+    Query,
+    /// `timestamp=<ts>`: a client updating the marker, or the server
+    /// reporting it. Holds the timestamp without the `timestamp=` prefix.
+    Timestamp(String),
+    /// `*`: the server has no marker stored for the target.
+    Unset,
+}
+
+impl ReadMarker {
+    /// Get the marker timestamp, if one is set.
+    #[must_use]
+    pub fn timestamp(&self) -> Option<&str> {
+        match self {
+            Self::Timestamp(ts) => Some(ts),
+            Self::Query | Self::Unset => None,
+        }
+    }
+}
@@ -1,7 +1,13 @@
 mod batch;
 mod cap;
 mod chathistory;
+mod markread;
+mod monitor;
+mod silence;
 
 pub use batch::BatchSubCommand;
 pub use cap::CapSubCommand;
 pub use chathistory::{ChatHistorySubCommand, MessageReference};
+pub use markread::ReadMarker;
+pub use monitor::MonitorSubCommand;
+pub use silence::SilenceChange;
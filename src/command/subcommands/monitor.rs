@@ -0,0 +1,117 @@
+//! MONITOR subcommand types for IRCv3 presence tracking.
+//!
+//! # Reference
+//! - IRCv3 monitor specification: <https://ircv3.net/specs/extensions/monitor>
+
+use std::str::FromStr;
+
+use crate::error::MessageParseError;
+
+/// Subcommand for MONITOR messages.
+///
+/// MONITOR lets a client track when nicknames come online or go offline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum MonitorSubCommand {
+    /// `+` - Add targets to the monitor list
+    Add,
+    /// `-` - Remove targets from the monitor list
+    Remove,
+    /// `C` - Clear the monitor list
+    Clear,
+    /// `L` - List all monitored targets
+    List,
+    /// `S` - Show online/offline status of all monitored targets
+    Status,
+}
+
+impl MonitorSubCommand {
+    /// Get the string representation of this subcommand.
+    #[inline]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Remove => "-",
+            Self::Clear => "C",
+            Self::List => "L",
+            Self::Status => "S",
+        }
+    }
+
+    /// Returns true if this subcommand takes a target list.
+    #[inline]
+    pub fn takes_targets(&self) -> bool {
+        matches!(self, Self::Add | Self::Remove)
+    }
+}
+
+impl FromStr for MonitorSubCommand {
+    type Err = MessageParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Self::Add),
+            "-" => Ok(Self::Remove),
+            "C" | "c" => Ok(Self::Clear),
+            "L" | "l" => Ok(Self::List),
+            "S" | "s" => Ok(Self::Status),
+            _ => Err(MessageParseError::InvalidSubcommand {
+                cmd: "MONITOR",
+                sub: s.to_owned(),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for MonitorSubCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "+".parse::<MonitorSubCommand>().unwrap(),
+            MonitorSubCommand::Add
+        );
+        assert_eq!(
+            "-".parse::<MonitorSubCommand>().unwrap(),
+            MonitorSubCommand::Remove
+        );
+        assert_eq!(
+            "C".parse::<MonitorSubCommand>().unwrap(),
+            MonitorSubCommand::Clear
+        );
+        assert_eq!(
+            "l".parse::<MonitorSubCommand>().unwrap(),
+            MonitorSubCommand::List
+        );
+        assert_eq!(
+            "S".parse::<MonitorSubCommand>().unwrap(),
+            MonitorSubCommand::Status
+        );
+        assert!("X".parse::<MonitorSubCommand>().is_err());
+        assert!("++".parse::<MonitorSubCommand>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", MonitorSubCommand::Add), "+");
+        assert_eq!(format!("{}", MonitorSubCommand::Status), "S");
+    }
+
+    #[test]
+    fn test_takes_targets() {
+        assert!(MonitorSubCommand::Add.takes_targets());
+        assert!(MonitorSubCommand::Remove.takes_targets());
+        assert!(!MonitorSubCommand::Clear.takes_targets());
+        assert!(!MonitorSubCommand::List.takes_targets());
+        assert!(!MonitorSubCommand::Status.takes_targets());
+    }
+}
@@ -0,0 +1,86 @@
+//! SILENCE list changes for server-side ignore.
+//!
+//! # Reference
+//! - ircu SILENCE: `SILENCE [+|-]<mask>[,[+|-]<mask>...]`
+
+use std::str::FromStr;
+
+use crate::error::MessageParseError;
+
+/// One entry of a SILENCE command: a mask added to or removed from the
+/// silence list.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SilenceChange {
+    /// `+mask` - Ignore messages from users matching the mask
+    Add(String),
+    /// `-mask` - Stop ignoring users matching the mask
+    Remove(String),
+}
+
+impl SilenceChange {
+    /// Get the mask being added or removed.
+    #[must_use]
+    pub fn mask(&self) -> &str {
+        match self {
+            Self::Add(mask) | Self::Remove(mask) => mask,
+        }
+    }
+
+    /// Returns true if the mask is being added.
+    #[must_use]
+    pub fn is_add(&self) -> bool {
+        matches!(self, Self::Add(_))
+    }
+}
+
+impl FromStr for SilenceChange {
+    type Err = MessageParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(mask) = s.strip_prefix('+').filter(|m| !m.is_empty()) {
+            Ok(Self::Add(mask.to_owned()))
+        } else if let Some(mask) = s.strip_prefix('-').filter(|m| !m.is_empty()) {
+            Ok(Self::Remove(mask.to_owned()))
+        } else {
+            Err(MessageParseError::InvalidSubcommand {
+                cmd: "SILENCE",
+                sub: s.to_owned(),
+            })
+        }
+    }
+}
+
+impl std::fmt::Display for SilenceChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.is_add() { '+' } else { '-' };
+        write!(f, "{}{}", sign, self.mask())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "+*!*@spam".parse::<SilenceChange>().unwrap(),
+            SilenceChange::Add("*!*@spam".to_owned())
+        );
+        assert_eq!(
+            "-nick!*@*".parse::<SilenceChange>().unwrap(),
+            SilenceChange::Remove("nick!*@*".to_owned())
+        );
+        assert!("*!*@spam".parse::<SilenceChange>().is_err());
+        assert!("+".parse::<SilenceChange>().is_err());
+        assert!("".parse::<SilenceChange>().is_err());
+        assert!("é".parse::<SilenceChange>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(SilenceChange::Add("a".to_owned()).to_string(), "+a");
+        assert_eq!(SilenceChange::Remove("b".to_owned()).to_string(), "-b");
+    }
+}
@@ -11,7 +11,10 @@ use smallvec::SmallVec;
 use crate::mode::{ChannelMode, Mode, UserMode};
 use crate::response::Response;
 
-use super::subcommands::{BatchSubCommand, CapSubCommand, ChatHistorySubCommand, MessageReference};
+use super::subcommands::{
+    BatchSubCommand, CapSubCommand, ChatHistorySubCommand, MessageReference, MonitorSubCommand,
+    ReadMarker, SilenceChange,
+};
 
 /// IRC command with its parameters.
 ///
@@ -38,6 +41,8 @@ pub enum Command {
     QUIT(Option<String>),
     /// `SQUIT server comment`
     SQUIT(String, String),
+    /// `PROTOCTL tokens...` - Legacy protocol options (e.g. `NAMESX UHNAMES`)
+    PROTOCTL(Vec<String>),
 
     // === Channel Operations (RFC 2812 Section 3.2) ===
     /// `JOIN channels [keys] [realname]`
@@ -64,6 +69,9 @@ pub enum Command {
     NOTICE(String, String),
     /// `ACCEPT [nicknames]` - Caller ID (allow/deny list)
     ACCEPT(String),
+    /// `SILENCE [+mask|-mask[,...]]` - Server-side ignore (an empty list
+    /// queries the silence list)
+    SILENCE(Vec<SilenceChange>),
 
     // === Server Queries (RFC 2812 Section 3.4) ===
     /// `MOTD [target]`
@@ -243,7 +251,7 @@ pub enum Command {
     /// `ACCOUNT accountname`
     ACCOUNT(String),
     /// `MONITOR +/-/C/L/S [targets]`
-    MONITOR(String, Option<String>),
+    MONITOR(MonitorSubCommand, Option<String>),
     /// `BATCH +/-reference [type] [params...]`
     BATCH(String, Option<BatchSubCommand>, Option<Vec<String>>),
     /// `CHGHOST user host`
@@ -277,6 +285,26 @@ pub enum Command {
         /// Maximum number of messages to return.
         limit: u32,
     },
+    /// `REDACT target msgid [:reason]` - IRCv3 draft/message-redaction
+    REDACT {
+        /// Channel (or nickname) the message was sent to.
+        channel: String,
+        /// The `msgid` of the message to remove.
+        msgid: String,
+        /// Optional reason for the redaction.
+        reason: Option<String>,
+    },
+    /// `MARKREAD target [timestamp=<ts> | *]` - IRCv3 draft/read-marker
+    ///
+    /// Sent by clients without a timestamp to query the read marker, or with
+    /// one to update it; sent by servers to report the marker, with `*` if
+    /// none is stored.
+    MARKREAD {
+        /// Channel or nickname the marker applies to.
+        target: String,
+        /// The query, timestamp or "no marker" reply.
+        marker: ReadMarker,
+    },
 
     // === Standard Replies (IRCv3) ===
     /// `FAIL command code [context...] :description`
@@ -290,11 +318,33 @@ pub enum Command {
     /// Numeric response from server
     Response(Response, Vec<String>),
 
+    // === Vendor Extensions ===
+    /// Vendor extension command listed in [`VENDOR_COMMANDS`], such as
+    /// `BOUNCER` from `soju.im/bouncer-networks`
+    ///
+    /// The first argument is usually the subcommand (e.g. `LISTNETWORKS`).
+    /// Other unknown commands are parsed as [`Raw`](Command::Raw).
+    Vendor {
+        /// Command name, in upper case.
+        name: String,
+        /// Command arguments, starting with the subcommand.
+        args: Vec<String>,
+    },
+
     // === Unknown/Raw Commands ===
     /// Unknown command captured as raw
     Raw(String, Vec<String>),
 }
 
+/// Vendor extension commands parsed as [`Command::Vendor`].
+///
+/// These are non-standard commands with no typed variant whose arguments are
+/// kept verbatim, unlike [`Command::Raw`] which is reserved for commands the
+/// crate does not know. Names are in upper case.
+///
+/// - `BOUNCER`: `soju.im/bouncer-networks`
+pub const VENDOR_COMMANDS: &[&str] = &["BOUNCER"];
+
 /// A borrowed reference to a command.
 ///
 /// Used for zero-copy parsing of IRC messages.
@@ -307,9 +357,10 @@ pub struct CommandRef<'a> {
 }
 
 impl Command {
-    /// Get the command name as a static string.
+    /// Get the command name.
     ///
     /// Returns the IRC command name (e.g., "PRIVMSG", "NICK", "JOIN").
+    /// For `Vendor` variants, returns the stored name (e.g. "BOUNCER").
     /// For `Response` variants, returns "RESPONSE".
     /// For `Raw` variants, this allocates - prefer using `Command::raw_name()` for those.
     ///
@@ -325,7 +376,7 @@ impl Command {
     /// assert_eq!(cmd.name(), "NICK");
     /// ```
     #[inline]
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &str {
         match self {
             // Connection Registration
             Command::PASS(_) => "PASS",
@@ -336,6 +387,7 @@ impl Command {
             Command::SERVICE(..) => "SERVICE",
             Command::QUIT(_) => "QUIT",
             Command::SQUIT(..) => "SQUIT",
+            Command::PROTOCTL(_) => "PROTOCTL",
 
             // Channel Operations
             Command::JOIN(..) => "JOIN",
@@ -351,6 +403,7 @@ impl Command {
             Command::PRIVMSG(..) => "PRIVMSG",
             Command::NOTICE(..) => "NOTICE",
             Command::ACCEPT(_) => "ACCEPT",
+            Command::SILENCE(_) => "SILENCE",
 
             // Server Queries
             Command::MOTD(_) => "MOTD",
@@ -453,6 +506,8 @@ impl Command {
             Command::ACK => "ACK",
             Command::WEBIRC(..) => "WEBIRC",
             Command::CHATHISTORY { .. } => "CHATHISTORY",
+            Command::REDACT { .. } => "REDACT",
+            Command::MARKREAD { .. } => "MARKREAD",
 
             // Standard Replies
             Command::FAIL(..) => "FAIL",
@@ -462,22 +517,33 @@ impl Command {
             // Numeric Response
             Command::Response(..) => "RESPONSE",
 
+            Command::Vendor { name, .. } => name,
+
             // Raw - returns "RAW", use raw_name() for the actual command
             Command::Raw(..) => "RAW",
         }
     }
 
-    /// Get the raw command name for `Raw` variants.
+    /// Get the raw command name for `Raw` and `Vendor` variants.
     ///
-    /// Returns `Some(&str)` for `Raw` commands, `None` for typed commands.
-    /// Use `name()` for typed commands.
+    /// Returns `Some(&str)` for `Raw` and `Vendor` commands, `None` for typed
+    /// commands. Use `name()` for typed commands.
     #[inline]
     pub fn raw_name(&self) -> Option<&str> {
         match self {
-            Command::Raw(name, _) => Some(name),
+            Command::Raw(name, _) | Command::Vendor { name, .. } => Some(name),
             _ => None,
         }
     }
+
+    /// Returns true if this is `JOIN 0`, a request to part all channels.
+    ///
+    /// The `0` is kept as the channel parameter when parsing, so servers
+    /// must check this before treating the JOIN as a channel name.
+    #[inline]
+    pub fn is_join_zero(&self) -> bool {
+        matches!(self, Command::JOIN(chan, _, _) if chan == "0")
+    }
 }
 
 impl<'a> CommandRef<'a> {
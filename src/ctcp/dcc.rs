@@ -0,0 +1,339 @@
+//! DCC (Direct Client-to-Client) request parsing.
+//!
+//! DCC offers travel inside a CTCP `DCC` message, for example
+//! `\x01DCC SEND file.txt 3232235777 5000 1048576\x01`. IPv4 addresses are
+//! sent as a single decimal integer in network byte order; IPv6 addresses
+//! are sent in their textual form. Filenames containing spaces are wrapped
+//! in double quotes.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use super::{Ctcp, CtcpKind};
+
+/// A parsed DCC request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DccRequest<'a> {
+    /// `DCC SEND <filename> <ip> <port> [<size>]` - offer a file.
+    Send {
+        /// Name of the offered file, without surrounding quotes.
+        filename: &'a str,
+        /// Address the sender listens on.
+        ip: IpAddr,
+        /// Port the sender listens on.
+        port: u16,
+        /// File size in bytes, if given.
+        size: Option<u64>,
+    },
+    /// `DCC CHAT chat <ip> <port>` - offer a direct chat.
+    Chat {
+        /// Address the sender listens on.
+        ip: IpAddr,
+        /// Port the sender listens on.
+        port: u16,
+    },
+    /// `DCC RESUME <filename> <port> <position>` - ask to resume a transfer.
+    Resume {
+        /// Name of the file being resumed.
+        filename: &'a str,
+        /// Port of the original offer.
+        port: u16,
+        /// Byte offset to resume from.
+        position: u64,
+    },
+    /// `DCC ACCEPT <filename> <port> <position>` - accept a resume request.
+    Accept {
+        /// Name of the file being resumed.
+        filename: &'a str,
+        /// Port of the original offer.
+        port: u16,
+        /// Byte offset the transfer resumes from.
+        position: u64,
+    },
+}
+
+impl<'a> DccRequest<'a> {
+    /// Parse a DCC request from a CTCP body such as
+    /// `DCC SEND file.txt 3232235777 5000 1048576`.
+    ///
+    /// The body must not include the `\x01` delimiters. Returns `None` if it
+    /// is not a recognized, well-formed DCC request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::net::{IpAddr, Ipv4Addr};
+    /// use slirc_proto::ctcp::dcc::DccRequest;
+    ///
+    /// let req = DccRequest::parse("DCC SEND \"my file.txt\" 3232235777 5000 1048576").unwrap();
+    /// assert_eq!(
+    ///     req,
+    ///     DccRequest::Send {
+    ///         filename: "my file.txt",
+    ///         ip: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)),
+    ///         port: 5000,
+    ///         size: Some(1048576),
+    ///     }
+    /// );
+    /// ```
+    pub fn parse(body: &'a str) -> Option<Self> {
+        let (dcc, rest) = next_token(body)?;
+        if !dcc.eq_ignore_ascii_case("DCC") {
+            return None;
+        }
+        Self::parse_params(rest)
+    }
+
+    /// Parse a DCC request from a parsed [`Ctcp`] message.
+    ///
+    /// Returns `None` if the message is not a `DCC` CTCP or its parameters
+    /// are malformed.
+    pub fn from_ctcp(ctcp: &Ctcp<'a>) -> Option<Self> {
+        match ctcp.kind {
+            CtcpKind::Dcc => Self::parse_params(ctcp.params?),
+            _ => None,
+        }
+    }
+
+    /// Parse the parameters following `DCC`.
+    fn parse_params(params: &'a str) -> Option<Self> {
+        let (kind, rest) = next_token(params)?;
+        let (argument, rest) = next_argument(rest)?;
+        let mut args = rest.split_ascii_whitespace();
+
+        let request = match kind.to_ascii_uppercase().as_str() {
+            "SEND" => DccRequest::Send {
+                filename: argument,
+                ip: parse_ip(args.next()?)?,
+                port: args.next()?.parse().ok()?,
+                size: match args.next() {
+                    Some(size) => Some(size.parse().ok()?),
+                    None => None,
+                },
+            },
+            "CHAT" => DccRequest::Chat {
+                ip: parse_ip(args.next()?)?,
+                port: args.next()?.parse().ok()?,
+            },
+            "RESUME" => DccRequest::Resume {
+                filename: argument,
+                port: args.next()?.parse().ok()?,
+                position: args.next()?.parse().ok()?,
+            },
+            "ACCEPT" => DccRequest::Accept {
+                filename: argument,
+                port: args.next()?.parse().ok()?,
+                position: args.next()?.parse().ok()?,
+            },
+            _ => return None,
+        };
+        Some(request)
+    }
+
+    /// Serialize this request as a CTCP body, without the `\x01` delimiters.
+    ///
+    /// IPv4 addresses are written in integer form and filenames containing
+    /// spaces are quoted, so the result parses back to the same request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::net::{IpAddr, Ipv4Addr};
+    /// use slirc_proto::ctcp::dcc::DccRequest;
+    ///
+    /// let req = DccRequest::Chat {
+    ///     ip: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+    ///     port: 5000,
+    /// };
+    /// assert_eq!(req.to_ctcp_body(), "DCC CHAT chat 2130706433 5000");
+    /// ```
+    pub fn to_ctcp_body(&self) -> String {
+        match self {
+            DccRequest::Send {
+                filename,
+                ip,
+                port,
+                size,
+            } => {
+                let mut body = format!(
+                    "DCC SEND {} {} {}",
+                    quote_filename(filename),
+                    format_ip(ip),
+                    port
+                );
+                if let Some(size) = size {
+                    body.push(' ');
+                    body.push_str(&size.to_string());
+                }
+                body
+            }
+            DccRequest::Chat { ip, port } => format!("DCC CHAT chat {} {}", format_ip(ip), port),
+            DccRequest::Resume {
+                filename,
+                port,
+                position,
+            } => format!(
+                "DCC RESUME {} {} {}",
+                quote_filename(filename),
+                port,
+                position
+            ),
+            DccRequest::Accept {
+                filename,
+                port,
+                position,
+            } => format!(
+                "DCC ACCEPT {} {} {}",
+                quote_filename(filename),
+                port,
+                position
+            ),
+        }
+    }
+}
+
+/// Split off the next space-separated token, skipping leading spaces.
+fn next_token(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start_matches(' ');
+    if s.is_empty() {
+        return None;
+    }
+    Some(s.split_once(' ').unwrap_or((s, "")))
+}
+
+/// Split off the next argument, which may be a double-quoted string.
+fn next_argument(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start_matches(' ');
+    match s.strip_prefix('"') {
+        Some(quoted) => quoted.split_once('"'),
+        None => next_token(s),
+    }
+}
+
+/// Decode an address in integer form, falling back to textual IPv4/IPv6.
+fn parse_ip(s: &str) -> Option<IpAddr> {
+    match s.parse::<u32>() {
+        Ok(n) => Some(IpAddr::V4(Ipv4Addr::from(n))),
+        Err(_) => s.parse().ok(),
+    }
+}
+
+/// Encode an address the way DCC peers expect it.
+fn format_ip(ip: &IpAddr) -> String {
+    match ip {
+        IpAddr::V4(v4) => u32::from(*v4).to_string(),
+        IpAddr::V6(v6) => v6.to_string(),
+    }
+}
+
+/// Quote a filename if it contains spaces.
+fn quote_filename(filename: &str) -> String {
+    if filename.contains(' ') {
+        format!("\"{}\"", filename)
+    } else {
+        filename.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn test_integer_ip() {
+        let req = DccRequest::parse("DCC SEND file.txt 3232235777 5000 1048576").unwrap();
+        assert_eq!(
+            req,
+            DccRequest::Send {
+                filename: "file.txt",
+                ip: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)),
+                port: 5000,
+                size: Some(1048576),
+            }
+        );
+        assert_eq!(parse_ip("0"), Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED)));
+        assert_eq!(
+            parse_ip("4294967295"),
+            Some(IpAddr::V4(Ipv4Addr::BROADCAST))
+        );
+        assert_eq!(parse_ip("4294967296"), None);
+    }
+
+    #[test]
+    fn test_quoted_filename() {
+        let req = DccRequest::parse("DCC SEND \"my holiday photo.jpg\" 2130706433 6000").unwrap();
+        assert_eq!(
+            req,
+            DccRequest::Send {
+                filename: "my holiday photo.jpg",
+                ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                port: 6000,
+                size: None,
+            }
+        );
+        assert_eq!(
+            req.to_ctcp_body(),
+            "DCC SEND \"my holiday photo.jpg\" 2130706433 6000"
+        );
+
+        // Unterminated quote
+        assert_eq!(DccRequest::parse("DCC SEND \"my file 1 2 3"), None);
+    }
+
+    #[test]
+    fn test_ipv6_chat() {
+        let req = DccRequest::parse("DCC CHAT chat 2001:db8::1 4000").unwrap();
+        let ip = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        assert_eq!(req, DccRequest::Chat { ip, port: 4000 });
+        assert_eq!(req.to_ctcp_body(), "DCC CHAT chat 2001:db8::1 4000");
+    }
+
+    #[test]
+    fn test_resume_accept() {
+        let req = DccRequest::parse("DCC RESUME \"a b.txt\" 5000 1024").unwrap();
+        assert_eq!(
+            req,
+            DccRequest::Resume {
+                filename: "a b.txt",
+                port: 5000,
+                position: 1024,
+            }
+        );
+        assert_eq!(req.to_ctcp_body(), "DCC RESUME \"a b.txt\" 5000 1024");
+
+        let req = DccRequest::parse("dcc accept file.txt 5000 1024").unwrap();
+        assert_eq!(
+            req,
+            DccRequest::Accept {
+                filename: "file.txt",
+                port: 5000,
+                position: 1024,
+            }
+        );
+        assert_eq!(req.to_ctcp_body(), "DCC ACCEPT file.txt 5000 1024");
+    }
+
+    #[test]
+    fn test_from_ctcp() {
+        let ctcp = Ctcp::parse("\x01DCC SEND file.txt 3232235777 5000\x01").unwrap();
+        assert!(matches!(
+            DccRequest::from_ctcp(&ctcp),
+            Some(DccRequest::Send { port: 5000, .. })
+        ));
+
+        let ctcp = Ctcp::parse("\x01VERSION\x01").unwrap();
+        assert_eq!(DccRequest::from_ctcp(&ctcp), None);
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(DccRequest::parse("SEND file.txt 1 2"), None);
+        assert_eq!(DccRequest::parse("DCC SEND file.txt 1"), None);
+        assert_eq!(DccRequest::parse("DCC SEND file.txt 1 99999"), None);
+        assert_eq!(DccRequest::parse("DCC SEND file.txt not-an-ip 5000"), None);
+        assert_eq!(DccRequest::parse("DCC SEND file.txt 1 5000 big"), None);
+        assert_eq!(DccRequest::parse("DCC XMIT file.txt 1 5000"), None);
+        assert_eq!(DccRequest::parse("DCC"), None);
+    }
+}
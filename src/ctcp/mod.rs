@@ -22,6 +22,8 @@
 //! assert_eq!(action.to_string(), "\x01ACTION dances\x01");
 //! ```
 
+pub mod dcc;
+
 use std::fmt;
 
 /// The CTCP delimiter character (`\x01`).
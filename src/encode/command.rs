@@ -2,12 +2,12 @@
 
 use std::io::{self, Write};
 
-use crate::command::Command;
+use crate::command::{Command, ReadMarker};
 use crate::command::util::{
     write_args_with_trailing, write_cmd, write_cmd_freeform, write_collapsed_mode_flags,
     write_service_args, write_standard_reply, needs_colon_prefix, IrcSink, IoWriteSink,
 };
-use super::IrcEncode;
+use super::{IrcEncode, TrailingPolicy};
 
 impl IrcEncode for Command {
     fn encode<W: Write>(&self, w: &mut W) -> io::Result<usize> {
@@ -35,6 +35,10 @@ impl IrcEncode for Command {
             Command::QUIT(Some(m)) => write_cmd(w, "QUIT", &[m]),
             Command::QUIT(None) => w.write_str("QUIT"),
             Command::SQUIT(s, c) => write_cmd_freeform(w, "SQUIT", &[s, c]),
+            Command::PROTOCTL(tokens) => {
+                let args: Vec<&str> = tokens.iter().map(|s| s.as_str()).collect();
+                write_cmd(w, "PROTOCTL", &args)
+            }
 
             // Channel Operations
             Command::JOIN(c, Some(k), Some(n)) => write_cmd(w, "JOIN", &[c, k, n]),
@@ -78,6 +82,11 @@ impl IrcEncode for Command {
             Command::PRIVMSG(t, m) => write_cmd_freeform(w, "PRIVMSG", &[t, m]),
             Command::NOTICE(t, m) => write_cmd_freeform(w, "NOTICE", &[t, m]),
             Command::ACCEPT(n) => write_cmd(w, "ACCEPT", &[n]),
+            Command::SILENCE(changes) if changes.is_empty() => write_cmd(w, "SILENCE", &[]),
+            Command::SILENCE(changes) => {
+                let list: Vec<String> = changes.iter().map(ToString::to_string).collect();
+                write_cmd(w, "SILENCE", &[&list.join(",")])
+            }
 
             // Server Queries
             Command::MOTD(Some(t)) => write_cmd(w, "MOTD", &[t]),
@@ -303,27 +312,17 @@ impl IrcEncode for Command {
 
             // IRCv3 Extensions
             Command::CAP(target, subcmd, code, params) => {
-                let mut written = w.write_str("CAP")?;
-                if let Some(t) = target {
-                    written += w.write_char(' ')?;
-                    written += w.write_str(t)?;
-                }
-                written += w.write_char(' ')?;
-                written += w.write_str(subcmd.to_str())?;
-                if let Some(c) = code {
-                    written += w.write_char(' ')?;
-                    written += w.write_str(c)?;
-                }
-                if let Some(p) = params {
-                    written += w.write_char(' ')?;
-                    written += w.write_str(p)?;
-                }
-                Ok(written)
+                let mut args: Vec<&str> = Vec::with_capacity(4);
+                args.extend(target.as_deref());
+                args.push(subcmd.to_str());
+                args.extend(code.as_deref());
+                args.extend(params.as_deref());
+                write_cmd(w, "CAP", &args)
             }
             Command::AUTHENTICATE(d) => write_cmd(w, "AUTHENTICATE", &[d]),
             Command::ACCOUNT(a) => write_cmd(w, "ACCOUNT", &[a]),
-            Command::MONITOR(c, Some(t)) => write_cmd(w, "MONITOR", &[c, t]),
-            Command::MONITOR(c, None) => write_cmd(w, "MONITOR", &[c]),
+            Command::MONITOR(c, Some(t)) => write_cmd(w, "MONITOR", &[c.as_str(), t]),
+            Command::MONITOR(c, None) => write_cmd(w, "MONITOR", &[c.as_str()]),
             Command::BATCH(t, Some(c), Some(a)) => {
                 let mut written = w.write_str("BATCH ")?;
                 written += w.write_str(t)?;
@@ -404,6 +403,28 @@ impl IrcEncode for Command {
                 }
                 Ok(written)
             }
+            Command::REDACT {
+                channel,
+                msgid,
+                reason: Some(reason),
+            } => write_cmd_freeform(w, "REDACT", &[channel, msgid, reason]),
+            Command::REDACT {
+                channel,
+                msgid,
+                reason: None,
+            } => write_cmd(w, "REDACT", &[channel, msgid]),
+            Command::MARKREAD {
+                target,
+                marker: ReadMarker::Query,
+            } => write_cmd(w, "MARKREAD", &[target]),
+            Command::MARKREAD {
+                target,
+                marker: ReadMarker::Timestamp(ts),
+            } => write_cmd(w, "MARKREAD", &[target, &format!("timestamp={}", ts)]),
+            Command::MARKREAD {
+                target,
+                marker: ReadMarker::Unset,
+            } => write_cmd(w, "MARKREAD", &[target, "*"]),
 
             // Standard Replies
             Command::FAIL(command, code, context) => {
@@ -432,14 +453,68 @@ impl IrcEncode for Command {
                 Ok(written)
             }
 
-            // Raw
-            Command::Raw(cmd, args) => {
+            // Raw and vendor extensions
+            Command::Raw(cmd, args) | Command::Vendor { name: cmd, args } => {
                 let mut written = w.write_str(cmd)?;
                 written += write_args_with_trailing(w, args.iter().map(String::as_str))?;
                 Ok(written)
             }
         }
     }
+
+    fn encode_with_trailing<W: Write>(
+        &self,
+        w: &mut W,
+        policy: TrailingPolicy,
+    ) -> io::Result<usize> {
+        match self {
+            _ if policy == TrailingPolicy::Auto => self.encode(w),
+            Command::Raw(cmd, args) | Command::Vendor { name: cmd, args } => {
+                let mut written = w.write(cmd.as_bytes())?;
+                let len = args.len();
+                for (i, arg) in args.iter().enumerate() {
+                    written += w.write(b" ")?;
+                    if i == len - 1 && policy.needs_colon(arg) {
+                        written += w.write(b":")?;
+                    }
+                    written += w.write(arg.as_bytes())?;
+                }
+                Ok(written)
+            }
+            _ => write_with_trailing(w, &self.to_bytes(), policy),
+        }
+    }
+}
+
+/// Write an encoded command, re-deciding the colon before its final
+/// parameter per `policy`.
+///
+/// Middle parameters never contain a space or start with `:`, so the final
+/// parameter begins after the first ` :` or, failing that, the last space.
+fn write_with_trailing<W: Write>(
+    w: &mut W,
+    line: &[u8],
+    policy: TrailingPolicy,
+) -> io::Result<usize> {
+    let split = match line.windows(2).position(|pair| pair == b" :") {
+        Some(i) => Some((&line[..i], &line[i + 2..])),
+        None => line
+            .iter()
+            .rposition(|&b| b == b' ')
+            .map(|i| (&line[..i], &line[i + 1..])),
+    };
+    let Some((head, last)) = split else {
+        // No parameters
+        return w.write(line);
+    };
+
+    let mut written = w.write(head)?;
+    written += w.write(b" ")?;
+    if std::str::from_utf8(last).map_or(true, |arg| policy.needs_colon(arg)) {
+        written += w.write(b":")?;
+    }
+    written += w.write(last)?;
+    Ok(written)
 }
 
 #[cfg(test)]
@@ -786,6 +861,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_cap_ls_multiple_caps() {
+        assert_eq!(
+            encode_cmd(Command::CAP(
+                Some("*".into()),
+                crate::command::CapSubCommand::LS,
+                None,
+                Some("multi-prefix sasl".into())
+            )),
+            "CAP * LS :multi-prefix sasl"
+        );
+    }
+
     #[test]
     fn test_encode_cap_end() {
         assert_eq!(
@@ -837,7 +925,10 @@ mod tests {
     #[test]
     fn test_encode_monitor() {
         assert_eq!(
-            encode_cmd(Command::MONITOR("+".into(), Some("nick1,nick2".into()))),
+            encode_cmd(Command::MONITOR(
+                crate::command::MonitorSubCommand::Add,
+                Some("nick1,nick2".into())
+            )),
             "MONITOR + nick1,nick2"
         );
     }
@@ -6,7 +6,7 @@ use crate::message::tags::escape_tag_value_to_writer;
 use crate::message::{Message, MessageRef, Tag};
 use crate::prefix::Prefix;
 
-use super::IrcEncode;
+use super::{IrcEncode, LineEnding, TrailingPolicy};
 
 /// Encode a single tag to the writer.
 fn encode_tag<W: Write>(w: &mut W, tag: &Tag) -> io::Result<usize> {
@@ -20,67 +20,156 @@ fn encode_tag<W: Write>(w: &mut W, tag: &Tag) -> io::Result<usize> {
 
 impl IrcEncode for Message {
     fn encode<W: Write>(&self, w: &mut W) -> io::Result<usize> {
-        let mut written = 0;
-
-        // Tags
-        if let Some(ref tags) = self.tags {
-            written += w.write(b"@")?;
-            for (i, tag) in tags.iter().enumerate() {
-                if i > 0 {
-                    written += w.write(b";")?;
-                }
-                written += encode_tag(w, tag)?;
+        self.encode_with_line_ending(w, LineEnding::CrLf)
+    }
+
+    fn encode_with_line_ending<W: Write>(
+        &self,
+        w: &mut W,
+        ending: LineEnding,
+    ) -> io::Result<usize> {
+        encode_owned(w, self, ending, TrailingPolicy::Auto)
+    }
+
+    fn encode_with_trailing<W: Write>(
+        &self,
+        w: &mut W,
+        policy: TrailingPolicy,
+    ) -> io::Result<usize> {
+        encode_owned(w, self, LineEnding::CrLf, policy)
+    }
+}
+
+/// Encode an owned message, writing its final parameter per `policy`.
+fn encode_owned<W: Write>(
+    w: &mut W,
+    msg: &Message,
+    ending: LineEnding,
+    policy: TrailingPolicy,
+) -> io::Result<usize> {
+    let mut written = 0;
+
+    // Tags
+    if let Some(ref tags) = msg.tags {
+        written += w.write(b"@")?;
+        for (i, tag) in tags.iter().enumerate() {
+            if i > 0 {
+                written += w.write(b";")?;
             }
-            written += w.write(b" ")?;
+            written += encode_tag(w, tag)?;
         }
+        written += w.write(b" ")?;
+    }
 
-        // Prefix
-        if let Some(ref prefix) = self.prefix {
-            written += w.write(b":")?;
-            written += prefix.encode(w)?;
-            written += w.write(b" ")?;
-        }
+    // Prefix
+    if let Some(ref prefix) = msg.prefix {
+        written += w.write(b":")?;
+        written += prefix.encode(w)?;
+        written += w.write(b" ")?;
+    }
 
-        // Command
-        written += self.command.encode(w)?;
+    // Command
+    written += msg.command.encode_with_trailing(w, policy)?;
 
-        // CRLF
-        written += w.write(b"\r\n")?;
+    // Line terminator
+    written += w.write(ending.as_bytes())?;
 
-        Ok(written)
-    }
+    Ok(written)
 }
 
 impl<'a> IrcEncode for MessageRef<'a> {
     fn encode<W: Write>(&self, w: &mut W) -> io::Result<usize> {
-        let mut written = 0;
+        self.encode_with_line_ending(w, LineEnding::CrLf)
+    }
 
-        // Tags (raw, already formatted)
-        if let Some(tags) = self.tags {
-            written += w.write(b"@")?;
-            written += w.write(tags.as_bytes())?;
-            written += w.write(b" ")?;
-        }
+    fn encode_with_line_ending<W: Write>(
+        &self,
+        w: &mut W,
+        ending: LineEnding,
+    ) -> io::Result<usize> {
+        let prefix = self.prefix.as_ref().map_or("", |p| p.raw);
+        encode_ref(w, self, prefix, ending, TrailingPolicy::Auto)
+    }
 
-        // Prefix
-        if let Some(ref prefix) = self.prefix {
-            written += w.write(b":")?;
-            written += w.write(prefix.raw.as_bytes())?;
-            written += w.write(b" ")?;
-        }
+    fn encode_with_trailing<W: Write>(
+        &self,
+        w: &mut W,
+        policy: TrailingPolicy,
+    ) -> io::Result<usize> {
+        let prefix = self.prefix.as_ref().map_or("", |p| p.raw);
+        encode_ref(w, self, prefix, LineEnding::CrLf, policy)
+    }
+}
 
-        // Command (raw)
-        written += w.write(self.command.name.as_bytes())?;
-        for arg in &self.command.args {
-            written += w.write(b" ")?;
-            written += w.write(arg.as_bytes())?;
-        }
+/// Encode a borrowed message with its prefix replaced by `prefix`.
+///
+/// Tags, command and arguments are written straight from the borrowed
+/// input; only the prefix is substituted. An empty `prefix` omits it.
+/// This lets relaying servers rewrite the source (e.g. to the sender's
+/// full hostmask) without building an owned [`Message`].
+///
+/// Returns the number of bytes written.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::encode::encode_with_prefix;
+/// use slirc_proto::MessageRef;
+///
+/// let msg = MessageRef::parse(":nick PRIVMSG #rust :hello world").unwrap();
+/// let mut buf = Vec::new();
+/// encode_with_prefix(&mut buf, &msg, "nick!user@host").unwrap();
+/// assert_eq!(&buf, b":nick!user@host PRIVMSG #rust :hello world\r\n");
+/// ```
+pub fn encode_with_prefix<W: Write>(
+    w: &mut W,
+    msg: &MessageRef<'_>,
+    prefix: &str,
+) -> io::Result<usize> {
+    encode_ref(w, msg, prefix, LineEnding::CrLf, TrailingPolicy::Auto)
+}
 
-        // CRLF
-        written += w.write(b"\r\n")?;
+/// Encode a borrowed message from its raw parts with the given prefix.
+fn encode_ref<W: Write>(
+    w: &mut W,
+    msg: &MessageRef<'_>,
+    prefix: &str,
+    ending: LineEnding,
+    policy: TrailingPolicy,
+) -> io::Result<usize> {
+    let mut written = 0;
+
+    // Tags (raw, already formatted)
+    if let Some(tags) = msg.tags {
+        written += w.write(b"@")?;
+        written += w.write(tags.as_bytes())?;
+        written += w.write(b" ")?;
+    }
 
-        Ok(written)
+    // Prefix
+    if !prefix.is_empty() {
+        written += w.write(b":")?;
+        written += w.write(prefix.as_bytes())?;
+        written += w.write(b" ")?;
     }
+
+    // Command (raw)
+    written += w.write(msg.command.name.as_bytes())?;
+    let args = &msg.command.args;
+    for (i, arg) in args.iter().enumerate() {
+        written += w.write(b" ")?;
+        // The trailing argument needs a colon if it could be misparsed
+        let is_last = i == args.len() - 1;
+        if is_last && policy.needs_colon(arg) {
+            written += w.write(b":")?;
+        }
+        written += w.write(arg.as_bytes())?;
+    }
+
+    // Line terminator
+    written += w.write(ending.as_bytes())?;
+
+    Ok(written)
 }
 
 impl IrcEncode for Prefix {
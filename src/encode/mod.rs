@@ -27,6 +27,64 @@ use std::io::{self, Write};
 mod command;
 mod message;
 
+pub use message::encode_with_prefix;
+
+/// Line terminator written after each encoded message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LineEnding {
+    /// `\r\n`, as required by the IRC protocol.
+    #[default]
+    CrLf,
+    /// `\n` only, for bridges and embedded peers that accept bare LF.
+    Lf,
+    /// No terminator, for message-framed transports such as WebSocket.
+    None,
+}
+
+impl LineEnding {
+    /// Get the terminator bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            Self::CrLf => b"\r\n",
+            Self::Lf => b"\n",
+            Self::None => b"",
+        }
+    }
+}
+
+/// When the final parameter of a message is written with a leading `:`.
+///
+/// A colon is always written when the parameter is empty, contains a space,
+/// or starts with `:`, since it would otherwise be misparsed. The policy only
+/// decides what happens to parameters that do not need one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TrailingPolicy {
+    /// Follow each command's convention: freeform text parameters (e.g. of
+    /// PRIVMSG) always get a colon, other parameters only when needed.
+    #[default]
+    Auto,
+    /// Always write a colon before the final parameter.
+    AlwaysColon,
+    /// Only write a colon when the final parameter needs one.
+    Minimal,
+}
+
+impl TrailingPolicy {
+    /// Check whether `arg`, written as the final parameter, gets a colon.
+    ///
+    /// For [`Auto`](Self::Auto) this answers for generic (untyped) commands.
+    #[must_use]
+    pub fn needs_colon(&self, arg: &str) -> bool {
+        match self {
+            Self::AlwaysColon => true,
+            Self::Auto | Self::Minimal => crate::command::util::needs_colon_prefix(arg),
+        }
+    }
+}
+
 /// A trait for encoding IRC protocol elements directly to a byte stream.
 ///
 /// This provides zero-copy encoding by writing directly to any [`Write`]
@@ -48,6 +106,62 @@ pub trait IrcEncode {
     /// Returns an I/O error if the write fails.
     fn encode<W: Write>(&self, writer: &mut W) -> io::Result<usize>;
 
+    /// Encode this value, terminating lines with `ending` instead of CRLF.
+    ///
+    /// Only complete messages end a line; for other implementors (commands,
+    /// prefixes) this is the same as [`encode`](Self::encode).
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the write fails.
+    fn encode_with_line_ending<W: Write>(
+        &self,
+        writer: &mut W,
+        ending: LineEnding,
+    ) -> io::Result<usize> {
+        let _ = ending;
+        self.encode(writer)
+    }
+
+    /// Encode this value, writing the final parameter per `policy`.
+    ///
+    /// [`TrailingPolicy::Auto`] is the same as [`encode`](Self::encode). The
+    /// other policies let proxies match a target server's style; they apply
+    /// to typed commands and [`Raw`](crate::Command::Raw) commands alike.
+    /// Values without parameters (prefixes) ignore the policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the write fails.
+    fn encode_with_trailing<W: Write>(
+        &self,
+        writer: &mut W,
+        policy: TrailingPolicy,
+    ) -> io::Result<usize> {
+        let _ = policy;
+        self.encode(writer)
+    }
+
+    /// Encode this value without the trailing line terminator.
+    ///
+    /// Useful for message-framed sinks (WebSocket text frames, log records)
+    /// that must not contain `\r\n`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the write fails.
+    fn encode_body<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        self.encode_with_line_ending(writer, LineEnding::None)
+    }
+
+    /// Encode this value to a new `Vec<u8>` without the trailing CRLF.
+    #[must_use]
+    fn to_bytes_no_crlf(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(512); // IRC max line length
+        let _ = self.encode_body(&mut buf);
+        buf
+    }
+
     /// Encode this value to a new `Vec<u8>`.
     ///
     /// This is a convenience method for cases where you need a buffer.
@@ -60,12 +174,11 @@ pub trait IrcEncode {
     }
 }
 
-
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::message::Message;
+    use crate::command::Command;
+    use crate::message::{Message, MessageRef};
     use crate::prefix::Prefix;
 
     #[test]
@@ -97,6 +210,166 @@ mod tests {
         assert_eq!(&bytes, b"@time=2023-01-01T00:00:00Z PRIVMSG #test :Hi\r\n");
     }
 
+    #[test]
+    fn test_encode_with_prefix_swaps_prefix() {
+        let raw = "@time=2023-01-01T00:00:00Z;msgid=x :nick PRIVMSG #test :Hello there";
+        let msg = MessageRef::parse(raw).unwrap();
+
+        let mut buf = Vec::new();
+        let written = encode_with_prefix(&mut buf, &msg, "nick!user@host").unwrap();
+        assert_eq!(
+            &buf,
+            b"@time=2023-01-01T00:00:00Z;msgid=x :nick!user@host PRIVMSG #test :Hello there\r\n"
+        );
+        assert_eq!(written, buf.len());
+
+        // Adding a prefix to a message that had none
+        let msg = MessageRef::parse("JOIN #test").unwrap();
+        let mut buf = Vec::new();
+        encode_with_prefix(&mut buf, &msg, "nick!user@host").unwrap();
+        assert_eq!(&buf, b":nick!user@host JOIN #test\r\n");
+
+        // An empty prefix drops it
+        let msg = MessageRef::parse(":server PING :token").unwrap();
+        let mut buf = Vec::new();
+        encode_with_prefix(&mut buf, &msg, "").unwrap();
+        assert_eq!(&buf, b"PING token\r\n");
+    }
+
+    #[test]
+    fn test_encode_line_endings() {
+        let msg = Message::privmsg("#test", "Hi").with_tag("msgid", Some("a"));
+
+        let mut buf = Vec::new();
+        let written = msg
+            .encode_with_line_ending(&mut buf, LineEnding::Lf)
+            .unwrap();
+        assert_eq!(&buf, b"@msgid=a PRIVMSG #test :Hi\n");
+        assert!(!buf.contains(&b'\r'));
+        assert_eq!(written, buf.len());
+
+        let mut buf = Vec::new();
+        msg.encode_with_line_ending(&mut buf, LineEnding::None)
+            .unwrap();
+        assert_eq!(&buf, b"@msgid=a PRIVMSG #test :Hi");
+
+        let mut buf = Vec::new();
+        msg.encode_with_line_ending(&mut buf, LineEnding::default())
+            .unwrap();
+        assert_eq!(buf, msg.to_bytes());
+
+        let msg_ref = MessageRef::parse(":nick PRIVMSG #test :Hi").unwrap();
+        let mut buf = Vec::new();
+        msg_ref
+            .encode_with_line_ending(&mut buf, LineEnding::Lf)
+            .unwrap();
+        assert_eq!(&buf, b":nick PRIVMSG #test Hi\n");
+    }
+
+    #[test]
+    fn test_encode_body_plus_crlf_equals_encode() {
+        let messages = [
+            Message::privmsg("#test", "Hello world!"),
+            Message::nick("testnick").with_tag("label", Some("a b")),
+            Message::join("#rust").with_prefix(Prefix::new_from_str("nick!user@host")),
+        ];
+        for msg in &messages {
+            let mut body = Vec::new();
+            let written = msg.encode_body(&mut body).unwrap();
+            assert_eq!(written, body.len());
+            assert_eq!(body, msg.to_bytes_no_crlf());
+
+            body.extend_from_slice(b"\r\n");
+            assert_eq!(body, msg.to_bytes());
+        }
+
+        let msg_ref = MessageRef::parse("@a=1 :nick PRIVMSG #test :hi there").unwrap();
+        let mut body = msg_ref.to_bytes_no_crlf();
+        assert_eq!(&body, b"@a=1 :nick PRIVMSG #test :hi there");
+        body.extend_from_slice(b"\r\n");
+        assert_eq!(body, msg_ref.to_bytes());
+    }
+
+    #[test]
+    fn test_encode_trailing_policy_single_word() {
+        fn encode<T: IrcEncode>(value: &T, policy: TrailingPolicy) -> String {
+            let mut buf = Vec::new();
+            let written = value.encode_with_trailing(&mut buf, policy).unwrap();
+            assert_eq!(written, buf.len());
+            String::from_utf8(buf).unwrap()
+        }
+
+        let raw = Command::Raw("CUSTOM".into(), vec!["#c".into(), "hello".into()]);
+        let always = TrailingPolicy::AlwaysColon;
+        assert_eq!(encode(&raw, TrailingPolicy::Auto), "CUSTOM #c hello");
+        assert_eq!(encode(&raw, always), "CUSTOM #c :hello");
+        assert_eq!(encode(&raw, TrailingPolicy::Minimal), "CUSTOM #c hello");
+
+        let msg = Message::privmsg("#c", "hello").with_tag("msgid", Some("a"));
+        assert_eq!(
+            encode(&msg, TrailingPolicy::Auto),
+            "@msgid=a PRIVMSG #c :hello\r\n"
+        );
+        assert_eq!(
+            encode(&msg, TrailingPolicy::AlwaysColon),
+            "@msgid=a PRIVMSG #c :hello\r\n"
+        );
+        assert_eq!(
+            encode(&msg, TrailingPolicy::Minimal),
+            "@msgid=a PRIVMSG #c hello\r\n"
+        );
+
+        let msg_ref = MessageRef::parse(":nick PRIVMSG #c hello").unwrap();
+        assert_eq!(
+            encode(&msg_ref, TrailingPolicy::AlwaysColon),
+            ":nick PRIVMSG #c :hello\r\n"
+        );
+        assert_eq!(
+            encode(&msg_ref, TrailingPolicy::Minimal),
+            ":nick PRIVMSG #c hello\r\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_trailing_policy_keeps_required_colon() {
+        let msg = Message::privmsg("#c", "hello world");
+        let mut buf = Vec::new();
+        msg.encode_with_trailing(&mut buf, TrailingPolicy::Minimal)
+            .unwrap();
+        assert_eq!(&buf, b"PRIVMSG #c :hello world\r\n");
+
+        let msg = Message::join("#c");
+        let mut buf = Vec::new();
+        msg.encode_with_trailing(&mut buf, TrailingPolicy::AlwaysColon)
+            .unwrap();
+        assert_eq!(&buf, b"JOIN :#c\r\n");
+    }
+
+    #[test]
+    fn test_encode_trailing_policy_unparseable_message() {
+        // Tags beyond what the parser accepts must still encode
+        let big = "x".repeat(9000);
+        let msg = Message::privmsg("#c", "hello").with_tag("big", Some(big.as_str()));
+        let mut buf = Vec::new();
+        let written = msg
+            .encode_with_trailing(&mut buf, TrailingPolicy::Minimal)
+            .unwrap();
+        assert_eq!(written, buf.len());
+        assert!(buf.ends_with(b" PRIVMSG #c hello\r\n"));
+
+        let user = Command::USER("u".into(), "0".into(), "Real".into());
+        let mut buf = Vec::new();
+        user.encode_with_trailing(&mut buf, TrailingPolicy::Minimal)
+            .unwrap();
+        assert_eq!(&buf, b"USER u 0 * Real");
+
+        let quit = Command::QUIT(None);
+        let mut buf = Vec::new();
+        quit.encode_with_trailing(&mut buf, TrailingPolicy::AlwaysColon)
+            .unwrap();
+        assert_eq!(&buf, b"QUIT");
+    }
+
     #[test]
     fn test_encode_returns_byte_count() {
         let msg = Message::ping("server");
@@ -5,6 +5,8 @@
 
 use thiserror::Error;
 
+use crate::isupport::LimitKind;
+
 /// Convenience type alias for Results using [`ProtocolError`].
 pub type Result<T, E = ProtocolError> = std::result::Result<T, E>;
 
@@ -46,6 +48,21 @@ pub enum ProtocolError {
     #[error("illegal control character: {0:?}")]
     IllegalControlChar(char),
 
+    /// An argument was not valid for the message being built.
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    /// Text was longer than a server-advertised ISUPPORT length limit.
+    #[error("{} exceeded: {actual} bytes (limit: {limit})", kind.token())]
+    LengthExceeded {
+        /// The limit that was exceeded.
+        kind: LimitKind,
+        /// Actual length in bytes.
+        actual: usize,
+        /// Maximum allowed length in bytes.
+        limit: usize,
+    },
+
     /// Failed to parse an IRC message.
     #[error("invalid message: {string}")]
     InvalidMessage {
@@ -134,6 +151,26 @@ pub enum MessageParseError {
     #[error("unterminated origin/prefix")]
     UnterminatedOrigin,
 
+    /// A tag key did not match the IRCv3 key grammar.
+    #[error("invalid tag key: {0:?}")]
+    InvalidTagKey(String),
+
+    /// Tags section was longer than allowed.
+    #[error("tags section too long: {actual} bytes (limit: {limit})")]
+    TagsTooLong {
+        /// Length of the tags section, including `@` and the trailing space.
+        actual: usize,
+        /// Maximum length allowed.
+        limit: usize,
+    },
+
+    /// Message carried more tags than allowed.
+    #[error("too many tags (limit: {limit})")]
+    TooManyTags {
+        /// Maximum number of tags allowed.
+        limit: usize,
+    },
+
     /// Parsing error with detailed context information.
     #[error("parsing failed at position {position}: {context}")]
     ParseContext {
@@ -186,6 +223,14 @@ impl Clone for MessageParseError {
             }
             MessageParseError::UnterminatedTags => MessageParseError::UnterminatedTags,
             MessageParseError::UnterminatedOrigin => MessageParseError::UnterminatedOrigin,
+            MessageParseError::InvalidTagKey(key) => MessageParseError::InvalidTagKey(key.clone()),
+            MessageParseError::TagsTooLong { actual, limit } => MessageParseError::TagsTooLong {
+                actual: *actual,
+                limit: *limit,
+            },
+            MessageParseError::TooManyTags { limit } => {
+                MessageParseError::TooManyTags { limit: *limit }
+            }
             MessageParseError::ParseContext {
                 position,
                 context,
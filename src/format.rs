@@ -88,6 +88,45 @@ pub fn is_illegal_control_char(ch: char) -> bool {
     ch.is_control() && ch != '\r' && ch != '\n' && !is_irc_format_code(ch)
 }
 
+/// Returns the first illegal character in a received IRC line, if any.
+///
+/// A single trailing `\r\n` or `\n` terminator is ignored. Any other `\r`
+/// is a bare CR (not immediately followed by `\n`) and is reported as
+/// illegal: some servers treat it as a line break, so accepting it would
+/// let one line carry a second command. An embedded `\n` is rejected for
+/// the same reason. Remaining characters are checked with
+/// [`is_illegal_control_char`].
+///
+/// # Examples
+///
+/// ```
+/// use slirc_proto::format::find_illegal_line_char;
+///
+/// assert_eq!(find_illegal_line_char("PRIVMSG #c :hi\r\n"), None);
+/// assert_eq!(find_illegal_line_char("PRIVMSG #c :hi\n"), None);
+/// assert_eq!(find_illegal_line_char("PRIVMSG #c :a\rb\n"), Some('\r'));
+/// assert_eq!(find_illegal_line_char("PRIVMSG #c :a\0b\r\n"), Some('\0'));
+/// ```
+pub fn find_illegal_line_char(line: &str) -> Option<char> {
+    find_illegal_text_char(strip_line_ending(line))
+}
+
+/// Returns the first character that may not appear inside a single IRC
+/// line: any CR or LF, or a character rejected by [`is_illegal_control_char`].
+pub(crate) fn find_illegal_text_char(text: &str) -> Option<char> {
+    text.chars()
+        .find(|&ch| ch == '\r' || ch == '\n' || is_illegal_control_char(ch))
+}
+
+/// Strip a single trailing `\r\n` or `\n` terminator, leaving any other
+/// CR or LF in place.
+pub(crate) fn strip_line_ending(line: &str) -> &str {
+    match line.strip_suffix('\n') {
+        Some(rest) => rest.strip_suffix('\r').unwrap_or(rest),
+        None => line,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,6 +180,15 @@ mod tests {
         assert!(is_illegal_control_char('\x08')); // BS - not a format code
     }
 
+    #[test]
+    fn test_bare_cr_rejected() {
+        assert_eq!(find_illegal_line_char("PRIVMSG #c :a\rb\n"), Some('\r'));
+        assert_eq!(find_illegal_line_char("PRIVMSG #c :a\r\r\n"), Some('\r'));
+        assert_eq!(find_illegal_line_char("PRIVMSG #c :a\r\n"), None);
+        assert_eq!(find_illegal_line_char("PRIVMSG #c :a\n"), None);
+        assert_eq!(find_illegal_line_char("PRIVMSG #c :a"), None);
+    }
+
     #[test]
     fn test_ctcp_allowed() {
         // CTCP delimiter must be allowed for CTCP messages like ACTION
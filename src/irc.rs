@@ -35,6 +35,13 @@ impl IrcCodec {
         LineCodec::with_max_len(label, max_len).map(|codec| Self { inner: codec })
     }
 
+    /// Require every incoming line to be valid UTF-8.
+    ///
+    /// See [`LineCodec::set_utf8_only`].
+    pub fn set_utf8_only(&mut self, utf8_only: bool) {
+        self.inner.set_utf8_only(utf8_only);
+    }
+
     /// Sanitize outgoing message data.
     ///
     /// - Truncates at first line ending
@@ -1,8 +1,12 @@
-//! Batch reference generation for IRCv3 BATCH command.
+//! Batch reference generation and tracking for IRCv3 BATCH command.
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::command::Command;
+use crate::message::{Message, MessageRef};
+
 static BATCH_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 /// Generate a unique batch reference string.
@@ -17,6 +21,108 @@ pub fn generate_batch_ref() -> String {
     format!("{}-{}", timestamp, counter)
 }
 
+/// Tracks open batches so batched messages can be grouped for display.
+///
+/// Feed every incoming message. `BATCH +ref` opens a batch and
+/// `BATCH -ref` closes it; while open, [`opening`](Self::opening) maps the
+/// reference (as found via [`MessageRef::batch_ref`]) to its opening
+/// `BATCH` message. A batch opened inside another batch carries a `batch`
+/// tag itself, which [`chain`](Self::chain) follows to resolve nesting.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::ircv3::BatchStack;
+/// use slirc_proto::MessageRef;
+///
+/// let mut batches = BatchStack::new();
+/// batches.feed(&MessageRef::parse(":irc.test BATCH +abc chathistory #rust").unwrap());
+///
+/// let msg = MessageRef::parse("@batch=abc :nick PRIVMSG #rust :old").unwrap();
+/// let reference = msg.batch_ref().unwrap();
+/// assert!(batches.opening(reference).is_some());
+/// assert_eq!(batches.batch_type(reference), Some("CHATHISTORY"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BatchStack {
+    open: HashMap<String, Message>,
+}
+
+impl BatchStack {
+    /// Create an empty tracker.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a message, opening or closing a batch if it is a `BATCH` command.
+    ///
+    /// Returns the opening message when a batch is closed.
+    pub fn feed(&mut self, msg: &MessageRef<'_>) -> Option<Message> {
+        if !msg.command_eq("BATCH") {
+            return None;
+        }
+        let reference = msg.arg(0)?;
+        if let Some(reference) = reference.strip_prefix('+') {
+            self.open.insert(reference.to_string(), msg.to_owned());
+            None
+        } else if let Some(reference) = reference.strip_prefix('-') {
+            self.open.remove(reference)
+        } else {
+            None
+        }
+    }
+
+    /// Get the opening `BATCH` message for an open reference.
+    #[must_use]
+    pub fn opening(&self, reference: &str) -> Option<&Message> {
+        self.open.get(reference)
+    }
+
+    /// Get the type of an open batch, uppercased as by [`BatchSubCommand`]
+    /// (e.g. `NETSPLIT`, `CHATHISTORY`).
+    ///
+    /// [`BatchSubCommand`]: crate::command::BatchSubCommand
+    #[must_use]
+    pub fn batch_type(&self, reference: &str) -> Option<&str> {
+        match self.open.get(reference)?.command {
+            Command::BATCH(_, Some(ref sub), _) => Some(sub.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Iterate over the opening messages from `reference` out to the
+    /// outermost enclosing batch.
+    pub fn chain<'s>(&'s self, reference: &str) -> impl Iterator<Item = &'s Message> + 's {
+        let mut next = self.open.get(reference);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = current.batch_ref().and_then(|parent| self.open.get(parent));
+            Some(current)
+        })
+        // A malicious server could make batches reference each other
+        .take(self.open.len())
+    }
+
+    /// Get the opening message of the outermost batch containing `reference`.
+    #[must_use]
+    pub fn root(&self, reference: &str) -> Option<&Message> {
+        self.chain(reference).last()
+    }
+
+    /// Number of currently open batches.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Check whether no batches are open.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.open.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,4 +155,57 @@ mod tests {
         let counter2: u64 = ref2.split('-').nth(1).unwrap().parse().unwrap();
         assert!(counter2 > counter1);
     }
+
+    fn feed(stack: &mut BatchStack, raw: &str) -> Option<Message> {
+        stack.feed(&MessageRef::parse(raw).unwrap())
+    }
+
+    #[test]
+    fn test_batch_ref_tag() {
+        let mut stack = BatchStack::new();
+        feed(&mut stack, ":irc.test BATCH +r1 netsplit irc.a irc.b");
+
+        let msg = MessageRef::parse("@batch=r1 :nick!u@h QUIT :irc.a irc.b").unwrap();
+        assert_eq!(msg.batch_ref(), Some("r1"));
+        assert_eq!(stack.batch_type("r1"), Some("NETSPLIT"));
+        assert!(stack.opening("r1").is_some());
+
+        let plain = MessageRef::parse(":nick!u@h QUIT :bye").unwrap();
+        assert_eq!(plain.batch_ref(), None);
+
+        let closed = feed(&mut stack, ":irc.test BATCH -r1").unwrap();
+        assert!(matches!(closed.command, Command::BATCH(ref r, _, _) if r == "+r1"));
+        assert!(stack.is_empty());
+        assert!(stack.opening("r1").is_none());
+    }
+
+    #[test]
+    fn test_nested_batches() {
+        let mut stack = BatchStack::new();
+        feed(&mut stack, ":irc.test BATCH +outer labeled-response");
+        feed(
+            &mut stack,
+            "@batch=outer :irc.test BATCH +inner chathistory #rust",
+        );
+        assert_eq!(stack.len(), 2);
+
+        let msg = MessageRef::parse("@batch=inner :nick PRIVMSG #rust :old").unwrap();
+        let chain: Vec<_> = stack.chain(msg.batch_ref().unwrap()).collect();
+        assert_eq!(chain.len(), 2);
+        assert_eq!(stack.batch_type("inner"), Some("CHATHISTORY"));
+        assert_eq!(chain[1], stack.opening("outer").unwrap());
+        assert_eq!(stack.root("inner").and_then(|m| m.batch_ref()), None);
+
+        feed(&mut stack, "@batch=outer :irc.test BATCH -inner");
+        assert_eq!(stack.chain("inner").count(), 0);
+        assert_eq!(stack.chain("outer").count(), 1);
+    }
+
+    #[test]
+    fn test_batch_cycle_terminates() {
+        let mut stack = BatchStack::new();
+        feed(&mut stack, "@batch=b :irc.test BATCH +a x");
+        feed(&mut stack, "@batch=a :irc.test BATCH +b y");
+        assert_eq!(stack.chain("a").count(), 2);
+    }
 }
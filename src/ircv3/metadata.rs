@@ -0,0 +1,91 @@
+//! Parsing of `draft/metadata-2` numeric replies.
+
+use crate::response::Response;
+
+/// A metadata key/value pair from `RPL_WHOISKEYVALUE` (760) or
+/// `RPL_KEYVALUE` (761).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetadataKeyValue<'a> {
+    /// The user or channel the key belongs to.
+    pub target: &'a str,
+    /// The metadata key.
+    pub key: &'a str,
+    /// Visibility of the key (`*` for public).
+    pub visibility: &'a str,
+    /// The value, or `None` if the reply carries no value.
+    pub value: Option<&'a str>,
+}
+
+/// Parse the arguments of a metadata key/value reply.
+///
+/// Both replies have the form `<client> <target> <key> <visibility> [:<value>]`.
+/// Returns `None` for any other response or if arguments are missing.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::ircv3::metadata::parse_keyvalue;
+/// use slirc_proto::Response;
+///
+/// let args = ["me", "alice", "url", "*", "https://example.com"];
+/// let kv = parse_keyvalue(Response::RPL_KEYVALUE, &args).unwrap();
+/// assert_eq!(kv.target, "alice");
+/// assert_eq!(kv.key, "url");
+/// assert_eq!(kv.value, Some("https://example.com"));
+/// ```
+pub fn parse_keyvalue<S: AsRef<str>>(resp: Response, args: &[S]) -> Option<MetadataKeyValue<'_>> {
+    if !matches!(resp, Response::RPL_WHOISKEYVALUE | Response::RPL_KEYVALUE) {
+        return None;
+    }
+    match args {
+        [_, target, key, visibility, rest @ ..] => Some(MetadataKeyValue {
+            target: target.as_ref(),
+            key: key.as_ref(),
+            visibility: visibility.as_ref(),
+            value: rest.first().map(AsRef::as_ref),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Command;
+    use crate::Message;
+
+    fn response_args(raw: &str) -> (Response, Vec<String>) {
+        match raw.parse::<Message>().unwrap().command {
+            Command::Response(resp, args) => (resp, args),
+            other => panic!("Expected numeric, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_keyvalue() {
+        let (resp, args) =
+            response_args(":irc.test 761 me #rust topic-url * :https://rust-lang.org");
+        assert!(resp.is_metadata_related());
+        assert_eq!(
+            parse_keyvalue(resp, &args),
+            Some(MetadataKeyValue {
+                target: "#rust",
+                key: "topic-url",
+                visibility: "*",
+                value: Some("https://rust-lang.org"),
+            })
+        );
+
+        let (resp, args) = response_args(":irc.test 760 me alice avatar *");
+        assert_eq!(parse_keyvalue(resp, &args).unwrap().value, None);
+    }
+
+    #[test]
+    fn test_keynotset() {
+        let (resp, args) = response_args(":irc.test 768 me alice url :key not set");
+        assert_eq!(resp, Response::ERR_KEYNOTSET);
+        assert!(resp.is_metadata_related());
+        assert!(resp.is_error());
+        assert_eq!(parse_keyvalue(resp, &args), None);
+    }
+}
@@ -1,17 +1,22 @@
 //! IRCv3 extensions and utilities.
 //!
 //! This module provides helpers for IRCv3 features including:
-//! - Batch reference generation
+//! - Batch reference generation and tracking
+//! - Metadata numeric reply parsing
 //! - Message ID generation
 //! - Server-time formatting
 
 /// Batch processing utilities.
 pub mod batch;
+/// Metadata numeric reply parsing.
+pub mod metadata;
 /// Message ID generation.
 pub mod msgid;
 /// Server-time formatting.
 pub mod server_time;
 
-pub use self::batch::generate_batch_ref;
+pub use self::batch::{generate_batch_ref, BatchStack};
+#[cfg(feature = "crypto")]
+pub use self::msgid::content_msgid;
 pub use self::msgid::generate_msgid;
 pub use self::server_time::{format_server_time, format_timestamp, parse_server_time};
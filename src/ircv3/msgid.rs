@@ -3,6 +3,14 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+#[cfg(feature = "crypto")]
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+#[cfg(feature = "crypto")]
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "crypto")]
+use crate::Message;
+
 static MSGID_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 /// Generate a unique message ID string.
@@ -17,6 +25,35 @@ pub fn generate_msgid() -> String {
     format!("{}-{}", timestamp, counter)
 }
 
+/// Derive a message ID from the message content.
+///
+/// Hashes the [canonical form](Message::canonical_bytes) of the message
+/// with SHA-256 and encodes the digest as unpadded URL-safe base64, giving
+/// a 43-character ID. Any existing `msgid` tag is ignored, so the ID of a
+/// message does not depend on whether one was already assigned.
+///
+/// Identical messages get identical IDs; include a `time` tag to tell
+/// repeated messages apart. Requires the `crypto` feature.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::ircv3::content_msgid;
+/// use slirc_proto::Message;
+///
+/// let msg = Message::privmsg("#rust", "hello");
+/// assert_eq!(content_msgid(&msg), content_msgid(&msg.clone()));
+/// assert_eq!(content_msgid(&msg).len(), 43);
+/// ```
+#[cfg(feature = "crypto")]
+pub fn content_msgid(msg: &Message) -> String {
+    let mut msg = msg.clone();
+    if let Some(tags) = &mut msg.tags {
+        tags.retain(|tag| tag.0 != "msgid");
+    }
+    URL_SAFE_NO_PAD.encode(Sha256::digest(msg.canonical_bytes()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,6 +82,38 @@ mod tests {
         assert_ne!(id1, id3);
     }
 
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_content_msgid() {
+        let a: Message =
+            "@time=2024-01-01T00:00:00.000Z;account=alice :alice!a@host PRIVMSG #rust :hi"
+                .parse()
+                .unwrap();
+        let b: Message =
+            "@account=alice;time=2024-01-01T00:00:00.000Z;msgid=xyz :alice!a@host PRIVMSG #rust hi"
+                .parse()
+                .unwrap();
+        assert_eq!(content_msgid(&a), content_msgid(&b));
+
+        let id = content_msgid(&a);
+        assert_eq!(id.len(), 43);
+        assert!(id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+
+        let c: Message =
+            "@time=2024-01-01T00:00:00.000Z;account=alice :alice!a@host PRIVMSG #rust :hi!"
+                .parse()
+                .unwrap();
+        assert_ne!(content_msgid(&a), content_msgid(&c));
+
+        let d: Message =
+            "@time=2024-01-01T00:00:01.000Z;account=alice :alice!a@host PRIVMSG #rust :hi"
+                .parse()
+                .unwrap();
+        assert_ne!(content_msgid(&a), content_msgid(&d));
+    }
+
     #[test]
     fn test_msgid_counter_increments() {
         let id1 = generate_msgid();
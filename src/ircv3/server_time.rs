@@ -28,7 +28,9 @@ pub fn format_timestamp(unix_secs: u64) -> String {
 /// Parse an IRCv3 server-time string to nanoseconds since Unix epoch.
 ///
 /// Accepts RFC 3339 formatted timestamps like `2023-01-01T12:00:00.000Z`.
-/// Returns 0 if parsing fails.
+/// The spec mandates millisecond precision, but any number of fractional
+/// digits (including none) is accepted; digits beyond nanosecond precision
+/// are truncated. Returns 0 if parsing fails.
 pub fn parse_server_time(ts: &str) -> i64 {
     use chrono::DateTime;
 
@@ -60,7 +62,30 @@ mod tests {
         let ts = "2023-01-01T12:00:00.000Z";
         let nanos = parse_server_time(ts);
         // 2023-01-01 12:00:00 UTC = 1672574400 seconds
-        assert_eq!(nanos, 1672574400_000_000_000);
+        assert_eq!(nanos, 1_672_574_400_000_000_000);
+    }
+
+    #[test]
+    fn test_parse_server_time_fractional_precision() {
+        let base = 1_672_574_400_000_000_000;
+        assert_eq!(parse_server_time("2023-01-01T12:00:00Z"), base);
+        assert_eq!(
+            parse_server_time("2023-01-01T12:00:00.1Z"),
+            base + 100_000_000
+        );
+        assert_eq!(
+            parse_server_time("2023-01-01T12:00:00.123456Z"),
+            base + 123_456_000
+        );
+        assert_eq!(
+            parse_server_time("2023-01-01T12:00:00.123456789Z"),
+            base + 123_456_789
+        );
+        // Digits beyond nanoseconds are truncated
+        assert_eq!(
+            parse_server_time("2023-01-01T12:00:00.123456789999Z"),
+            base + 123_456_789
+        );
     }
 
     #[test]
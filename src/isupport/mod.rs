@@ -9,5 +9,8 @@
 mod parser;
 mod tokens;
 
-pub use parser::{parse_params, ChanModes, Isupport, IsupportEntry, MaxList, PrefixSpec, TargMax};
+pub use parser::{
+    parse_params, ChanLimit, ChanModes, Isupport, IsupportConflict, IsupportEntry, LimitKind,
+    MaxList, PrefixSpec, TargMax,
+};
 pub use tokens::{ChanModesBuilder, IsupportBuilder, TargMaxBuilder};
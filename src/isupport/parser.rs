@@ -1,5 +1,7 @@
 //! ISUPPORT parsing and data structures.
 
+use crate::casemap::CaseMapping;
+
 /// A single ISUPPORT key-value entry.
 ///
 /// Represents a token from an ISUPPORT line, which can be either:
@@ -13,6 +15,19 @@ pub struct IsupportEntry<'a> {
     pub value: Option<&'a str>,
 }
 
+/// An ISUPPORT token that a later line redefined with a different value.
+///
+/// Recorded by [`Isupport::merge`] for diagnostics; the later value wins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IsupportConflict<'a> {
+    /// The token key, as spelled in the redefining line.
+    pub key: &'a str,
+    /// The value that was replaced.
+    pub previous: Option<&'a str>,
+    /// The value now in effect.
+    pub value: Option<&'a str>,
+}
+
 /// Parsed ISUPPORT (005) server capabilities.
 ///
 /// Contains all tokens from one or more `RPL_ISUPPORT` messages, providing
@@ -32,6 +47,7 @@ pub struct IsupportEntry<'a> {
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Isupport<'a> {
     entries: Vec<IsupportEntry<'a>>,
+    conflicts: Vec<IsupportConflict<'a>>,
 }
 
 impl<'a> Isupport<'a> {
@@ -79,6 +95,62 @@ impl<'a> Isupport<'a> {
         None
     }
 
+    /// Merge tokens from another ISUPPORT line into this one.
+    ///
+    /// Servers split ISUPPORT across several `RPL_ISUPPORT` replies. Tokens
+    /// in `other` replace any earlier token with the same key, and a negated
+    /// token such as `-NICKLEN` removes a previously advertised one.
+    ///
+    /// A token redefined with a different value is recorded in
+    /// [`conflicts`](Self::conflicts); negation is not a conflict.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::isupport::parse_params;
+    ///
+    /// let mut isupport = parse_params(&["NICKLEN=30", "NETWORK=TestNet"]);
+    /// isupport.merge(parse_params(&["NICKLEN=16", "-NETWORK"]));
+    ///
+    /// assert_eq!(isupport.nicklen(), Some(16));
+    /// assert_eq!(isupport.network(), None);
+    /// ```
+    pub fn merge(&mut self, other: Isupport<'a>) {
+        self.conflicts.extend(other.conflicts);
+        for entry in other.entries {
+            let key = entry.key.strip_prefix('-').unwrap_or(entry.key);
+            if !entry.key.starts_with('-') {
+                if let Some(previous) = self.get(key).filter(|&v| v != entry.value) {
+                    self.conflicts.push(IsupportConflict {
+                        key,
+                        previous,
+                        value: entry.value,
+                    });
+                }
+            }
+            self.entries.retain(|e| !e.key.eq_ignore_ascii_case(key));
+            if !entry.key.starts_with('-') {
+                self.entries.push(entry);
+            }
+        }
+    }
+
+    /// Get the tokens that a merged line redefined with a different value,
+    /// in the order they were seen.
+    pub fn conflicts(&self) -> &[IsupportConflict<'a>] {
+        &self.conflicts
+    }
+
+    /// Parse the arguments of another `RPL_ISUPPORT` reply and merge them in.
+    ///
+    /// Accepts the same arguments as [`from_response_args`](Self::from_response_args);
+    /// see [`merge`](Self::merge) for how tokens are combined.
+    pub fn parse_and_merge(&mut self, args: &[&'a str]) {
+        if let Some(other) = Self::from_response_args(args) {
+            self.merge(other);
+        }
+    }
+
     /// Iterate over all parsed ISUPPORT entries.
     pub fn iter(&self) -> impl Iterator<Item = &IsupportEntry<'a>> {
         self.entries.iter()
@@ -96,16 +168,87 @@ impl<'a> Isupport<'a> {
             .map(|e| e.value)
     }
 
-    /// Get the `CASEMAPPING` value (e.g., `rfc1459`, `ascii`).
-    pub fn casemapping(&self) -> Option<&'a str> {
+    /// Get the raw `CASEMAPPING` value (e.g., `rfc1459`, `ascii`).
+    pub fn casemapping_name(&self) -> Option<&'a str> {
         self.get("CASEMAPPING").flatten()
     }
 
+    /// Get the server's [`CaseMapping`].
+    ///
+    /// Falls back to `rfc1459`, the historical default, if the token is
+    /// absent or names a mapping this crate does not implement.
+    pub fn casemapping(&self) -> CaseMapping {
+        self.casemapping_name()
+            .and_then(CaseMapping::parse)
+            .unwrap_or_default()
+    }
+
     /// Get the `CHANTYPES` value (e.g., `#&`).
     pub fn chantypes(&self) -> Option<&'a str> {
         self.get("CHANTYPES").flatten()
     }
 
+    /// Get an advertised length limit.
+    ///
+    /// Returns `None` if the token is absent or its value is not a number.
+    pub fn length_limit(&self, kind: LimitKind) -> Option<usize> {
+        self.get(kind.token())
+            .flatten()
+            .and_then(|v| v.parse().ok())
+    }
+
+    /// Iterate over every length limit the server advertised.
+    pub fn length_limits(&self) -> impl Iterator<Item = (LimitKind, usize)> + '_ {
+        LimitKind::ALL
+            .iter()
+            .filter_map(|&kind| Some((kind, self.length_limit(kind)?)))
+    }
+
+    /// Get the maximum nickname length (`NICKLEN`).
+    pub fn nicklen(&self) -> Option<usize> {
+        self.length_limit(LimitKind::Nick)
+    }
+
+    /// Get the maximum channel name length (`CHANNELLEN`).
+    pub fn channellen(&self) -> Option<usize> {
+        self.length_limit(LimitKind::Channel)
+    }
+
+    /// Get the maximum topic length in bytes (`TOPICLEN`).
+    pub fn topiclen(&self) -> Option<usize> {
+        self.length_limit(LimitKind::Topic)
+    }
+
+    /// Get the maximum away message length in bytes (`AWAYLEN`).
+    pub fn awaylen(&self) -> Option<usize> {
+        self.length_limit(LimitKind::Away)
+    }
+
+    /// Get the maximum kick reason length in bytes (`KICKLEN`).
+    pub fn kicklen(&self) -> Option<usize> {
+        self.length_limit(LimitKind::Kick)
+    }
+
+    /// Get the maximum hostname length (`HOSTLEN`).
+    pub fn hostlen(&self) -> Option<usize> {
+        self.length_limit(LimitKind::Host)
+    }
+
+    /// Get the maximum username length (`USERLEN`).
+    pub fn userlen(&self) -> Option<usize> {
+        self.length_limit(LimitKind::User)
+    }
+
+    /// Check if the server requires all messages to be valid UTF-8 (`UTF8ONLY`).
+    pub fn utf8only(&self) -> bool {
+        self.get("UTF8ONLY").is_some()
+    }
+
+    /// Check if the server supports RFC 2811 safe (`!`) channels.
+    pub fn has_safe_channels(&self) -> bool {
+        self.chantypes().is_some_and(|types| types.contains('!'))
+    }
+
     /// Get the `NETWORK` name (e.g., `Libera.Chat`).
     pub fn network(&self) -> Option<&'a str> {
         self.get("NETWORK").flatten()
@@ -141,6 +284,20 @@ impl<'a> Isupport<'a> {
         self.get("INVEX").flatten().and_then(|s| s.chars().next())
     }
 
+    /// Check if the server supports `PROTOCTL NAMESX` (all prefixes in NAMES).
+    ///
+    /// This is the legacy equivalent of the `multi-prefix` capability.
+    pub fn has_namesx(&self) -> bool {
+        self.get("NAMESX").is_some()
+    }
+
+    /// Check if the server supports `PROTOCTL UHNAMES` (hostmasks in NAMES).
+    ///
+    /// This is the legacy equivalent of the `userhost-in-names` capability.
+    pub fn has_uhnames(&self) -> bool {
+        self.get("UHNAMES").is_some()
+    }
+
     /// Parse the `TARGMAX` token into a [`TargMax`] structure.
     pub fn targmax(&self) -> Option<TargMax<'a>> {
         self.get("TARGMAX").flatten().and_then(TargMax::parse)
@@ -150,6 +307,70 @@ impl<'a> Isupport<'a> {
     pub fn maxlist(&self) -> Option<MaxList> {
         self.get("MAXLIST").flatten().and_then(MaxList::parse)
     }
+
+    /// Get the maximum number of `SILENCE` masks (`SILENCE` token).
+    pub fn silence_limit(&self) -> Option<usize> {
+        self.get("SILENCE").flatten().and_then(|v| v.parse().ok())
+    }
+
+    /// Parse the `CHANLIMIT` token into a [`ChanLimit`] structure.
+    pub fn chanlimit(&self) -> Option<ChanLimit> {
+        self.get("CHANLIMIT").flatten().and_then(ChanLimit::parse)
+    }
+}
+
+/// A length limit advertised in ISUPPORT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LimitKind {
+    /// Nickname length (`NICKLEN`).
+    Nick,
+    /// Channel name length (`CHANNELLEN`).
+    Channel,
+    /// Topic length (`TOPICLEN`).
+    Topic,
+    /// Away message length (`AWAYLEN`).
+    Away,
+    /// Kick reason length (`KICKLEN`).
+    Kick,
+    /// Hostname length (`HOSTLEN`).
+    Host,
+    /// Username length (`USERLEN`).
+    User,
+}
+
+impl LimitKind {
+    /// All known limit kinds.
+    pub const ALL: &'static [LimitKind] = &[
+        LimitKind::Nick,
+        LimitKind::Channel,
+        LimitKind::Topic,
+        LimitKind::Away,
+        LimitKind::Kick,
+        LimitKind::Host,
+        LimitKind::User,
+    ];
+
+    /// Get the ISUPPORT token name for this limit.
+    pub fn token(self) -> &'static str {
+        match self {
+            LimitKind::Nick => "NICKLEN",
+            LimitKind::Channel => "CHANNELLEN",
+            LimitKind::Topic => "TOPICLEN",
+            LimitKind::Away => "AWAYLEN",
+            LimitKind::Kick => "KICKLEN",
+            LimitKind::Host => "HOSTLEN",
+            LimitKind::User => "USERLEN",
+        }
+    }
+
+    /// Look up a limit kind by its ISUPPORT token name (case-insensitive).
+    pub fn from_token(token: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.token().eq_ignore_ascii_case(token))
+    }
 }
 
 /// Parse ISUPPORT tokens from a slice of string parameters.
@@ -172,7 +393,10 @@ pub fn parse_params<'a>(params: &[&'a str]) -> Isupport<'a> {
 
         entries.push(IsupportEntry { key: k, value: v });
     }
-    Isupport { entries }
+    Isupport {
+        entries,
+        conflicts: Vec::new(),
+    }
 }
 
 /// Parsed `PREFIX` ISUPPORT token.
@@ -255,6 +479,36 @@ impl<'a> PrefixSpec<'a> {
             .position(|c| c == prefix)
             .and_then(|i| self.modes.chars().nth(i))
     }
+
+    /// Returns the rank of a prefix symbol, where `0` is the highest status.
+    ///
+    /// Ranks follow the order declared in `PREFIX`, so sorting members by
+    /// the rank of their highest prefix yields the conventional nick list
+    /// order. For example, with `PREFIX=(qaohv)~&@%+`:
+    /// - `rank_of_prefix('~')` returns `Some(0)`
+    /// - `rank_of_prefix('+')` returns `Some(4)`
+    /// - `rank_of_prefix('!')` returns `None`
+    #[inline]
+    pub fn rank_of_prefix(&self, prefix: char) -> Option<usize> {
+        self.prefixes.chars().position(|c| c == prefix)
+    }
+
+    /// Split all leading status prefixes off a NAMES entry or WHO flag run.
+    ///
+    /// With the `multi-prefix` capability a member can carry several
+    /// prefixes at once, so every leading prefix symbol is consumed, not
+    /// just the first. Returns the prefixes in the order they appeared and
+    /// the remainder of the token.
+    ///
+    /// For example, with `PREFIX=(qaohv)~&@%+`, `"@%+nick"` splits into
+    /// `(['@', '%', '+'], "nick")`.
+    pub fn split_all_prefixes<'t>(&self, token: &'t str) -> (Vec<char>, &'t str) {
+        let end = token
+            .find(|c| !self.prefixes.contains(c))
+            .unwrap_or(token.len());
+        let (prefixes, rest) = token.split_at(end);
+        (prefixes.chars().collect(), rest)
+    }
 }
 
 /// Parsed `CHANMODES` ISUPPORT token.
@@ -283,6 +537,35 @@ impl<'a> ChanModes<'a> {
         let (a, b, c, d) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
         Some(ChanModes { a, b, c, d })
     }
+
+    /// Whether `mode` takes a parameter when being added (`adding`) or
+    /// removed.
+    ///
+    /// Type A and B modes always take one, type C modes only when added and
+    /// type D modes never. Returns `None` for modes not listed in any
+    /// category, such as the status modes advertised in `PREFIX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::isupport::ChanModes;
+    ///
+    /// let chanmodes = ChanModes::parse("eIbq,k,flj,CFLMPQScgimnprstuz").unwrap();
+    /// assert_eq!(chanmodes.mode_takes_arg('l', true), Some(true));
+    /// assert_eq!(chanmodes.mode_takes_arg('l', false), Some(false));
+    /// assert_eq!(chanmodes.mode_takes_arg('o', true), None);
+    /// ```
+    pub fn mode_takes_arg(&self, mode: char, adding: bool) -> Option<bool> {
+        if self.a.contains(mode) || self.b.contains(mode) {
+            Some(true)
+        } else if self.c.contains(mode) {
+            Some(adding)
+        } else if self.d.contains(mode) {
+            Some(false)
+        } else {
+            None
+        }
+    }
 }
 
 /// Parsed `TARGMAX` ISUPPORT token.
@@ -383,10 +666,96 @@ impl MaxList {
     }
 }
 
+/// Parsed `CHANLIMIT` ISUPPORT token.
+///
+/// Specifies how many channels of each type a client may join. Prefixes
+/// listed together (e.g. `#&:100`) share a single limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChanLimit {
+    /// Prefix, limit, and the index of the group the prefix was listed in.
+    entries: Vec<(char, Option<u32>, usize)>,
+}
+
+impl ChanLimit {
+    /// Parse a `CHANLIMIT` value like `#:50,&:10` or `#&:100`.
+    ///
+    /// A prefix group with an empty limit (e.g. `#:`) is unlimited.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut entries: Vec<(char, Option<u32>, usize)> = Vec::new();
+        for (group, part) in s.split(',').enumerate() {
+            if part.is_empty() {
+                continue;
+            }
+            let (prefixes, limit_str) = part.split_once(':')?;
+
+            let limit = if limit_str.is_empty() {
+                None
+            } else {
+                match limit_str.parse() {
+                    Ok(n) => Some(n),
+                    Err(_) => continue,
+                }
+            };
+            for ch in prefixes.chars() {
+                entries.retain(|(c, _, _)| *c != ch);
+                entries.push((ch, limit, group));
+            }
+        }
+        Some(ChanLimit { entries })
+    }
+
+    /// Get the join limit for a channel type prefix.
+    ///
+    /// Returns `None` if the prefix is not listed or is unlimited.
+    pub fn limit_for(&self, prefix: char) -> Option<u32> {
+        self.entries
+            .iter()
+            .find(|(c, _, _)| *c == prefix)
+            .and_then(|(_, n, _)| *n)
+    }
+
+    /// Get the limit and group index for a limited prefix.
+    ///
+    /// Prefixes in the same group share the limit.
+    pub(crate) fn group_for(&self, prefix: char) -> Option<(usize, u32)> {
+        self.entries
+            .iter()
+            .find(|(c, _, _)| *c == prefix)
+            .and_then(|(_, n, g)| n.map(|n| (*g, n)))
+    }
+
+    /// Iterate over all prefix/limit pairs (`None` meaning unlimited).
+    pub fn iter(&self) -> impl Iterator<Item = (char, Option<u32>)> + '_ {
+        self.entries.iter().map(|(c, n, _)| (*c, *n))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn chanmodes_mode_takes_arg() {
+        let chanmodes = ChanModes::parse("eIbq,k,flj,CFLMPQScgimnprstuz").unwrap();
+
+        // Type A: list modes
+        assert_eq!(chanmodes.mode_takes_arg('b', true), Some(true));
+        assert_eq!(chanmodes.mode_takes_arg('q', false), Some(true));
+        // Type B: key
+        assert_eq!(chanmodes.mode_takes_arg('k', true), Some(true));
+        assert_eq!(chanmodes.mode_takes_arg('k', false), Some(true));
+        // Type C: limit, forward, join throttle
+        assert_eq!(chanmodes.mode_takes_arg('l', true), Some(true));
+        assert_eq!(chanmodes.mode_takes_arg('l', false), Some(false));
+        assert_eq!(chanmodes.mode_takes_arg('j', false), Some(false));
+        // Type D: flags
+        assert_eq!(chanmodes.mode_takes_arg('n', true), Some(false));
+        assert_eq!(chanmodes.mode_takes_arg('z', false), Some(false));
+        // Unknown, including PREFIX modes
+        assert_eq!(chanmodes.mode_takes_arg('o', true), None);
+        assert_eq!(chanmodes.mode_takes_arg('X', true), None);
+    }
+
     #[test]
     fn prefix_spec_is_prefix_mode() {
         let spec = PrefixSpec::parse("(qaohv)~&@%+").unwrap();
@@ -428,6 +797,29 @@ mod tests {
         assert_eq!(spec.mode_for_prefix('!'), None);
     }
 
+    #[test]
+    fn prefix_spec_rank_of_prefix() {
+        let spec = PrefixSpec::parse("(qaohv)~&@%+").unwrap();
+
+        assert_eq!(spec.rank_of_prefix('~'), Some(0));
+        assert_eq!(spec.rank_of_prefix('@'), Some(2));
+        assert_eq!(spec.rank_of_prefix('!'), None);
+        assert!(spec.rank_of_prefix('~') < spec.rank_of_prefix('&'));
+        assert!(spec.rank_of_prefix('@') < spec.rank_of_prefix('%'));
+        assert!(spec.rank_of_prefix('@') < spec.rank_of_prefix('+'));
+
+        // Sorting a NAMES list by highest prefix
+        let mut names = ["+voice", "regular", "~owner", "@op", "%half"];
+        names.sort_by_key(|name| {
+            let (prefixes, _) = spec.split_all_prefixes(name);
+            prefixes
+                .first()
+                .and_then(|&p| spec.rank_of_prefix(p))
+                .unwrap_or(usize::MAX)
+        });
+        assert_eq!(names, ["~owner", "@op", "%half", "+voice", "regular"]);
+    }
+
     #[test]
     fn prefix_spec_standard_ov_only() {
         // Minimal PREFIX=(ov)@+ as seen on many servers
@@ -440,4 +832,234 @@ mod tests {
         assert_eq!(spec.prefix_for_mode('o'), Some('@'));
         assert_eq!(spec.prefix_for_mode('v'), Some('+'));
     }
+
+    #[test]
+    fn prefix_spec_split_all_prefixes() {
+        let spec = PrefixSpec::parse("(qaohv)~&@%+").unwrap();
+
+        assert_eq!(
+            spec.split_all_prefixes("@%+nick"),
+            (vec!['@', '%', '+'], "nick")
+        );
+        assert_eq!(spec.split_all_prefixes("+nick"), (vec!['+'], "nick"));
+        assert_eq!(spec.split_all_prefixes("nick"), (vec![], "nick"));
+        assert_eq!(spec.split_all_prefixes("@%"), (vec!['@', '%'], ""));
+    }
+
+    #[test]
+    fn topiclen_value() {
+        assert_eq!(parse_params(&["TOPICLEN=390"]).topiclen(), Some(390));
+        assert_eq!(parse_params(&["TOPICLEN"]).topiclen(), None);
+        assert_eq!(parse_params(&["NETWORK=Test"]).topiclen(), None);
+    }
+
+    #[test]
+    fn awaylen_value() {
+        assert_eq!(parse_params(&["AWAYLEN=200"]).awaylen(), Some(200));
+        assert_eq!(parse_params(&["NETWORK=Test"]).awaylen(), None);
+    }
+
+    #[test]
+    fn kicklen_value() {
+        assert_eq!(parse_params(&["KICKLEN=255"]).kicklen(), Some(255));
+        assert_eq!(parse_params(&["NETWORK=Test"]).kicklen(), None);
+    }
+
+    #[test]
+    fn merge_two_lines() {
+        let mut isupport = Isupport::from_response_args(&[
+            "me",
+            "CHANMODES=b,k,l,imnpst",
+            "PREFIX=(ov)@+",
+            "NICKLEN=9",
+            "NETWORK=TestNet",
+            "are supported by this server",
+        ])
+        .unwrap();
+        isupport.parse_and_merge(&[
+            "me",
+            "CHANMODES=beI,k,l,imnpstCT",
+            "PREFIX=(qaohv)~&@%+",
+            "NICKLEN=30",
+            "TOPICLEN=390",
+            "are supported by this server",
+        ]);
+
+        let chanmodes = isupport.chanmodes().unwrap();
+        assert_eq!(chanmodes.a, "beI");
+        assert_eq!(chanmodes.d, "imnpstCT");
+        assert_eq!(isupport.prefix().unwrap().mode_for_prefix('~'), Some('q'));
+        assert_eq!(isupport.nicklen(), Some(30));
+        assert_eq!(isupport.topiclen(), Some(390));
+        assert_eq!(isupport.network(), Some("TestNet"));
+
+        // Replaced tokens are not kept around
+        assert_eq!(isupport.iter().filter(|e| e.key == "NICKLEN").count(), 1);
+        assert_eq!(isupport.iter().count(), 5);
+    }
+
+    #[test]
+    fn merge_records_conflicts() {
+        let mut isupport = Isupport::from_response_args(&[
+            "me",
+            "CHANMODES=b,k,l,imnpst",
+            "NETWORK=TestNet",
+            "are supported by this server",
+        ])
+        .unwrap();
+        assert!(isupport.conflicts().is_empty());
+
+        isupport.parse_and_merge(&[
+            "me",
+            "CHANMODES=beI,k,l,imnpst",
+            "NETWORK=TestNet",
+            "-EXCEPTS",
+            "are supported by this server",
+        ]);
+
+        // Last definition wins
+        assert_eq!(isupport.chanmodes().unwrap().a, "beI");
+        // Re-advertising the same value or negating is not a conflict
+        assert_eq!(
+            isupport.conflicts(),
+            [IsupportConflict {
+                key: "CHANMODES",
+                previous: Some("b,k,l,imnpst"),
+                value: Some("beI,k,l,imnpst"),
+            }]
+        );
+    }
+
+    #[test]
+    fn merge_negated_token_removes() {
+        let mut isupport = parse_params(&["NICKLEN=30", "EXCEPTS"]);
+        isupport.merge(parse_params(&["-NICKLEN", "-EXCEPTS", "-UNKNOWN"]));
+
+        assert_eq!(isupport.nicklen(), None);
+        assert_eq!(isupport.get("NICKLEN"), None);
+        assert_eq!(isupport.get("EXCEPTS"), None);
+        assert_eq!(isupport.get("-NICKLEN"), None);
+        assert_eq!(isupport.iter().count(), 0);
+
+        // A later line can advertise the token again
+        isupport.merge(parse_params(&["NICKLEN=16"]));
+        assert_eq!(isupport.nicklen(), Some(16));
+    }
+
+    #[test]
+    fn casemapping_value() {
+        let isupport = parse_params(&["CASEMAPPING=ascii"]);
+        assert_eq!(isupport.casemapping(), CaseMapping::Ascii);
+        assert_eq!(isupport.casemapping_name(), Some("ascii"));
+        assert!(!isupport.casemapping().eq("Nick[]", "nick{}"));
+
+        let isupport = parse_params(&["CASEMAPPING=strict-rfc1459"]);
+        assert_eq!(isupport.casemapping(), CaseMapping::StrictRfc1459);
+        assert!(isupport.casemapping().eq("Nick[]", "nick{}"));
+
+        assert_eq!(
+            parse_params(&["NETWORK=Test"]).casemapping(),
+            CaseMapping::Rfc1459
+        );
+        assert_eq!(
+            parse_params(&["CASEMAPPING=rfc7613"]).casemapping(),
+            CaseMapping::Rfc1459
+        );
+    }
+
+    #[test]
+    fn length_limits() {
+        let isupport = parse_params(&[
+            "NICKLEN=30",
+            "CHANNELLEN=64",
+            "TOPICLEN=390",
+            "HOSTLEN=63",
+            "USERLEN",
+            "NETWORK=Test",
+        ]);
+        assert_eq!(isupport.length_limit(LimitKind::Nick), Some(30));
+        assert_eq!(isupport.length_limit(LimitKind::Channel), Some(64));
+        assert_eq!(isupport.length_limit(LimitKind::Host), Some(63));
+        assert_eq!(isupport.length_limit(LimitKind::User), None);
+        assert_eq!(isupport.length_limit(LimitKind::Kick), None);
+
+        assert_eq!(isupport.nicklen(), Some(30));
+        assert_eq!(isupport.channellen(), Some(64));
+        assert_eq!(isupport.topiclen(), Some(390));
+        assert_eq!(isupport.hostlen(), Some(63));
+        assert_eq!(isupport.userlen(), None);
+
+        let limits: Vec<_> = isupport.length_limits().collect();
+        assert_eq!(
+            limits,
+            vec![
+                (LimitKind::Nick, 30),
+                (LimitKind::Channel, 64),
+                (LimitKind::Topic, 390),
+                (LimitKind::Host, 63),
+            ]
+        );
+    }
+
+    #[test]
+    fn limit_kind_tokens() {
+        for &kind in LimitKind::ALL {
+            assert_eq!(LimitKind::from_token(kind.token()), Some(kind));
+        }
+        assert_eq!(LimitKind::from_token("nicklen"), Some(LimitKind::Nick));
+        assert_eq!(LimitKind::from_token("MODES"), None);
+    }
+
+    #[test]
+    fn utf8only_flag() {
+        assert!(parse_params(&["UTF8ONLY", "NETWORK=Test"]).utf8only());
+        assert!(!parse_params(&["NETWORK=Test"]).utf8only());
+    }
+
+    #[test]
+    fn safe_channels_from_chantypes() {
+        assert!(parse_params(&["CHANTYPES=#!"]).has_safe_channels());
+        assert!(!parse_params(&["CHANTYPES=#&"]).has_safe_channels());
+        assert!(!parse_params(&["NETWORK=Test"]).has_safe_channels());
+    }
+
+    #[test]
+    fn chanlimit_grouped() {
+        let isupport = parse_params(&["CHANLIMIT=#:50,&:10", "NETWORK=Test"]);
+        let limits = isupport.chanlimit().unwrap();
+        assert_eq!(limits.limit_for('#'), Some(50));
+        assert_eq!(limits.limit_for('&'), Some(10));
+        assert_eq!(limits.limit_for('+'), None);
+
+        let limits = ChanLimit::parse("#&:100,+:").unwrap();
+        assert_eq!(limits.limit_for('#'), Some(100));
+        assert_eq!(limits.limit_for('&'), Some(100));
+        assert_eq!(limits.limit_for('+'), None);
+        assert_eq!(limits.iter().count(), 3);
+        // '#' and '&' share one limit; '+' is unlimited
+        assert_eq!(limits.group_for('#'), limits.group_for('&'));
+        assert_eq!(limits.group_for('+'), None);
+
+        assert!(parse_params(&["NETWORK=Test"]).chanlimit().is_none());
+    }
+
+    #[test]
+    fn silence_limit() {
+        let isupport = parse_params(&["SILENCE=15", "NETWORK=Test"]);
+        assert_eq!(isupport.silence_limit(), Some(15));
+
+        assert_eq!(parse_params(&["SILENCE"]).silence_limit(), None);
+        assert_eq!(parse_params(&["NETWORK=Test"]).silence_limit(), None);
+    }
+
+    #[test]
+    fn namesx_uhnames() {
+        let isupport = parse_params(&["NAMESX", "UHNAMES", "NETWORK=Test"]);
+        assert!(isupport.has_namesx());
+        assert!(isupport.has_uhnames());
+
+        let isupport = parse_params(&["NETWORK=Test"]);
+        assert!(!isupport.has_namesx());
+        assert!(!isupport.has_uhnames());
+    }
 }
@@ -9,6 +9,7 @@
 //! - IRCv3 capability negotiation and message tags
 //! - Zero-copy parsing with borrowed message types
 //! - Optional Tokio integration for async networking
+//! - High-level async client (`client::IrcClient`) over transport and handshake
 //! - User and channel mode parsing
 //! - ISUPPORT (RPL_ISUPPORT) parsing
 //! - Convenient message construction with builder pattern
@@ -60,6 +61,8 @@
 
 pub mod caps;
 pub mod chan;
+#[cfg(feature = "tokio")]
+pub mod client;
 pub mod colors;
 pub mod command;
 pub mod compliance;
@@ -79,6 +82,7 @@ pub mod nick;
 pub mod prefix;
 pub mod response;
 pub mod sasl;
+pub mod server;
 pub mod state;
 pub mod util;
 
@@ -87,17 +91,18 @@ pub use self::chan::ChannelExt;
 pub use self::colors::FormattedStringExt;
 pub use self::command::{
     BatchSubCommand, CapSubCommand, ChatHistorySubCommand, Command, MessageReference,
+    MonitorSubCommand, ReadMarker, SilenceChange,
 };
 pub use self::compliance::{check_compliance, ComplianceConfig, ComplianceError};
 pub use self::ctcp::{Ctcp, CtcpKind, CtcpOwned};
-pub use self::encode::IrcEncode;
+pub use self::encode::{IrcEncode, LineEnding, TrailingPolicy};
 pub use self::nick::{NickExt, DEFAULT_NICK_MAX_LEN};
 
-pub use self::command::CommandRef;
+pub use self::command::{CommandRef, CommandRefEnum};
 #[cfg(feature = "tokio")]
 pub use self::irc::IrcCodec;
 pub use self::isupport::{
-    ChanModes, Isupport, IsupportBuilder, IsupportEntry, MaxList, PrefixSpec, TargMax,
+    ChanLimit, ChanModes, Isupport, IsupportBuilder, IsupportEntry, MaxList, PrefixSpec, TargMax,
 };
 pub use self::message::MessageRef;
 pub use self::message::{Message, Tag};
@@ -106,9 +111,9 @@ pub use self::prefix::Prefix;
 pub use self::prefix::PrefixRef;
 pub use self::response::Response;
 pub use self::sasl::{
-    choose_mechanism, chunk_response, decode_base64, encode_external, encode_plain,
-    encode_plain_with_authzid, needs_chunking, parse_mechanisms, SaslMechanism, SaslState,
-    ScramClient, ScramError, ScramState, SASL_CHUNK_SIZE,
+    authenticate_messages, choose_mechanism, chunk_response, decode_base64, encode_external,
+    encode_plain, encode_plain_with_authzid, needs_chunking, parse_mechanisms, SaslMechanism,
+    SaslState, ScramClient, ScramError, ScramState, SASL_CHUNK_SIZE,
 };
 pub use self::state::{
     ConnectionState, HandshakeAction, HandshakeConfig, HandshakeError, HandshakeMachine,
@@ -116,7 +121,9 @@ pub use self::state::{
 };
 
 pub mod casemap;
-pub use self::casemap::{irc_eq, irc_lower_char, irc_to_lower};
+pub use self::casemap::{
+    irc_eq, irc_eq_with, irc_lower_char, irc_to_lower, irc_to_lower_with, CaseMapping,
+};
 
 pub use self::util::{matches_hostmask, wildcard_match};
 
@@ -125,7 +132,7 @@ pub use self::ircv3::{
     format_server_time, format_timestamp, generate_batch_ref, generate_msgid, parse_server_time,
 };
 pub mod scanner;
-pub use scanner::{detect_protocol, is_non_irc_protocol, DetectedProtocol};
+pub use scanner::{detect_protocol, is_non_irc_protocol, Confidence, DetectedProtocol};
 
 #[cfg(feature = "tokio")]
 pub mod transport;
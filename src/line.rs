@@ -17,7 +17,9 @@ use crate::error;
 
 /// Line-based codec that handles newline-terminated messages.
 ///
-/// By default, lines are limited to 512 bytes (IRC standard).
+/// By default, lines are limited to 512 bytes (IRC standard), and lines that
+/// are not valid UTF-8 are decoded as Latin-1 rather than rejected; see
+/// [`set_utf8_only`](Self::set_utf8_only).
 pub struct LineCodec {
     #[cfg(feature = "encoding")]
     encoding: &'static Encoding,
@@ -25,6 +27,8 @@ pub struct LineCodec {
     next_index: usize,
     /// Maximum line length
     max_len: usize,
+    /// Reject lines that are not valid UTF-8 instead of falling back
+    utf8_only: bool,
 }
 
 impl LineCodec {
@@ -46,6 +50,7 @@ impl LineCodec {
             },
             next_index: 0,
             max_len: 512,
+            utf8_only: false,
         })
     }
 
@@ -56,15 +61,30 @@ impl LineCodec {
         Ok(codec)
     }
 
+    /// Require every incoming line to be valid UTF-8.
+    ///
+    /// Enable this when the server advertises `UTF8ONLY`
+    /// (see [`Isupport::utf8only`](crate::isupport::Isupport::utf8only)).
+    /// Invalid lines then fail with [`ProtocolError::Decode`](error::ProtocolError::Decode)
+    /// instead of being decoded with the fallback encoding.
+    pub fn set_utf8_only(&mut self, utf8_only: bool) {
+        self.utf8_only = utf8_only;
+    }
+
+    /// Check whether incoming lines must be valid UTF-8.
+    pub fn utf8_only(&self) -> bool {
+        self.utf8_only
+    }
+
     /// Validate that a string contains no illegal control characters.
+    ///
+    /// A CR that is not immediately followed by the terminating LF is
+    /// rejected as [`IllegalControlChar('\r')`](error::ProtocolError::IllegalControlChar).
     fn validate_line(s: &str) -> error::Result<()> {
-        let trimmed = s.trim_end_matches(&['\r', '\n'][..]);
-        for ch in trimmed.chars() {
-            if crate::format::is_illegal_control_char(ch) {
-                return Err(error::ProtocolError::IllegalControlChar(ch));
-            }
+        match crate::format::find_illegal_line_char(s) {
+            Some(ch) => Err(error::ProtocolError::IllegalControlChar(ch)),
+            None => Ok(()),
         }
-        Ok(())
     }
 }
 
@@ -89,13 +109,21 @@ impl Decoder for LineCodec {
 
             // Decode bytes to string
             #[cfg(feature = "encoding")]
-            let data = {
+            let data = if self.utf8_only {
+                String::from_utf8(line.to_vec())?
+            } else {
                 let (cow, _enc, _had_errors) = self.encoding.decode(line.as_ref());
                 cow.into_owned()
             };
 
+            // Without a configured encoding, fall back to Latin-1, which
+            // maps every byte to the code point of the same value.
             #[cfg(not(feature = "encoding"))]
-            let data = String::from_utf8(line.to_vec())?;
+            let data = match String::from_utf8(line.to_vec()) {
+                Ok(data) => data,
+                Err(e) if self.utf8_only => return Err(e.into()),
+                Err(e) => e.into_bytes().into_iter().map(char::from).collect(),
+            };
 
             // Validate no illegal control characters
             Self::validate_line(&data)?;
@@ -175,6 +203,51 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_decode_bare_cr_rejected() {
+        let mut codec = LineCodec::new("utf-8").unwrap();
+        let mut buf = BytesMut::from("PRIVMSG #c :a\rb\n");
+
+        let result = codec.decode(&mut buf);
+        assert!(matches!(
+            result,
+            Err(error::ProtocolError::IllegalControlChar('\r'))
+        ));
+    }
+
+    #[test]
+    fn test_decode_crlf_accepted() {
+        let mut codec = LineCodec::new("utf-8").unwrap();
+        let mut buf = BytesMut::from("PRIVMSG #c :a\r\n");
+
+        let result = codec.decode(&mut buf).unwrap();
+        assert_eq!(result, Some("PRIVMSG #c :a\r\n".to_string()));
+    }
+
+    #[test]
+    #[cfg(not(feature = "encoding"))]
+    fn test_decode_invalid_utf8_falls_back_to_latin1() {
+        let mut codec = LineCodec::new("utf-8").unwrap();
+        let mut buf = BytesMut::from(&b"PRIVMSG #c :caf\xe9\r\n"[..]);
+
+        let result = codec.decode(&mut buf).unwrap();
+        assert_eq!(result, Some("PRIVMSG #c :caf\u{e9}\r\n".to_string()));
+    }
+
+    #[test]
+    fn test_decode_invalid_utf8_rejected_when_utf8_only() {
+        let mut codec = LineCodec::new("utf-8").unwrap();
+        codec.set_utf8_only(true);
+        assert!(codec.utf8_only());
+        let mut buf = BytesMut::from(&b"PRIVMSG #c :caf\xe9\r\n"[..]);
+
+        let result = codec.decode(&mut buf);
+        assert!(matches!(result, Err(error::ProtocolError::Decode(_))));
+
+        let mut buf = BytesMut::from("PRIVMSG #c :caf\u{e9}\r\n");
+        assert!(codec.decode(&mut buf).unwrap().is_some());
+    }
+
     #[test]
     fn test_encode() {
         let mut codec = LineCodec::new("utf-8").unwrap();
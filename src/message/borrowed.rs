@@ -18,10 +18,11 @@
 
 use std::fmt::{self, Display, Formatter};
 
+use crate::casemap::CaseMapping;
 use crate::command::Command;
 use crate::command::CommandRef;
 use crate::error::MessageParseError;
-use crate::message::{Message, Tag};
+use crate::message::{Message, ParseOptions, Tag};
 use crate::prefix::PrefixRef;
 
 use super::nom_parser::ParsedMessage;
@@ -69,16 +70,40 @@ impl<'a> MessageRef<'a> {
     /// ```
     #[must_use = "parsing result should be handled"]
     pub fn parse(s: &'a str) -> Result<MessageRef<'a>, MessageParseError> {
+        Self::parse_with(s, &ParseOptions::default())
+    }
+
+    /// Parse a message, applying the limits and strictness in `options`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::{MessageRef, ParseOptions};
+    ///
+    /// let raw = ":nick  PRIVMSG #rust :hi";
+    /// assert!(MessageRef::parse_with(raw, &ParseOptions::strict()).is_err());
+    ///
+    /// let msg = MessageRef::parse_with(raw, &ParseOptions::lenient()).unwrap();
+    /// assert_eq!(msg.args(), ["#rust", "hi"]);
+    /// ```
+    #[must_use = "parsing result should be handled"]
+    pub fn parse_with(
+        s: &'a str,
+        options: &ParseOptions,
+    ) -> Result<MessageRef<'a>, MessageParseError> {
         if s.is_empty() {
             return Err(MessageParseError::EmptyMessage);
         }
 
-        let trimmed = s.trim_end_matches(['\r', '\n']);
+        let trimmed = options.check_line(s.trim_end_matches(['\r', '\n']))?;
 
         let parsed = match ParsedMessage::parse(trimmed) {
             Ok(m) => m,
             Err(_e) => return Err(MessageParseError::InvalidCommand),
         };
+        if let Some(tags) = parsed.tags {
+            options.check_tags(tags)?;
+        }
 
         let prefix = parsed.prefix.map(PrefixRef::parse);
         let command = CommandRef::new(parsed.command, parsed.params.clone());
@@ -91,12 +116,74 @@ impl<'a> MessageRef<'a> {
         })
     }
 
+    /// Parse every line of a buffer of CRLF- or LF-separated messages.
+    ///
+    /// Yields one parse result per non-empty line. Unlike the streaming
+    /// transports, the whole buffer must already be in memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let buf = "PING :a\r\n\r\n:nick PRIVMSG #rust :hi\r\n";
+    /// let names: Vec<_> = MessageRef::parse_all(buf)
+    ///     .map(|msg| msg.unwrap().command_name().to_string())
+    ///     .collect();
+    /// assert_eq!(names, ["PING", "PRIVMSG"]);
+    /// ```
+    pub fn parse_all(
+        buf: &'a str,
+    ) -> impl Iterator<Item = Result<MessageRef<'a>, MessageParseError>> + 'a {
+        buf.split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .filter(|line| !line.is_empty())
+            .map(MessageRef::parse)
+    }
+
+    /// Get the exact input this message was parsed from.
+    ///
+    /// The slice is returned unmodified, including any trailing CRLF, so
+    /// it can be forwarded or logged byte-for-byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let line = "@msgid=1 :nick PRIVMSG #rust :hi\r\n";
+    /// assert_eq!(MessageRef::parse(line).unwrap().raw(), line);
+    /// ```
+    #[inline]
+    pub fn raw(&self) -> &'a str {
+        self.raw
+    }
+
     /// Get the command name.
     #[inline]
     pub fn command_name(&self) -> &str {
         self.command.name
     }
 
+    /// Compare the command name ASCII-case-insensitively.
+    ///
+    /// [`command_name`](Self::command_name) preserves the original case
+    /// from the wire; use this to compare without worrying about it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let msg = MessageRef::parse("privmsg #rust :hi").unwrap();
+    /// assert_eq!(msg.command_name(), "privmsg");
+    /// assert!(msg.command_eq("PRIVMSG"));
+    /// ```
+    #[inline]
+    pub fn command_eq(&self, name: &str) -> bool {
+        self.command.name.eq_ignore_ascii_case(name)
+    }
+
     /// Get the command arguments.
     #[inline]
     pub fn args(&self) -> &[&'a str] {
@@ -109,10 +196,19 @@ impl<'a> MessageRef<'a> {
         self.command.args.get(index).copied()
     }
 
+    /// Iterate over the command name followed by every argument.
+    ///
+    /// The command is at position 0 and the trailing argument, if any, comes
+    /// last, giving uniform positional access for generic processors.
+    pub fn words(&self) -> impl Iterator<Item = &'a str> + '_ {
+        std::iter::once(self.command.name).chain(self.command.args.iter().copied())
+    }
+
     /// Get the value of a tag by key.
     ///
     /// Tags are stored as a raw string in the format `key1=value1;key2=value2`.
-    /// This method parses the tags string on-demand to find the requested key.
+    /// This method scans the tags string on-demand without allocating and
+    /// returns at the first tag whose key matches.
     ///
     /// # Example
     ///
@@ -125,30 +221,12 @@ impl<'a> MessageRef<'a> {
     /// assert_eq!(msg.tag_value("missing"), None);
     /// ```
     pub fn tag_value(&self, key: &str) -> Option<&'a str> {
-        let tags = self.tags?;
-        for tag in tags.split(';') {
-            if let Some((k, v)) = tag.split_once('=') {
-                if k == key {
-                    return Some(v);
-                }
-            } else if tag == key {
-                // Tag with no value
-                return Some("");
-            }
-        }
-        None
+        find_tag(self.tags?, key)
     }
 
     /// Check if a tag exists (regardless of value).
     pub fn has_tag(&self, key: &str) -> bool {
-        let Some(tags) = self.tags else { return false };
-        for tag in tags.split(';') {
-            let k = tag.split_once('=').map(|(k, _)| k).unwrap_or(tag);
-            if k == key {
-                return true;
-            }
-        }
-        false
+        self.tags.is_some_and(|tags| find_tag(tags, key).is_some())
     }
 
     /// Iterate over all tags as (key, value) pairs.
@@ -179,12 +257,73 @@ impl<'a> MessageRef<'a> {
         self.tag_value("label")
     }
 
+    /// Get the batch reference tag value, if this message is inside a batch.
+    #[inline]
+    pub fn batch_ref(&self) -> Option<&'a str> {
+        self.tag_value("batch")
+    }
+
     /// Get the account tag value, if present.
     #[inline]
     pub fn account_tag(&self) -> Option<&'a str> {
         self.tag_value("account")
     }
 
+    /// Get the value of a client-only tag, with or without its `+` prefix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let msg = MessageRef::parse("@+typing=active TAGMSG #rust").unwrap();
+    /// assert_eq!(msg.client_tag_value("typing"), Some("active"));
+    /// assert_eq!(msg.client_tag_value("+typing"), Some("active"));
+    /// ```
+    pub fn client_tag_value(&self, name: &str) -> Option<&'a str> {
+        self.tags_iter()
+            .find(|(k, _)| super::tags::client_tag_matches(k, name))
+            .map(|(_, v)| v)
+    }
+
+    /// Get the typing notification state (`active`, `paused` or `done`).
+    ///
+    /// Accepts `+typing` and `typing`, falling back to `+draft/typing`.
+    #[inline]
+    pub fn typing(&self) -> Option<&'a str> {
+        self.client_tag_value("typing")
+            .or_else(|| self.client_tag_value("draft/typing"))
+    }
+
+    /// Get the reaction tag value.
+    ///
+    /// Accepts `+draft/react` and `+react`, with or without the `+` prefix.
+    #[inline]
+    pub fn react(&self) -> Option<&'a str> {
+        self.client_tag_value("draft/react")
+            .or_else(|| self.client_tag_value("react"))
+    }
+
+    /// Get the value of a client-only tag in its ratified or `draft/` form.
+    pub fn draft_tag_value(&self, name: &str) -> Option<&'a str> {
+        self.tags_iter()
+            .find(|(k, _)| super::tags::draft_tag_matches(k, name))
+            .map(|(_, v)| v)
+    }
+
+    /// Get the msgid this message edits (`+draft/edit`), if any.
+    #[inline]
+    pub fn edits(&self) -> Option<&'a str> {
+        self.draft_tag_value("edit")
+    }
+
+    /// Get the channel a private message relates to
+    /// (`+draft/channel-context`), if any.
+    #[inline]
+    pub fn channel_context(&self) -> Option<&'a str> {
+        self.draft_tag_value("channel-context")
+    }
+
     /// Get the source nickname from the prefix, if present.
     ///
     /// Returns `None` if there's no prefix or if the prefix is a server name.
@@ -284,13 +423,181 @@ impl<'a> MessageRef<'a> {
     /// Check if this is a PRIVMSG command.
     #[inline]
     pub fn is_privmsg(&self) -> bool {
-        self.command.name.eq_ignore_ascii_case("PRIVMSG")
+        self.command_eq("PRIVMSG")
     }
 
     /// Check if this is a NOTICE command.
     #[inline]
     pub fn is_notice(&self) -> bool {
-        self.command.name.eq_ignore_ascii_case("NOTICE")
+        self.command_eq("NOTICE")
+    }
+
+    /// Check if this is a PING command.
+    #[inline]
+    pub fn is_ping(&self) -> bool {
+        self.command_eq("PING")
+    }
+
+    /// Check if this is a PONG command.
+    #[inline]
+    pub fn is_pong(&self) -> bool {
+        self.command_eq("PONG")
+    }
+
+    /// Get the token of a PING, to be echoed back in the PONG.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let msg = MessageRef::parse("PING :irc.example.com").unwrap();
+    /// assert_eq!(msg.ping_token(), Some("irc.example.com"));
+    /// ```
+    #[inline]
+    pub fn ping_token(&self) -> Option<&'a str> {
+        if self.is_ping() {
+            self.arg(0)
+        } else {
+            None
+        }
+    }
+
+    /// Check if this is a TAGMSG command (tags only, no text).
+    #[inline]
+    pub fn is_tagmsg(&self) -> bool {
+        self.command_eq("TAGMSG")
+    }
+
+    /// Get the comma-separated targets of an addressable command.
+    ///
+    /// Returns `None` unless this is a PRIVMSG, NOTICE or TAGMSG with a
+    /// target parameter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let msg = MessageRef::parse("PRIVMSG #a,#b,nick :hi").unwrap();
+    /// let targets: Vec<_> = msg.targets().unwrap().collect();
+    /// assert_eq!(targets, ["#a", "#b", "nick"]);
+    /// ```
+    pub fn targets(&self) -> Option<impl Iterator<Item = &'a str>> {
+        if !(self.is_privmsg() || self.is_notice() || self.is_tagmsg()) {
+            return None;
+        }
+        self.arg(0)
+            .map(|targets| targets.split(',').filter(|t| !t.is_empty()))
+    }
+
+    /// Check if this PRIVMSG or NOTICE is addressed to `my_nick` directly.
+    ///
+    /// Returns `true` when one of the targets equals `my_nick` under `case`,
+    /// i.e. a private message rather than a channel message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::casemap::CaseMapping;
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let msg = MessageRef::parse(":bob!b@h PRIVMSG Alice :hi").unwrap();
+    /// assert!(msg.is_direct_message("alice", CaseMapping::Rfc1459));
+    /// ```
+    pub fn is_direct_message(&self, my_nick: &str, case: CaseMapping) -> bool {
+        if !(self.is_privmsg() || self.is_notice()) {
+            return false;
+        }
+        self.targets()
+            .is_some_and(|mut targets| targets.any(|t| case.eq(t, my_nick)))
+    }
+
+    /// Check if the text of this PRIVMSG or NOTICE mentions `my_nick`.
+    ///
+    /// The nickname must appear as a whole word: it may not be preceded or
+    /// followed by a character that can appear in a nickname.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::casemap::CaseMapping;
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let msg = MessageRef::parse(":bob!b@h PRIVMSG #rust :ALICE: ping").unwrap();
+    /// assert!(msg.mentions("alice", CaseMapping::Rfc1459));
+    /// ```
+    pub fn mentions(&self, my_nick: &str, case: CaseMapping) -> bool {
+        if my_nick.is_empty() || !(self.is_privmsg() || self.is_notice()) {
+            return false;
+        }
+        let Some(text) = self.arg(1) else {
+            return false;
+        };
+
+        text.char_indices().any(|(start, _)| {
+            let end = start + my_nick.len();
+            text.get(start..end)
+                .is_some_and(|word| case.eq(word, my_nick))
+                && !text[..start].chars().next_back().is_some_and(is_nick_char)
+                && !text[end..].chars().next().is_some_and(is_nick_char)
+        })
+    }
+
+    /// Check if this is a NICK change announced for a user.
+    ///
+    /// A NICK relayed by the server carries the user's old nickname as its
+    /// prefix; a prefixless NICK is the client choosing its nickname during
+    /// registration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// assert!(MessageRef::parse(":old!u@h NICK new").unwrap().is_nick_change());
+    /// assert!(!MessageRef::parse("NICK foo").unwrap().is_nick_change());
+    /// ```
+    pub fn is_nick_change(&self) -> bool {
+        self.command_eq("NICK") && self.source_nickname().is_some() && self.arg(0).is_some()
+    }
+
+    /// Get the nickname a NICK message sets.
+    ///
+    /// Returns `None` for other commands.
+    #[inline]
+    pub fn new_nick(&self) -> Option<&'a str> {
+        if self.command_eq("NICK") {
+            self.arg(0)
+        } else {
+            None
+        }
+    }
+
+    /// Get the reason text of a KICK, PART, QUIT or ERROR message.
+    ///
+    /// Each command carries its reason at a different position; this
+    /// returns `None` for other commands or when no reason was given.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let msg = MessageRef::parse(":op KICK #rust spammer :Go away").unwrap();
+    /// assert_eq!(msg.reason(), Some("Go away"));
+    /// ```
+    pub fn reason(&self) -> Option<&'a str> {
+        let index = if self.command_eq("KICK") {
+            2
+        } else if self.command_eq("PART") {
+            1
+        } else if self.command_eq("QUIT") || self.command_eq("ERROR") {
+            0
+        } else {
+            return None;
+        };
+        self.arg(index)
     }
 
     /// Check if this is a numeric response (3-digit command).
@@ -307,6 +614,57 @@ impl<'a> MessageRef<'a> {
             None
         }
     }
+
+    /// Get the target of a numeric response (its first parameter).
+    ///
+    /// Servers address numerics to the client's current nickname (or `*`
+    /// before registration). Compare this against the tracked nickname to
+    /// spot stale numerics after a nick change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::MessageRef;
+    ///
+    /// let msg = MessageRef::parse(":srv 001 alice :Welcome").unwrap();
+    /// assert_eq!(msg.numeric_target(), Some("alice"));
+    /// ```
+    pub fn numeric_target(&self) -> Option<&'a str> {
+        if self.is_numeric() {
+            self.arg(0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Find the first tag named `key` in a raw tags string.
+///
+/// Tags that don't start with `key` are skipped without looking for `=`,
+/// and the scan stops at the first match.
+#[inline]
+fn find_tag<'a>(tags: &'a str, key: &str) -> Option<&'a str> {
+    tags.split(';').find_map(|tag| {
+        let rest = tag.strip_prefix(key)?;
+        if rest.is_empty() {
+            // Tag with no value
+            Some("")
+        } else {
+            rest.strip_prefix('=')
+        }
+    })
+}
+
+impl From<MessageRef<'_>> for Message {
+    fn from(msg: MessageRef<'_>) -> Self {
+        msg.to_owned()
+    }
+}
+
+impl From<&MessageRef<'_>> for Message {
+    fn from(msg: &MessageRef<'_>) -> Self {
+        msg.to_owned()
+    }
 }
 
 impl Display for MessageRef<'_> {
@@ -349,6 +707,11 @@ impl Display for MessageRef<'_> {
     }
 }
 
+/// Characters that can appear in a nickname, for mention word boundaries.
+fn is_nick_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "[]\\`_^{|}-".contains(c)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -372,6 +735,16 @@ mod tests {
         assert_eq!(msg.args(), &["#channel", "Hello"]);
     }
 
+    #[test]
+    fn test_words() {
+        let msg = MessageRef::parse(":nick!user@host PRIVMSG #c :hello world").unwrap();
+        let words: Vec<_> = msg.words().collect();
+        assert_eq!(words, ["PRIVMSG", "#c", "hello world"]);
+
+        let msg = MessageRef::parse("QUIT").unwrap();
+        assert_eq!(msg.words().collect::<Vec<_>>(), ["QUIT"]);
+    }
+
     #[test]
     fn test_parse_with_tags() {
         let msg = MessageRef::parse("@time=2023-01-01;msgid=abc PING").unwrap();
@@ -382,6 +755,26 @@ mod tests {
         assert!(!msg.has_tag("missing"));
     }
 
+    #[test]
+    fn test_tag_value_first_match() {
+        // The first match wins; later segments are never examined (the
+        // malformed key is only accepted by lenient parsing)
+        let msg = MessageRef::parse_with(
+            "@time=first;time=second;\\broken== PING",
+            &ParseOptions::lenient(),
+        )
+        .unwrap();
+        assert_eq!(msg.tag_value("time"), Some("first"));
+
+        // Keys sharing a prefix must not match each other
+        let msg = MessageRef::parse("@timestamp=1;time=2;t PING").unwrap();
+        assert_eq!(msg.tag_value("time"), Some("2"));
+        assert_eq!(msg.tag_value("t"), Some(""));
+        assert_eq!(msg.tag_value("tim"), None);
+        assert!(msg.has_tag("timestamp"));
+        assert!(!msg.has_tag("times"));
+    }
+
     #[test]
     fn test_tags_iter() {
         let msg = MessageRef::parse("@a=1;b=2;c PING").unwrap();
@@ -389,6 +782,102 @@ mod tests {
         assert_eq!(tags, vec![("a", "1"), ("b", "2"), ("c", "")]);
     }
 
+    #[test]
+    fn test_client_tag_plus_normalized() {
+        // Server echoes client tags with their `+` prefix
+        let msg = MessageRef::parse("@+typing=active;+draft/react=👍 :nick TAGMSG #ch").unwrap();
+        assert_eq!(msg.typing(), Some("active"));
+        assert_eq!(msg.client_tag_value("typing"), Some("active"));
+        assert_eq!(msg.react(), Some("👍"));
+
+        let msg = MessageRef::parse("@typing=paused;react=x TAGMSG #ch").unwrap();
+        assert_eq!(msg.typing(), Some("paused"));
+        assert_eq!(msg.react(), Some("x"));
+
+        let msg = MessageRef::parse("@+draft/typing=done TAGMSG #ch").unwrap();
+        assert_eq!(msg.typing(), Some("done"));
+        assert_eq!(msg.react(), None);
+    }
+
+    #[test]
+    fn test_command_eq() {
+        let msg = MessageRef::parse(":nick privmsg #ch :hi").unwrap();
+        assert_eq!(msg.command_name(), "privmsg");
+        assert!(msg.command_eq("PRIVMSG"));
+        assert!(msg.command_eq("privmsg"));
+        assert!(msg.command_eq("PrivMsg"));
+        assert!(!msg.command_eq("NOTICE"));
+        assert!(!msg.command_eq("PRIVMSGX"));
+        assert!(msg.is_privmsg());
+    }
+
+    #[test]
+    fn test_ping_pong() {
+        let msg = MessageRef::parse("PING :x").unwrap();
+        assert!(msg.is_ping());
+        assert!(!msg.is_pong());
+        assert_eq!(msg.ping_token(), Some("x"));
+
+        let msg = MessageRef::parse(":srv ping token").unwrap();
+        assert!(msg.is_ping());
+        assert_eq!(msg.ping_token(), Some("token"));
+
+        let msg = MessageRef::parse(":srv PONG srv :x").unwrap();
+        assert!(msg.is_pong());
+        assert!(!msg.is_ping());
+        assert_eq!(msg.ping_token(), None);
+
+        let msg = MessageRef::parse(":nick PRIVMSG #ch :PING").unwrap();
+        assert!(!msg.is_ping());
+        assert_eq!(msg.ping_token(), None);
+    }
+
+    #[test]
+    fn test_targets() {
+        let msg = MessageRef::parse(":nick PRIVMSG #a,#b,bob :hello").unwrap();
+        let targets: Vec<_> = msg.targets().unwrap().collect();
+        assert_eq!(targets, vec!["#a", "#b", "bob"]);
+
+        let msg = MessageRef::parse("@+typing=active :nick TAGMSG #rust").unwrap();
+        assert!(msg.is_tagmsg());
+        let targets: Vec<_> = msg.targets().unwrap().collect();
+        assert_eq!(targets, vec!["#rust"]);
+
+        let msg = MessageRef::parse("NOTICE alice :hi").unwrap();
+        assert_eq!(msg.targets().unwrap().count(), 1);
+
+        assert!(MessageRef::parse("JOIN #rust").unwrap().targets().is_none());
+        assert!(MessageRef::parse("TAGMSG").unwrap().targets().is_none());
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let buf = "PING :one\r\n\r\n:nick PRIVMSG #ch :two\nNOTICE me :three";
+        let msgs: Vec<_> = MessageRef::parse_all(buf)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(msgs.len(), 3);
+        assert_eq!(msgs[0].arg(0), Some("one"));
+        assert_eq!(msgs[1].arg(1), Some("two"));
+        assert_eq!(msgs[2].arg(1), Some("three"));
+
+        let results: Vec<_> = MessageRef::parse_all("PING :ok\r\n:bad\r\n").collect();
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn test_raw_is_input() {
+        for line in [
+            "PING :server",
+            "PING :server\r\n",
+            "@a=1;b :nick!u@h PRIVMSG #ch :with  double  spaces\r\n",
+        ] {
+            let msg = MessageRef::parse(line).unwrap();
+            assert_eq!(msg.raw(), line);
+        }
+    }
+
     #[test]
     fn test_to_owned() {
         let msg = MessageRef::parse("@time=2023 :nick!user@host PRIVMSG #ch :Hi").unwrap();
@@ -397,6 +886,25 @@ mod tests {
         assert!(owned.prefix.is_some());
     }
 
+    #[test]
+    fn test_to_owned_roundtrip() {
+        for line in [
+            "@time=2023-01-01T00:00:00.000Z;msgid=abc :nick!user@host PRIVMSG #ch :Hello there\r\n",
+            "@+draft/reply=abc\\sdef :nick!user@host TAGMSG #ch\r\n",
+            ":irc.example.com 001 me :Welcome to the network\r\n",
+            ":irc.example.com 353 me = #ch :@alice +bob carol\r\n",
+            ":nick!user@host JOIN #ch\r\n",
+            "PING irc.example.com\r\n",
+            ":nick!user@host MODE #ch +ov alice bob\r\n",
+        ] {
+            let msg = MessageRef::parse(line).unwrap();
+            let owned: Message = msg.to_owned();
+            assert_eq!(owned.to_string(), line, "roundtrip of {:?}", line);
+            assert_eq!(Message::from(&msg), owned);
+            assert_eq!(Message::from(msg), owned);
+        }
+    }
+
     #[test]
     fn test_is_numeric() {
         let msg = MessageRef::parse(":server 001 nick :Welcome").unwrap();
@@ -408,6 +916,63 @@ mod tests {
         assert_eq!(msg.numeric_code(), None);
     }
 
+    #[test]
+    fn test_numeric_target() {
+        let msg = MessageRef::parse(":srv 001 alice :Welcome").unwrap();
+        assert_eq!(msg.numeric_target(), Some("alice"));
+
+        let msg = MessageRef::parse(":srv 451 * :You have not registered").unwrap();
+        assert_eq!(msg.numeric_target(), Some("*"));
+
+        let msg = MessageRef::parse(":nick PRIVMSG alice :hi").unwrap();
+        assert_eq!(msg.numeric_target(), None);
+    }
+
+    #[test]
+    fn test_nick_change() {
+        let msg = MessageRef::parse(":old NICK new").unwrap();
+        assert!(msg.is_nick_change());
+        assert_eq!(msg.source_nickname(), Some("old"));
+        assert_eq!(msg.new_nick(), Some("new"));
+
+        let msg = MessageRef::parse(":old!user@host nick :new").unwrap();
+        assert!(msg.is_nick_change());
+        assert_eq!(msg.new_nick(), Some("new"));
+
+        let msg = MessageRef::parse("NICK foo").unwrap();
+        assert!(!msg.is_nick_change());
+        assert_eq!(msg.new_nick(), Some("foo"));
+
+        let msg = MessageRef::parse(":irc.example.com NICK foo").unwrap();
+        assert!(!msg.is_nick_change());
+
+        let msg = MessageRef::parse(":old PRIVMSG #rust new").unwrap();
+        assert!(!msg.is_nick_change());
+        assert_eq!(msg.new_nick(), None);
+    }
+
+    #[test]
+    fn test_reason() {
+        let msg = MessageRef::parse(":op!u@h KICK #ch bob :Flooding").unwrap();
+        assert_eq!(msg.reason(), Some("Flooding"));
+        let msg = MessageRef::parse(":op!u@h KICK #ch bob").unwrap();
+        assert_eq!(msg.reason(), None);
+
+        let msg = MessageRef::parse(":bob!u@h PART #ch :See you").unwrap();
+        assert_eq!(msg.reason(), Some("See you"));
+        let msg = MessageRef::parse(":bob!u@h PART #ch").unwrap();
+        assert_eq!(msg.reason(), None);
+
+        let msg = MessageRef::parse(":bob!u@h quit :Quit: bye").unwrap();
+        assert_eq!(msg.reason(), Some("Quit: bye"));
+
+        let msg = MessageRef::parse("ERROR :Closing Link: timeout").unwrap();
+        assert_eq!(msg.reason(), Some("Closing Link: timeout"));
+
+        let msg = MessageRef::parse(":bob PRIVMSG #ch :text").unwrap();
+        assert_eq!(msg.reason(), None);
+    }
+
     #[test]
     fn test_to_raw_owned() {
         let raw = ":nick PRIVMSG #channel :Hello world";
@@ -444,4 +1009,89 @@ mod tests {
         let msg = MessageRef::parse("PRIVMSG #test ::)").unwrap();
         assert_eq!(format!("{msg}"), "PRIVMSG #test ::)\r\n");
     }
+
+    #[test]
+    fn test_is_direct_message() {
+        let case = CaseMapping::Rfc1459;
+        let pm = MessageRef::parse(":bob!b@h PRIVMSG Me[a] :hello").unwrap();
+        assert!(pm.is_direct_message("me{a}", case));
+        assert!(!pm.is_direct_message("me{a}", CaseMapping::Ascii));
+
+        let chan = MessageRef::parse(":bob!b@h PRIVMSG #rust :me: hello").unwrap();
+        assert!(!chan.is_direct_message("me", case));
+
+        let join = MessageRef::parse(":me!m@h JOIN #rust").unwrap();
+        assert!(!join.is_direct_message("me", case));
+    }
+
+    #[test]
+    fn test_mentions() {
+        let case = CaseMapping::Rfc1459;
+        let mention = |raw: &str| MessageRef::parse(raw).unwrap().mentions("alice", case);
+
+        assert!(mention(":bob!b@h PRIVMSG #rust :hey Alice, look"));
+        assert!(mention(":bob!b@h PRIVMSG #rust :alice"));
+        assert!(mention(":bob!b@h NOTICE #rust :(ALICE)"));
+        assert!(!mention(":bob!b@h PRIVMSG #rust :malice aforethought"));
+        assert!(!mention(":bob!b@h PRIVMSG #rust :alice_ is away"));
+        assert!(!mention(":bob!b@h PRIVMSG #rust :née alic"));
+        assert!(!mention(":alice!a@h PART #rust :alice"));
+    }
+
+    #[test]
+    fn test_parse_with_strictness() {
+        let raw = "@a=1;b=2 :nick PRIVMSG #ch :x\0y";
+        let err = MessageRef::parse(raw).unwrap_err();
+        assert!(matches!(
+            err,
+            MessageParseError::InvalidCharacterAt { byte: 0, .. }
+        ));
+
+        let msg = MessageRef::parse_with(raw, &ParseOptions::lenient()).unwrap();
+        assert_eq!(msg.source_nickname(), Some("nick"));
+        assert_eq!(msg.arg(1), Some("x\0y"));
+
+        // Format codes and other control characters are not malformed
+        assert!(MessageRef::parse("PRIVMSG #ch :\x02bold\x02 \x07").is_ok());
+
+        let err = MessageRef::parse("PRIVMSG  #ch :hi").unwrap_err();
+        assert!(matches!(
+            err,
+            MessageParseError::InvalidCharacterAt {
+                byte: b' ',
+                position: 8
+            }
+        ));
+
+        let options = ParseOptions {
+            max_tags: 1,
+            ..ParseOptions::default()
+        };
+        assert!(matches!(
+            MessageRef::parse_with("@a=1;b=2 PING x", &options),
+            Err(MessageParseError::TooManyTags { limit: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_tags_length_limit() {
+        // "@" + tags + " " is exactly 8191 bytes
+        let at_limit = format!("@a={} PING x", "x".repeat(8187));
+        assert!(MessageRef::parse(&at_limit).is_ok());
+
+        let over = format!("@a={} PING x", "x".repeat(8188));
+        assert!(matches!(
+            MessageRef::parse(&over),
+            Err(MessageParseError::TagsTooLong {
+                actual: 8192,
+                limit: 8191
+            })
+        ));
+
+        let options = ParseOptions {
+            max_tags_length: 9000,
+            ..ParseOptions::default()
+        };
+        assert!(MessageRef::parse_with(&over, &options).is_ok());
+    }
 }
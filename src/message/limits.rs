@@ -0,0 +1,291 @@
+//! Line length budgeting for outgoing messages.
+
+use crate::command::Command;
+use crate::util::MAX_MESSAGE_BODY;
+use crate::Message;
+
+/// Compute how many bytes of trailing text fit in a relayed message.
+///
+/// When the server relays `<command> <target> :<text>` to other clients it
+/// prepends the sender's `:nick!user@host ` source, so the text must leave
+/// room for it within the 512-byte line limit. `prefix_len` is the length of
+/// the source without the leading `:` and trailing space (i.e. of
+/// `nick!user@host`).
+///
+/// Returns `0` if nothing fits. Pass the result to
+/// [`split_message`](crate::util::split_message) to break long text into
+/// lines the server will not truncate.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::message::max_payload_for;
+///
+/// let source = "alice!alice@host.example.com";
+/// let budget = max_payload_for(source.len(), "#rust", "PRIVMSG");
+/// assert_eq!(budget, 512 - ":alice!alice@host.example.com PRIVMSG #rust :\r\n".len());
+/// ```
+pub fn max_payload_for(prefix_len: usize, target: &str, command: &str) -> usize {
+    // ":" prefix " " command " " target " :" text "\r\n"
+    let overhead = 1 + prefix_len + 1 + command.len() + 1 + target.len() + 2 + 2;
+    MAX_MESSAGE_BODY.saturating_sub(overhead)
+}
+
+impl Message {
+    /// Split a PRIVMSG or NOTICE into messages that each serialize to at most
+    /// `max_len` bytes, including tags, source and the trailing CRLF.
+    ///
+    /// Tags, prefix and target are copied to every part. The text is broken
+    /// after whitespace where possible, otherwise between characters; a
+    /// character and the combining marks following it are kept together, as
+    /// are color codes (`\x03NN,MM` and `\x04RRGGBB`) and their arguments.
+    /// Concatenating the texts of the parts yields the original text.
+    ///
+    /// A single unit that does not fit on its own is still emitted as its own
+    /// part, so a part may exceed `max_len` only when nothing smaller is
+    /// possible. Other commands are returned unchanged as a single message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::Message;
+    ///
+    /// let msg = Message::privmsg("#rust", "hello there world");
+    /// let parts = msg.split_to_fit(26);
+    /// let lines: Vec<String> = parts.iter().map(|m| m.to_string()).collect();
+    /// assert_eq!(
+    ///     lines,
+    ///     ["PRIVMSG #rust :hello \r\n", "PRIVMSG #rust :there \r\n", "PRIVMSG #rust :world\r\n"]
+    /// );
+    /// ```
+    pub fn split_to_fit(&self, max_len: usize) -> Vec<Message> {
+        let (target, text) = match &self.command {
+            Command::PRIVMSG(target, text) | Command::NOTICE(target, text) => (target, text),
+            _ => return vec![self.clone()],
+        };
+
+        let with_text = |text: &str| {
+            let command = match self.command {
+                Command::PRIVMSG(..) => Command::PRIVMSG(target.clone(), text.to_string()),
+                _ => Command::NOTICE(target.clone(), text.to_string()),
+            };
+            Message {
+                tags: self.tags.clone(),
+                prefix: self.prefix.clone(),
+                command,
+            }
+        };
+
+        // Serialized length of everything except the text itself
+        let overhead = with_text(" ").to_string().len() - 1;
+        let budget = max_len.saturating_sub(overhead);
+        if text.len() <= budget {
+            return vec![self.clone()];
+        }
+
+        split_text(text, budget)
+            .into_iter()
+            .map(with_text)
+            .collect()
+    }
+}
+
+/// Break `text` into chunks of at most `budget` bytes on unit boundaries,
+/// preferring to end a chunk after whitespace.
+fn split_text(text: &str, budget: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    // End of the chunk so far, and of its last whitespace unit
+    let mut end = 0;
+    let mut break_at = None;
+
+    while end < text.len() {
+        let unit_end = end + unit_len(&text[end..]);
+        if unit_end - start > budget && end > start {
+            let split = break_at.unwrap_or(end);
+            chunks.push(&text[start..split]);
+            start = split;
+            break_at = None;
+            continue;
+        }
+        if text[end..unit_end].starts_with(char::is_whitespace) {
+            break_at = Some(unit_end);
+        }
+        end = unit_end;
+    }
+    if start < text.len() {
+        chunks.push(&text[start..]);
+    }
+    chunks
+}
+
+/// Length in bytes of the indivisible unit at the start of `s`.
+fn unit_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    match bytes[0] {
+        // Color: \x03[N[N][,M[M]]]
+        0x03 => {
+            let digits = |from: usize| {
+                bytes[from..]
+                    .iter()
+                    .take(2)
+                    .take_while(|b| b.is_ascii_digit())
+                    .count()
+            };
+            let mut len = 1 + digits(1);
+            if len > 1 && bytes.get(len) == Some(&b',') && digits(len + 1) > 0 {
+                len += 1 + digits(len + 1);
+            }
+            len
+        }
+        // Hex color: \x04[RRGGBB[,RRGGBB]]
+        0x04 => {
+            let hex = |from: usize| {
+                bytes.len() >= from + 6 && bytes[from..from + 6].iter().all(u8::is_ascii_hexdigit)
+            };
+            let mut len = 1;
+            if hex(1) {
+                len += 6;
+                if bytes.get(len) == Some(&b',') && hex(len + 1) {
+                    len += 7;
+                }
+            }
+            len
+        }
+        _ => {
+            let mut chars = s.char_indices().skip(1).peekable();
+            let mut len = s.chars().next().map_or(0, char::len_utf8);
+            let mut joined = false;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(joined || is_extending(c)) {
+                    break;
+                }
+                joined = c == '\u{200D}';
+                len = i + c.len_utf8();
+                chars.next();
+            }
+            len
+        }
+    }
+}
+
+/// Whether `c` attaches to the preceding character.
+fn is_extending(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'   // combining diacritical marks
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}' // variation selectors
+        | '\u{FE20}'..='\u{FE2F}'
+        | '\u{200D}'               // zero width joiner
+        | '\u{1F3FB}'..='\u{1F3FF}' // skin tone modifiers
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Tag;
+    use crate::util::split_message;
+
+    #[test]
+    fn test_realistic_prefix() {
+        // Nick (9) + user (10) + typical cloaked host (63)
+        let source = format!("{}!{}@{}", "n".repeat(9), "u".repeat(10), "h".repeat(63));
+        assert_eq!(source.len(), 84);
+        assert_eq!(max_payload_for(source.len(), "#rust", "PRIVMSG"), 409);
+        assert_eq!(max_payload_for(source.len(), "#rust", "NOTICE"), 410);
+
+        // The relayed line fills the 512-byte budget exactly
+        let text = "x".repeat(409);
+        let mut relayed = Message::privmsg("#rust", text);
+        relayed.prefix = Some(source.as_str().into());
+        assert_eq!(relayed.to_string().len(), 512);
+    }
+
+    #[test]
+    fn test_split_with_budget() {
+        let source = "alice!alice@host.example.com";
+        let budget = max_payload_for(source.len(), "bob", "PRIVMSG");
+        let text = "a".repeat(1000);
+        let chunks: Vec<&str> = split_message(&text, budget).collect();
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| c.len() <= budget));
+    }
+
+    #[test]
+    fn test_nothing_fits() {
+        assert_eq!(max_payload_for(600, "#rust", "PRIVMSG"), 0);
+    }
+
+    fn texts(parts: &[Message]) -> Vec<&str> {
+        parts
+            .iter()
+            .map(|m| match &m.command {
+                Command::PRIVMSG(_, text) | Command::NOTICE(_, text) => text.as_str(),
+                other => panic!("unexpected command: {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_multibyte_with_prefix() {
+        let text = "日本語のテキスト ".repeat(20) + "é́ 👍🏽 👨\u{200D}👩\u{200D}👧";
+        let mut msg: Message = ":alice!a@host.example PRIVMSG #rust :x".parse().unwrap();
+        msg.command = Command::PRIVMSG("#rust".to_string(), text.clone());
+        msg.tags = Some(vec![Tag::new("msgid", Some("abc".to_string()))]);
+
+        let parts = msg.split_to_fit(120);
+        assert!(parts.len() > 1);
+        for part in &parts {
+            assert!(part.to_string().len() <= 120, "{}", part);
+            assert_eq!(part.prefix, msg.prefix);
+            assert_eq!(part.tags, msg.tags);
+            let line = part.to_string();
+            assert!(line.starts_with("@msgid=abc :alice!a@host.example PRIVMSG #rust :"));
+        }
+        assert_eq!(texts(&parts).concat(), text);
+        // Broken after whitespace rather than mid-word
+        assert!(texts(&parts)[..parts.len() - 1]
+            .iter()
+            .all(|t| t.ends_with(' ')));
+    }
+
+    #[test]
+    fn test_split_keeps_clusters_together() {
+        let text = "👨\u{200D}👩\u{200D}👧e\u{301}";
+        let parts = split_text(text, 4);
+        assert_eq!(parts, ["👨\u{200D}👩\u{200D}👧", "e\u{301}"]);
+    }
+
+    #[test]
+    fn test_split_oversized_unit_alone() {
+        let msg = Message::notice("bob", "ab😀cd");
+        let overhead = "NOTICE bob :\r\n".len();
+        let parts = msg.split_to_fit(overhead + 2);
+        assert_eq!(texts(&parts), ["ab", "😀", "cd"]);
+        assert!(matches!(parts[1].command, Command::NOTICE(..)));
+    }
+
+    #[test]
+    fn test_split_keeps_color_codes() {
+        let text = "aaaa\x0304,12red\x03 \x04FF0000hex";
+        let parts = split_text(text, 5);
+        assert_eq!(
+            parts,
+            ["aaaa", "\x0304,12", "red\x03 ", "\x04FF0000", "hex"]
+        );
+    }
+
+    #[test]
+    fn test_split_fits_or_unsplittable() {
+        let msg = Message::privmsg("#rust", "short");
+        let parts = msg.split_to_fit(512);
+        assert_eq!(parts, [msg]);
+
+        let join = Message::join("#rust");
+        let parts = join.split_to_fit(5);
+        assert_eq!(parts, [join]);
+    }
+}
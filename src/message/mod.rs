@@ -1,6 +1,7 @@
 //! IRC message types and parsing.
 
 mod borrowed;
+mod limits;
 mod nom_parser;
 mod parse;
 mod serialize;
@@ -9,4 +10,7 @@ pub mod tags;
 mod types;
 
 pub use self::borrowed::MessageRef;
+pub use self::limits::max_payload_for;
+pub use self::parse::{ParseOptions, Strictness, DEFAULT_MAX_TAGS};
+pub use self::tags::{relay_tags, TagPolicy};
 pub use self::types::{Message, Tag};
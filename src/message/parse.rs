@@ -6,11 +6,181 @@ use std::borrow::Cow;
 use std::str::FromStr;
 
 use crate::error::{MessageParseError, ProtocolError};
+use crate::util::MAX_TAGS_LENGTH;
 
 use super::nom_parser::ParsedMessage;
 use super::tags::unescape_tag_value;
 use super::types::{Message, Tag};
 
+/// Default maximum number of tags accepted on a single message.
+pub const DEFAULT_MAX_TAGS: usize = 100;
+
+/// How tolerant the parser is of malformed input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Reject malformed lines. The default, suited to untrusted peers.
+    ///
+    /// Fails on NUL and on CR or LF inside the line (which could smuggle a
+    /// second command), on leading or repeated spaces outside the trailing
+    /// parameter, and on a missing command. Trailing spaces at the end of
+    /// the line are accepted, as many servers send them.
+    #[default]
+    Strict,
+    /// Accept anything the grammar can make sense of, for logs and
+    /// interoperability with sloppy peers.
+    ///
+    /// NUL and stray CR/LF are passed through, leading whitespace is
+    /// skipped and repeated spaces collapse into one separator.
+    Lenient,
+}
+
+/// Limits and tolerances applied while parsing a message.
+///
+/// [`Message::from_str`] and [`MessageRef::parse`] use
+/// [`ParseOptions::default`] (strict); use [`Message::parse_with_options`]
+/// or [`MessageRef::parse_with`] to customize them.
+///
+/// [`MessageRef::parse`]: super::MessageRef::parse
+/// [`MessageRef::parse_with`]: super::MessageRef::parse_with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Maximum number of tags before parsing fails with
+    /// [`MessageParseError::TooManyTags`].
+    ///
+    /// Bounds the work done on messages stuffed with thousands of tiny
+    /// tags that still fit within the byte limit.
+    pub max_tags: usize,
+    /// Maximum length of the tags section in bytes, from the leading `@` up
+    /// to and including the space that ends it, before parsing fails with
+    /// [`MessageParseError::TagsTooLong`].
+    ///
+    /// Defaults to the IRCv3 limit of 8191 bytes.
+    pub max_tags_length: usize,
+    /// How malformed framing and characters are handled.
+    pub strictness: Strictness,
+}
+
+impl ParseOptions {
+    /// Strict parsing with the default tag limit (same as `default()`).
+    #[must_use]
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    /// Lenient parsing with no tag limits.
+    #[must_use]
+    pub fn lenient() -> Self {
+        Self {
+            max_tags: usize::MAX,
+            max_tags_length: usize::MAX,
+            strictness: Strictness::Lenient,
+        }
+    }
+
+    /// Apply the line-level checks, returning the part to hand to the parser.
+    ///
+    /// `line` must already have its CRLF terminator removed.
+    pub(super) fn check_line<'a>(&self, line: &'a str) -> Result<&'a str, MessageParseError> {
+        if line.is_empty() {
+            return Err(MessageParseError::InvalidCommand);
+        }
+        match self.strictness {
+            Strictness::Lenient => Ok(line.trim_start()),
+            Strictness::Strict => {
+                if let Some((position, ch)) = line
+                    .char_indices()
+                    .find(|&(_, ch)| matches!(ch, '\0' | '\r' | '\n'))
+                {
+                    return Err(MessageParseError::InvalidCharacterAt {
+                        byte: ch as u8,
+                        position,
+                    });
+                }
+                check_spacing(line)?;
+                Ok(line)
+            }
+        }
+    }
+
+    /// Enforce `max_tags_length` and `max_tags` on a raw tags section
+    /// (without the leading `@`), and in strict mode the IRCv3 tag key grammar.
+    pub(super) fn check_tags(&self, tags: &str) -> Result<(), MessageParseError> {
+        // Count the leading '@' and the separating space
+        let length = tags.len() + 2;
+        if length > self.max_tags_length {
+            return Err(MessageParseError::TagsTooLong {
+                actual: length,
+                limit: self.max_tags_length,
+            });
+        }
+        if tags.split(';').filter(|s| !s.is_empty()).count() > self.max_tags {
+            return Err(MessageParseError::TooManyTags {
+                limit: self.max_tags,
+            });
+        }
+        if self.strictness == Strictness::Strict {
+            for tag in tags.split(';').filter(|s| !s.is_empty()) {
+                let key = tag.split('=').next().unwrap_or_default();
+                if !Tag::is_valid_key(key) {
+                    return Err(MessageParseError::InvalidTagKey(key.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_tags: DEFAULT_MAX_TAGS,
+            max_tags_length: MAX_TAGS_LENGTH,
+            strictness: Strictness::Strict,
+        }
+    }
+}
+
+/// Reject empty space-separated fields before the trailing parameter.
+///
+/// Catches leading and repeated spaces; spaces at the end of the line are
+/// ignored and the trailing parameter (after ` :`) may contain anything.
+fn check_spacing(line: &str) -> Result<(), MessageParseError> {
+    let line = line.trim_end_matches(' ');
+    if line.is_empty() {
+        return Err(MessageParseError::InvalidCommand);
+    }
+    let mut position = 0;
+    let mut seen_prefix = false;
+    let mut seen_command = false;
+
+    for field in line.split(' ') {
+        if field.is_empty() {
+            return Err(MessageParseError::InvalidCharacterAt {
+                byte: b' ',
+                position: position.min(line.len() - 1),
+            });
+        }
+        if seen_command {
+            if field.starts_with(':') {
+                return Ok(());
+            }
+        } else if position == 0 && field.starts_with('@') {
+            // Tags
+        } else if !seen_prefix && field.starts_with(':') {
+            seen_prefix = true;
+        } else {
+            seen_command = true;
+        }
+        position += field.len() + 1;
+    }
+
+    if seen_command {
+        Ok(())
+    } else {
+        Err(MessageParseError::InvalidCommand)
+    }
+}
+
 /// Intern common tag keys to avoid allocations.
 ///
 /// IRCv3 messages frequently use the same tag keys. By returning
@@ -44,7 +214,8 @@ fn intern_tag_key(key: &str) -> Cow<'static, str> {
 
 /// Parse a raw tags string into a vector of `Tag` structs.
 ///
-/// The input should be the tags portion without the leading `@`.
+/// The input should be the tags portion without the leading `@`, already
+/// checked against the limits with [`ParseOptions::check_tags`].
 fn parse_tags_string(tags_str: &str) -> Vec<Tag> {
     tags_str
         .split(';')
@@ -69,6 +240,26 @@ impl FromStr for Message {
     type Err = ProtocolError;
 
     fn from_str(s: &str) -> Result<Message, Self::Err> {
+        Message::parse_with_options(s, &ParseOptions::default())
+    }
+}
+
+impl Message {
+    /// Parse a message, applying the limits in `options`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::{Message, ParseOptions};
+    ///
+    /// let options = ParseOptions {
+    ///     max_tags: 1,
+    ///     ..ParseOptions::default()
+    /// };
+    /// assert!(Message::parse_with_options("@a=1 PING x", &options).is_ok());
+    /// assert!(Message::parse_with_options("@a=1;b=2 PING x", &options).is_err());
+    /// ```
+    pub fn parse_with_options(s: &str, options: &ParseOptions) -> Result<Message, ProtocolError> {
         if s.is_empty() {
             return Err(ProtocolError::InvalidMessage {
                 string: s.to_owned(),
@@ -76,8 +267,16 @@ impl FromStr for Message {
             });
         }
 
+        let invalid = |cause| ProtocolError::InvalidMessage {
+            string: s.to_owned(),
+            cause,
+        };
+        let line = options
+            .check_line(s.trim_end_matches(['\r', '\n']))
+            .map_err(invalid)?;
+
         // Use the nom parser
-        let parsed = ParsedMessage::parse(s).map_err(|parse_err| {
+        let parsed = ParsedMessage::parse(line).map_err(|parse_err| {
             // Convert detailed parse error to appropriate message parse error
             let cause = MessageParseError::ParseContext {
                 position: parse_err.position,
@@ -93,7 +292,17 @@ impl FromStr for Message {
         })?;
 
         // Convert parsed tags to owned Tag structs
-        let tags = parsed.tags.map(parse_tags_string);
+        let tags = parsed
+            .tags
+            .map(|tags| {
+                options.check_tags(tags)?;
+                Ok(parse_tags_string(tags))
+            })
+            .transpose()
+            .map_err(|cause| ProtocolError::InvalidMessage {
+                string: s.to_owned(),
+                cause,
+            })?;
 
         // Build the owned Message
         Message::with_tags(tags, parsed.prefix, parsed.command, parsed.params.to_vec()).map_err(|cause| {
@@ -177,6 +386,134 @@ mod tests {
         assert!(matches!(msg.command, Command::ChannelMODE(_, _)));
     }
 
+    #[test]
+    fn test_max_tags() {
+        let tags = |n: usize| {
+            (0..n)
+                .map(|i| format!("t{}=v", i))
+                .collect::<Vec<_>>()
+                .join(";")
+        };
+
+        let msg: Message = format!("@{} PING :x", tags(50)).parse().unwrap();
+        assert_eq!(msg.tags.unwrap().len(), 50);
+
+        let err = format!("@{} PING :x", tags(200))
+            .parse::<Message>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ProtocolError::InvalidMessage {
+                cause: MessageParseError::TooManyTags { limit: 100 },
+                ..
+            }
+        ));
+
+        let options = ParseOptions {
+            max_tags: 500,
+            ..ParseOptions::default()
+        };
+        let msg = Message::parse_with_options(&format!("@{} PING :x", tags(200)), &options);
+        assert_eq!(msg.unwrap().tags.unwrap().len(), 200);
+    }
+
+    #[test]
+    fn test_tags_length_limit() {
+        // "@" + tags + " " is exactly 8191 bytes
+        let tags = format!("a={}", "x".repeat(8187));
+        let at_limit = format!("@{} PING :x", tags);
+        assert!(at_limit.parse::<Message>().is_ok());
+
+        let over = format!("@{}y PING :x", tags);
+        let err = over.parse::<Message>().unwrap_err();
+        assert!(matches!(
+            err,
+            ProtocolError::InvalidMessage {
+                cause: MessageParseError::TagsTooLong {
+                    actual: 8192,
+                    limit: 8191
+                },
+                ..
+            }
+        ));
+
+        let options = ParseOptions {
+            max_tags_length: 16,
+            ..ParseOptions::default()
+        };
+        assert!(Message::parse_with_options("@a=1234567890ab PING x", &options).is_ok());
+        assert!(Message::parse_with_options("@a=1234567890abc PING x", &options).is_err());
+        assert!(Message::parse_with_options(&over, &ParseOptions::lenient()).is_ok());
+    }
+
+    #[test]
+    fn test_strictness_levels() {
+        let malformed = [
+            ":nick  PRIVMSG #ch :hello",
+            "  PING :x",
+            "PING  x",
+            "PRIVMSG #ch :a\x00b",
+            "PRIVMSG #ch :a\rb",
+        ];
+        for raw in malformed {
+            assert!(raw.parse::<Message>().is_err(), "strict accepted {:?}", raw);
+            assert!(
+                Message::parse_with_options(raw, &ParseOptions::lenient()).is_ok(),
+                "lenient rejected {:?}",
+                raw
+            );
+        }
+
+        let msg =
+            Message::parse_with_options(":nick  PRIVMSG  #ch  :hello", &ParseOptions::lenient())
+                .unwrap();
+        assert_eq!(
+            msg.command,
+            Command::PRIVMSG("#ch".to_string(), "hello".to_string())
+        );
+
+        // Spaces inside the trailing parameter are fine in strict mode
+        let msg: Message = "PRIVMSG #ch :a  b ".parse().unwrap();
+        assert_eq!(
+            msg.command,
+            Command::PRIVMSG("#ch".to_string(), "a  b ".to_string())
+        );
+
+        // Neither level accepts a line without a command
+        assert!(":nick".parse::<Message>().is_err());
+        assert!(Message::parse_with_options(":nick ", &ParseOptions::lenient()).is_err());
+    }
+
+    #[test]
+    fn test_trailing_spaces_accepted() {
+        let msg: Message = "PING x \r\n".parse().unwrap();
+        assert_eq!(msg.command, Command::PING("x".to_string(), None));
+
+        let msg: Message = ":srv MODE #c +n ".parse().unwrap();
+        assert!(
+            matches!(msg.command, Command::ChannelMODE(ref c, ref m) if c == "#c" && m.len() == 1)
+        );
+    }
+
+    #[test]
+    fn test_empty_lines_are_errors() {
+        for raw in ["", "\r\n", "\n", "   ", " \r\n"] {
+            for options in [ParseOptions::strict(), ParseOptions::lenient()] {
+                assert!(
+                    Message::parse_with_options(raw, &options).is_err(),
+                    "accepted {:?}",
+                    raw
+                );
+                assert!(
+                    crate::message::MessageRef::parse_with(raw, &options).is_err(),
+                    "accepted {:?}",
+                    raw
+                );
+            }
+        }
+        assert!("\n".parse::<Message>().is_err());
+    }
+
     #[test]
     fn test_intern_common_tags() {
         // Verify that common tags are interned (borrowed, not owned)
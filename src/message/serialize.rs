@@ -1,7 +1,7 @@
 use std::fmt::{self, Display, Formatter};
 
 use super::tags::escape_tag_value;
-use super::types::Message;
+use super::types::{Message, Tag};
 
 impl Display for Message {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -31,3 +31,77 @@ impl Display for Message {
         write!(f, "{}\r\n", self.command)
     }
 }
+
+impl Message {
+    /// Serialize this message to a deterministic wire form.
+    ///
+    /// Logically equal messages produce identical bytes, which makes the
+    /// result suitable for signing, deduplication and comparing messages
+    /// relayed by different servers:
+    ///
+    /// - tags are sorted by key, and only the last of duplicate keys is kept;
+    /// - empty tag values are dropped, as `key=` and `key` are equivalent;
+    /// - an empty tag list is omitted;
+    /// - the command is written in its normal serialized form, so tag values
+    ///   are escaped consistently and runs of mode signs are collapsed.
+    ///
+    /// The result ends with `\r\n`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::Message;
+    ///
+    /// let a: Message = "@b=2;a= MODE #rust +o+v alice bob".parse().unwrap();
+    /// let b: Message = "@a;b=2 MODE #rust +ov alice bob".parse().unwrap();
+    /// assert_eq!(a.canonical_bytes(), b.canonical_bytes());
+    /// assert_eq!(a.canonical_bytes(), b"@a;b=2 MODE #rust +ov alice bob\r\n");
+    /// ```
+    #[must_use]
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut canonical = self.clone();
+        canonical.tags = self.tags.as_ref().and_then(|tags| {
+            let mut sorted: Vec<Tag> = Vec::with_capacity(tags.len());
+            for tag in tags.iter().rev() {
+                if !sorted.iter().any(|t| t.0 == tag.0) {
+                    let value = tag.1.clone().filter(|v| !v.is_empty());
+                    sorted.push(Tag(tag.0.clone(), value));
+                }
+            }
+            sorted.sort_by(|a, b| a.0.cmp(&b.0));
+            (!sorted.is_empty()).then_some(sorted)
+        });
+        canonical.to_string().into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_tag_order() {
+        let a: Message = "@time=2024-01-01T00:00:00.000Z;msgid=abc;+draft/reply=x\\sy :alice!a@host PRIVMSG #rust :hi"
+            .parse()
+            .unwrap();
+        let b: Message = "@+draft/reply=x\\sy;msgid=abc;time=2024-01-01T00:00:00.000Z :alice!a@host PRIVMSG #rust hi"
+            .parse()
+            .unwrap();
+        assert_ne!(a.to_string(), b.to_string());
+        assert_eq!(a.canonical_bytes(), b.canonical_bytes());
+        assert_eq!(
+            a.canonical_bytes(),
+            b"@+draft/reply=x\\sy;msgid=abc;time=2024-01-01T00:00:00.000Z :alice!a@host PRIVMSG #rust :hi\r\n"
+        );
+    }
+
+    #[test]
+    fn test_canonical_duplicates_and_empty() {
+        let msg: Message = "@a=1;b=;a=2 PING x".parse().unwrap();
+        assert_eq!(msg.canonical_bytes(), b"@a=2;b PING x\r\n");
+
+        let mut msg = Message::privmsg("#rust", "hi");
+        msg.tags = Some(Vec::new());
+        assert_eq!(msg.canonical_bytes(), b"PRIVMSG #rust :hi\r\n");
+    }
+}
@@ -1,4 +1,4 @@
-//! IRCv3 message tag escaping utilities.
+//! IRCv3 message tag escaping and relay utilities.
 
 use std::fmt::{Result as FmtResult, Write};
 use std::io::{self, Write as IoWrite};
@@ -67,6 +67,127 @@ pub fn unescape_tag_value(value: &str) -> String {
     unescaped
 }
 
+/// Check whether a wire tag key names the given client tag.
+///
+/// Client-only tags are sent with a `+` prefix (e.g. `+typing`) but specs
+/// usually refer to them without it. The `+` is ignored on both sides, so
+/// `typing`, `+typing` and a server-echoed `+typing` all compare equal.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::message::tags::client_tag_matches;
+///
+/// assert!(client_tag_matches("+typing", "typing"));
+/// assert!(client_tag_matches("typing", "+typing"));
+/// assert!(!client_tag_matches("+draft/typing", "typing"));
+/// ```
+pub fn client_tag_matches(key: &str, name: &str) -> bool {
+    key.strip_prefix('+').unwrap_or(key) == name.strip_prefix('+').unwrap_or(name)
+}
+
+/// Check whether a client-only tag key names `name` in either its ratified
+/// or its `draft/` form.
+///
+/// Specifications in progress use a `draft/` vendor prefix that is dropped
+/// once they are ratified, so both spellings are seen in the wild.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::message::tags::draft_tag_matches;
+///
+/// assert!(draft_tag_matches("+draft/edit", "edit"));
+/// assert!(draft_tag_matches("+edit", "edit"));
+/// assert!(!draft_tag_matches("+vendor/edit", "edit"));
+/// ```
+pub fn draft_tag_matches(key: &str, name: &str) -> bool {
+    let key = key.strip_prefix('+').unwrap_or(key);
+    client_tag_matches(key.strip_prefix("draft/").unwrap_or(key), name)
+}
+
+/// Which incoming tags a server forwards when relaying a client message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagPolicy {
+    /// Forward client-only (`+`-prefixed) tags.
+    pub allow_client_tags: bool,
+    /// Client-only tags to drop even when client tags are allowed
+    /// (e.g. those listed in `CLIENTTAGDENY`), with their `+` prefix.
+    pub denied_client_tags: Vec<String>,
+    /// Server tags a client may supply. Any other non-`+` tag from a client
+    /// is dropped, since only the server may set those.
+    pub allowed_server_tags: Vec<String>,
+}
+
+impl Default for TagPolicy {
+    fn default() -> Self {
+        Self {
+            allow_client_tags: true,
+            denied_client_tags: Vec::new(),
+            allowed_server_tags: Vec::new(),
+        }
+    }
+}
+
+impl TagPolicy {
+    /// Check whether an incoming tag key may be forwarded.
+    pub fn allows(&self, key: &str) -> bool {
+        if key.starts_with('+') {
+            self.allow_client_tags && !self.denied_client_tags.iter().any(|d| d == key)
+        } else {
+            self.allowed_server_tags.iter().any(|a| a == key)
+        }
+    }
+}
+
+/// Build the outbound tag string for relaying a client message.
+///
+/// Filters `incoming_raw_tags` (the raw tag section, without the leading
+/// `@`) through `policy`, then appends the server-generated `added` tags,
+/// escaping their values. An added tag replaces any incoming tag with the
+/// same key. The result has no leading `@` and is empty if no tags remain.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::message::{relay_tags, TagPolicy};
+///
+/// let tags = relay_tags(
+///     "+draft/reply=abc;time=forged",
+///     &[("time", "2023-01-01T00:00:00.000Z"), ("msgid", "x1")],
+///     &TagPolicy::default(),
+/// );
+/// assert_eq!(tags, "+draft/reply=abc;time=2023-01-01T00:00:00.000Z;msgid=x1");
+/// ```
+pub fn relay_tags(incoming_raw_tags: &str, added: &[(&str, &str)], policy: &TagPolicy) -> String {
+    let mut out = String::with_capacity(incoming_raw_tags.len() + 64);
+
+    for tag in incoming_raw_tags.split(';').filter(|t| !t.is_empty()) {
+        let key = tag.split_once('=').map_or(tag, |(k, _)| k);
+        if !policy.allows(key) || added.iter().any(|(k, _)| *k == key) {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push(';');
+        }
+        // Incoming values are already escaped
+        out.push_str(tag);
+    }
+
+    for (key, value) in added {
+        if !out.is_empty() {
+            out.push(';');
+        }
+        out.push_str(key);
+        if !value.is_empty() {
+            out.push('=');
+            let _ = escape_tag_value(&mut out, value);
+        }
+    }
+
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +266,69 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_client_tag_matches() {
+        assert!(client_tag_matches("+typing", "typing"));
+        assert!(client_tag_matches("+typing", "+typing"));
+        assert!(client_tag_matches("typing", "typing"));
+        assert!(client_tag_matches("draft/react", "+draft/react"));
+        assert!(!client_tag_matches("+typing", "react"));
+        assert!(!client_tag_matches("++typing", "typing"));
+    }
+
+    #[test]
+    fn test_draft_tag_matches() {
+        assert!(draft_tag_matches("+draft/typing", "typing"));
+        assert!(draft_tag_matches("channel-context", "+channel-context"));
+        assert!(!draft_tag_matches("+draft/draft/edit", "edit"));
+        assert!(!draft_tag_matches("+editor", "edit"));
+    }
+
+    #[test]
+    fn test_relay_tags() {
+        let policy = TagPolicy::default();
+        let out = relay_tags(
+            "+typing=active;account=spoofed;+draft/reply=m1",
+            &[("time", "2023-01-01T00:00:00.000Z"), ("msgid", "abc")],
+            &policy,
+        );
+        // Client tags kept, client-supplied server tag dropped, server tags added
+        assert_eq!(
+            out,
+            "+typing=active;+draft/reply=m1;time=2023-01-01T00:00:00.000Z;msgid=abc"
+        );
+    }
+
+    #[test]
+    fn test_relay_tags_policy() {
+        let policy = TagPolicy {
+            allow_client_tags: true,
+            denied_client_tags: vec!["+typing".to_string()],
+            allowed_server_tags: vec!["label".to_string()],
+        };
+        let out = relay_tags("+typing=active;+react=x;label=1;batch=b", &[], &policy);
+        assert_eq!(out, "+react=x;label=1");
+
+        let policy = TagPolicy {
+            allow_client_tags: false,
+            ..TagPolicy::default()
+        };
+        assert_eq!(relay_tags("+typing=active", &[], &policy), "");
+    }
+
+    #[test]
+    fn test_relay_tags_added_overrides_and_escapes() {
+        let policy = TagPolicy {
+            allowed_server_tags: vec!["time".to_string()],
+            ..TagPolicy::default()
+        };
+        let out = relay_tags(
+            "time=forged",
+            &[("time", "t"), ("account", "a b;c")],
+            &policy,
+        );
+        assert_eq!(out, "time=t;account=a\\sb\\:c");
+        assert_eq!(relay_tags("", &[("msgid", "1")], &policy), "msgid=1");
+    }
 }
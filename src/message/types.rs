@@ -1,9 +1,11 @@
 use std::borrow::Cow;
 
 use crate::chan::ChannelExt;
-use crate::command::Command;
+use crate::command::{Command, ReadMarker};
+use crate::ctcp::{Ctcp, CtcpKind};
 use crate::error;
 use crate::error::MessageParseError;
+use crate::isupport::LimitKind;
 use crate::prefix::Prefix;
 
 /// An owned IRC message.
@@ -65,6 +67,64 @@ impl Message {
         })
     }
 
+    /// Assemble a message from already-built parts.
+    ///
+    /// Unlike [`new`](Self::new), nothing is parsed, so this cannot fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::{Command, Message, Prefix, Tag};
+    ///
+    /// let msg = Message::from_parts(
+    ///     Some(vec![Tag::new("msgid", Some("abc".to_string()))]),
+    ///     Some(Prefix::new_from_str("nick!user@host")),
+    ///     Command::PRIVMSG("#rust".to_string(), "hello".to_string()),
+    /// );
+    /// assert_eq!(msg.to_string(), "@msgid=abc :nick!user@host PRIVMSG #rust :hello\r\n");
+    /// ```
+    #[must_use]
+    pub fn from_parts(tags: Option<Vec<Tag>>, prefix: Option<Prefix>, command: Command) -> Self {
+        Message {
+            tags,
+            prefix,
+            command,
+        }
+    }
+
+    /// Compare two messages, treating their tags as an unordered set.
+    ///
+    /// Tag order carries no meaning in IRCv3, but the derived [`PartialEq`]
+    /// compares tags in order so that equality matches serialization. Use
+    /// this instead in round-trip tests where tags may be reordered. Duplicate
+    /// tags are counted, and a missing tag list equals an empty one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::Message;
+    ///
+    /// let a: Message = "@a=1;b=2 PING x".parse().unwrap();
+    /// let b: Message = "@b=2;a=1 PING x".parse().unwrap();
+    /// assert_ne!(a, b);
+    /// assert!(a.eq_ignoring_tag_order(&b));
+    /// ```
+    pub fn eq_ignoring_tag_order(&self, other: &Message) -> bool {
+        fn sorted(tags: &Option<Vec<Tag>>) -> Vec<(&str, Option<&str>)> {
+            let mut tags: Vec<_> = tags
+                .iter()
+                .flatten()
+                .map(|Tag(k, v)| (k.as_ref(), v.as_deref()))
+                .collect();
+            tags.sort_unstable();
+            tags
+        }
+
+        self.prefix == other.prefix
+            && self.command == other.command
+            && sorted(&self.tags) == sorted(&other.tags)
+    }
+
     /// Get the nickname from the message prefix, if present.
     pub fn source_nickname(&self) -> Option<&str> {
         self.prefix.as_ref().and_then(|p| match p {
@@ -85,8 +145,46 @@ impl Message {
         }
     }
 
+    /// Get an IRCv3 tag by key.
+    ///
+    /// A leading `+` is ignored on both sides, so `tag("typing")` finds
+    /// `+typing`. Because a client-only `+account` also matches `account`,
+    /// read server tags with the dedicated accessors such as
+    /// [`account_tag`](Self::account_tag), which match exactly.
+    pub fn tag(&self, key: &str) -> Option<&Tag> {
+        self.tags
+            .as_ref()?
+            .iter()
+            .find(|Tag(k, _)| super::tags::client_tag_matches(k, key))
+    }
+
     /// Get the value of an IRCv3 tag by key.
+    ///
+    /// Matches keys like [`tag`](Self::tag).
+    /// Returns `None` both when the tag is absent and when it has no value;
+    /// use [`has_tag`](Self::has_tag) to tell them apart.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::Message;
+    ///
+    /// let msg: Message = "@msgid=abc;+typing=active;bot PING x".parse().unwrap();
+    /// assert_eq!(msg.tag_value("msgid"), Some("abc"));
+    /// assert_eq!(msg.tag_value("typing"), Some("active"));
+    /// assert_eq!(msg.tag_value("+typing"), Some("active"));
+    /// assert_eq!(msg.tag_value("bot"), None);
+    /// assert!(msg.has_tag("bot"));
+    /// ```
     pub fn tag_value(&self, key: &str) -> Option<&str> {
+        self.tag(key)?.1.as_deref()
+    }
+
+    /// Get the value of a server tag, where the `+` prefix is significant.
+    ///
+    /// Keeps a client-only `+account` or `+time` from being read as the
+    /// server's `account` or `time`.
+    fn server_tag_value(&self, key: &str) -> Option<&str> {
         self.tags
             .as_ref()?
             .iter()
@@ -94,25 +192,97 @@ impl Message {
             .and_then(|Tag(_, v)| v.as_deref())
     }
 
+    /// Check if a tag exists (regardless of value).
+    ///
+    /// Matches keys like [`tag`](Self::tag).
+    pub fn has_tag(&self, key: &str) -> bool {
+        self.tag(key).is_some()
+    }
+
+    /// Remove the first tag matching `key` and return it.
+    ///
+    /// Matches keys like [`tag`](Self::tag). If no tags remain, `tags` is
+    /// reset to `None`.
+    pub fn remove_tag(&mut self, key: &str) -> Option<Tag> {
+        let tags = self.tags.as_mut()?;
+        let index = tags
+            .iter()
+            .position(|Tag(k, _)| super::tags::client_tag_matches(k, key))?;
+        let tag = tags.remove(index);
+        if tags.is_empty() {
+            self.tags = None;
+        }
+        Some(tag)
+    }
+
     /// Get the server-time tag value.
     pub fn server_time(&self) -> Option<&str> {
-        self.tag_value("time")
+        self.server_tag_value("time")
     }
 
     /// Get the labeled-response label tag.
     pub fn label(&self) -> Option<&str> {
-        self.tag_value("label")
+        self.server_tag_value("label")
     }
 
     /// Get the message ID tag.
     pub fn msgid(&self) -> Option<&str> {
-        self.tag_value("msgid")
+        self.server_tag_value("msgid")
+    }
+
+    /// Get the batch reference tag value, if this message is inside a batch.
+    pub fn batch_ref(&self) -> Option<&str> {
+        self.server_tag_value("batch")
     }
 
     /// Get the account tag value.
     pub fn account_tag(&self) -> Option<&str> {
-        self.tag_value("account")
+        self.server_tag_value("account")
+    }
+
+    /// Get the value of a client-only tag, with or without its `+` prefix.
+    ///
+    /// `client_tag_value("typing")` matches both `typing` and `+typing`.
+    pub fn client_tag_value(&self, name: &str) -> Option<&str> {
+        self.tag(name)?.1.as_deref()
+    }
+
+    /// Get the typing notification state (`active`, `paused` or `done`).
+    ///
+    /// Accepts `+typing` and `typing`, falling back to `+draft/typing`.
+    pub fn typing(&self) -> Option<&str> {
+        self.client_tag_value("typing")
+            .or_else(|| self.client_tag_value("draft/typing"))
+    }
+
+    /// Get the reaction tag value.
+    ///
+    /// Accepts `+draft/react` and `+react`, with or without the `+` prefix.
+    pub fn react(&self) -> Option<&str> {
+        self.client_tag_value("draft/react")
+            .or_else(|| self.client_tag_value("react"))
+    }
+
+    /// Get the value of a client-only tag in its ratified or `draft/` form.
+    pub fn draft_tag_value(&self, name: &str) -> Option<&str> {
+        self.tags
+            .as_ref()?
+            .iter()
+            .find(|Tag(k, _)| super::tags::draft_tag_matches(k, name))
+            .and_then(|Tag(_, v)| v.as_deref())
     }
+
+    /// Get the msgid this message edits (`+draft/edit`), if any.
+    pub fn edits(&self) -> Option<&str> {
+        self.draft_tag_value("edit")
+    }
+
+    /// Get the channel a private message relates to
+    /// (`+draft/channel-context`), if any.
+    pub fn channel_context(&self) -> Option<&str> {
+        self.draft_tag_value("channel-context")
+    }
+
     /// Create a PRIVMSG message to a target with text
     #[must_use]
     pub fn privmsg<T, M>(target: T, text: M) -> Self
@@ -123,6 +293,19 @@ impl Message {
         Command::PRIVMSG(target.into(), text.into()).into()
     }
 
+    /// Create a PRIVMSG replacing the text of a previously sent message
+    ///
+    /// The new text is tagged with `+draft/edit` pointing at `target_msgid`.
+    #[must_use]
+    pub fn edit<T, M, I>(target: T, new_text: M, target_msgid: I) -> Self
+    where
+        T: Into<String>,
+        M: Into<String>,
+        I: Into<String>,
+    {
+        Self::privmsg(target, new_text).with_tag("+draft/edit", Some(target_msgid))
+    }
+
     /// Create a NOTICE message to a target with text
     #[must_use]
     pub fn notice<T, M>(target: T, text: M) -> Self
@@ -133,6 +316,38 @@ impl Message {
         Command::NOTICE(target.into(), text.into()).into()
     }
 
+    /// Create a CTCP request, sent as a PRIVMSG to a target
+    ///
+    /// The body is wrapped in `\x01` delimiters; an empty `args` sends the
+    /// bare command (e.g. `\x01VERSION\x01`).
+    #[must_use]
+    pub fn ctcp<T>(target: T, kind: CtcpKind, args: &str) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::privmsg(target, ctcp_body(kind, args))
+    }
+
+    /// Create a CTCP reply, sent as a NOTICE to a target
+    ///
+    /// Replies use NOTICE so that clients never answer them automatically.
+    #[must_use]
+    pub fn ctcp_reply<T>(target: T, kind: CtcpKind, args: &str) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::notice(target, ctcp_body(kind, args))
+    }
+
+    /// Create a CTCP ACTION (`/me`) message to a target
+    #[must_use]
+    pub fn action<T>(target: T, text: &str) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::ctcp(target, CtcpKind::Action, text)
+    }
+
     /// Create a JOIN message for a channel
     #[must_use]
     pub fn join<C>(channel: C) -> Self
@@ -190,6 +405,26 @@ impl Message {
         Command::USER(username.into(), "0".into(), realname.into()).into()
     }
 
+    /// Create a USER message requesting initial user modes
+    ///
+    /// RFC 2812 encodes the requested modes as a bitmask in the second
+    /// parameter: bit 3 (`8`) sets `+i` and bit 2 (`4`) sets `+w`.
+    #[must_use]
+    pub fn user_with_mode<U, R>(username: U, realname: R, invisible: bool, wallops: bool) -> Self
+    where
+        U: Into<String>,
+        R: Into<String>,
+    {
+        let mut mode = 0u8;
+        if invisible {
+            mode |= 8;
+        }
+        if wallops {
+            mode |= 4;
+        }
+        Command::USER(username.into(), mode.to_string(), realname.into()).into()
+    }
+
     /// Create a PING message to a server
     #[must_use]
     pub fn ping<S>(server: S) -> Self
@@ -236,6 +471,21 @@ impl Message {
         Command::QUIT(Some(message.into())).into()
     }
 
+    /// Create a QUIT message, validating the optional reason
+    ///
+    /// With `None` the QUIT carries no reason and the server substitutes its
+    /// default. The reason is written as a trailing argument, with a leading
+    /// `:` where needed, so it may contain spaces.
+    ///
+    /// Returns [`ProtocolError::IllegalControlChar`](error::ProtocolError::IllegalControlChar)
+    /// if `reason` contains CR, LF, or another illegal control character.
+    pub fn quit_checked(reason: Option<&str>) -> error::Result<Self> {
+        if let Some(reason) = reason {
+            validate_single_line(reason)?;
+        }
+        Ok(Command::QUIT(reason.map(str::to_owned)).into())
+    }
+
     /// Create a KICK message
     #[must_use]
     pub fn kick<C, N>(channel: C, nickname: N) -> Self
@@ -257,6 +507,117 @@ impl Message {
         Command::KICK(channel.into(), nickname.into(), Some(reason.into())).into()
     }
 
+    /// Create a KICK message, checked against the server's `KICKLEN`
+    ///
+    /// Pass the advertised limit (see
+    /// [`Isupport::kicklen`](crate::isupport::Isupport::kicklen)) to reject
+    /// reasons the server would otherwise truncate.
+    ///
+    /// Returns [`ProtocolError::LengthExceeded`](error::ProtocolError::LengthExceeded)
+    /// if `reason` is longer than `kicklen` bytes, or
+    /// [`ProtocolError::IllegalControlChar`](error::ProtocolError::IllegalControlChar)
+    /// if it contains CR, LF, or another illegal control character.
+    pub fn kick_checked<C, N, R>(
+        channel: C,
+        nickname: N,
+        reason: R,
+        kicklen: usize,
+    ) -> error::Result<Self>
+    where
+        C: Into<String>,
+        N: Into<String>,
+        R: Into<String>,
+    {
+        let reason = reason.into();
+        validate_single_line(&reason)?;
+        if reason.len() > kicklen {
+            return Err(error::ProtocolError::LengthExceeded {
+                kind: LimitKind::Kick,
+                actual: reason.len(),
+                limit: kicklen,
+            });
+        }
+        Ok(Command::KICK(channel.into(), nickname.into(), Some(reason)).into())
+    }
+
+    /// Create a REDACT message removing a previously sent message
+    ///
+    /// Requires the `draft/message-redaction` capability.
+    #[must_use]
+    pub fn redact<C, I>(channel: C, msgid: I) -> Self
+    where
+        C: Into<String>,
+        I: Into<String>,
+    {
+        Command::REDACT {
+            channel: channel.into(),
+            msgid: msgid.into(),
+            reason: None,
+        }
+        .into()
+    }
+
+    /// Create a REDACT message with a reason
+    #[must_use]
+    pub fn redact_with_reason<C, I, R>(channel: C, msgid: I, reason: R) -> Self
+    where
+        C: Into<String>,
+        I: Into<String>,
+        R: Into<String>,
+    {
+        Command::REDACT {
+            channel: channel.into(),
+            msgid: msgid.into(),
+            reason: Some(reason.into()),
+        }
+        .into()
+    }
+
+    /// Create a MARKREAD message querying or updating a read marker
+    ///
+    /// With `None` the server replies with the stored marker; with a
+    /// timestamp (e.g. `2024-01-01T00:00:00.000Z`) the marker is updated.
+    /// Requires the `draft/read-marker` capability.
+    #[must_use]
+    pub fn markread<T>(target: T, timestamp: Option<&str>) -> Self
+    where
+        T: Into<String>,
+    {
+        Command::MARKREAD {
+            target: target.into(),
+            marker: timestamp.map_or(ReadMarker::Query, |ts| ReadMarker::Timestamp(ts.to_owned())),
+        }
+        .into()
+    }
+
+    /// Create a TOPIC message setting a channel's topic, checked against
+    /// the server's `TOPICLEN`
+    ///
+    /// Servers silently truncate longer topics, so pass the advertised limit
+    /// (see [`Isupport::topiclen`](crate::isupport::Isupport::topiclen)) to
+    /// reject them up front instead.
+    ///
+    /// Returns [`ProtocolError::LengthExceeded`](error::ProtocolError::LengthExceeded)
+    /// if `topic` is longer than `topiclen` bytes, or
+    /// [`ProtocolError::IllegalControlChar`](error::ProtocolError::IllegalControlChar)
+    /// if it contains CR, LF, or another illegal control character.
+    pub fn topic_checked<C, T>(channel: C, topic: T, topiclen: usize) -> error::Result<Self>
+    where
+        C: Into<String>,
+        T: Into<String>,
+    {
+        let topic = topic.into();
+        validate_single_line(&topic)?;
+        if topic.len() > topiclen {
+            return Err(error::ProtocolError::LengthExceeded {
+                kind: LimitKind::Topic,
+                actual: topic.len(),
+                limit: topiclen,
+            });
+        }
+        Ok(Command::TOPIC(channel.into(), Some(topic)).into())
+    }
+
     /// Create an AWAY message
     #[must_use]
     pub fn away() -> Self {
@@ -272,6 +633,134 @@ impl Message {
         Command::AWAY(Some(message.into())).into()
     }
 
+    /// Create an AWAY message, checked against the server's `AWAYLEN`
+    ///
+    /// Pass the advertised limit (see
+    /// [`Isupport::awaylen`](crate::isupport::Isupport::awaylen)) to reject
+    /// reasons the server would otherwise truncate.
+    ///
+    /// Returns [`ProtocolError::LengthExceeded`](error::ProtocolError::LengthExceeded)
+    /// if `reason` is longer than `awaylen` bytes, or
+    /// [`ProtocolError::IllegalControlChar`](error::ProtocolError::IllegalControlChar)
+    /// if it contains CR, LF, or another illegal control character.
+    pub fn away_checked<M>(reason: M, awaylen: usize) -> error::Result<Self>
+    where
+        M: Into<String>,
+    {
+        let reason = reason.into();
+        validate_single_line(&reason)?;
+        if reason.len() > awaylen {
+            return Err(error::ProtocolError::LengthExceeded {
+                kind: LimitKind::Away,
+                actual: reason.len(),
+                limit: awaylen,
+            });
+        }
+        Ok(Command::AWAY(Some(reason)).into())
+    }
+
+    /// Create a PROTOCTL message enabling legacy protocol options
+    ///
+    /// Used on networks that advertise `NAMESX`/`UHNAMES` in `RPL_ISUPPORT`
+    /// instead of offering the `multi-prefix`/`userhost-in-names` capabilities.
+    #[must_use]
+    pub fn protoctl<I, S>(tokens: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Command::PROTOCTL(tokens.into_iter().map(Into::into).collect()).into()
+    }
+
+    /// Create a WALLOPS message broadcasting text to opers and `+w` users
+    ///
+    /// Returns [`ProtocolError::IllegalControlChar`](error::ProtocolError::IllegalControlChar)
+    /// if `text` contains CR, LF, or another illegal control character,
+    /// since the text must fit on a single line.
+    pub fn wallops<M>(text: M) -> error::Result<Self>
+    where
+        M: Into<String>,
+    {
+        let text = text.into();
+        validate_single_line(&text)?;
+        Ok(Command::WALLOPS(text).into())
+    }
+
+    /// Create a GLOBOPS message broadcasting text to all opers
+    ///
+    /// Returns [`ProtocolError::IllegalControlChar`](error::ProtocolError::IllegalControlChar)
+    /// if `text` contains CR, LF, or another illegal control character,
+    /// since the text must fit on a single line.
+    pub fn globops<M>(text: M) -> error::Result<Self>
+    where
+        M: Into<String>,
+    {
+        let text = text.into();
+        validate_single_line(&text)?;
+        Ok(Command::GLOBOPS(text).into())
+    }
+
+    /// Create an OPER message authenticating as an IRC operator
+    ///
+    /// For certificate-based oper blocks the server matches the TLS client
+    /// certificate instead of the password; pass an empty `password` (or the
+    /// certificate fingerprint, if the network expects one).
+    ///
+    /// Returns [`ProtocolError::InvalidArgument`](error::ProtocolError::InvalidArgument)
+    /// if `name` is empty, contains whitespace or control characters, or starts
+    /// with `:`, or
+    /// [`ProtocolError::IllegalControlChar`](error::ProtocolError::IllegalControlChar)
+    /// if `password` contains CR, LF, or another illegal control character.
+    pub fn oper<N, P>(name: N, password: P) -> error::Result<Self>
+    where
+        N: Into<String>,
+        P: Into<String>,
+    {
+        let name = name.into();
+        let password = password.into();
+        if name.is_empty()
+            || name.starts_with(':')
+            || name.chars().any(|c| c.is_whitespace() || c.is_control())
+        {
+            return Err(error::ProtocolError::InvalidArgument(format!(
+                "invalid oper name: {}",
+                name
+            )));
+        }
+        validate_single_line(&password)?;
+        Ok(Command::OPER(name, password).into())
+    }
+
+    /// Create an INVITE message inviting a nickname to a channel
+    ///
+    /// Returns [`ProtocolError::InvalidArgument`](error::ProtocolError::InvalidArgument)
+    /// if `nickname` is not a valid nickname or `channel` is not a valid
+    /// channel name.
+    pub fn invite<N, C>(nickname: N, channel: C) -> error::Result<Self>
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        use crate::chan::ChannelExt;
+        use crate::nick::NickExt;
+
+        let nickname = nickname.into();
+        let channel = channel.into();
+        if !nickname.is_valid_nick() {
+            return Err(error::ProtocolError::InvalidArgument(format!(
+                "invalid nickname: {}",
+                nickname
+            )));
+        }
+        if !channel.is_channel_name() {
+            return Err(error::ProtocolError::InvalidArgument(format!(
+                "invalid channel name: {}",
+                channel
+            )));
+        }
+        Ok(Command::INVITE(nickname, channel).into())
+    }
+
     /// Add IRCv3 tags to this message
     #[must_use]
     pub fn with_message_tags(mut self, tags: Vec<Tag>) -> Self {
@@ -295,6 +784,18 @@ impl Message {
         self
     }
 
+    /// Add a `+typing` client tag (`active`, `paused` or `done`).
+    #[must_use]
+    pub fn with_typing<S: Into<String>>(self, state: S) -> Self {
+        self.with_tag("+typing", Some(state))
+    }
+
+    /// Add a `+draft/react` client tag.
+    #[must_use]
+    pub fn with_react<S: Into<String>>(self, reaction: S) -> Self {
+        self.with_tag("+draft/react", Some(reaction))
+    }
+
     /// Set the prefix/source of this message
     #[must_use]
     pub fn with_prefix(mut self, prefix: crate::prefix::Prefix) -> Self {
@@ -303,6 +804,23 @@ impl Message {
     }
 }
 
+/// Delimit a CTCP command and its arguments.
+fn ctcp_body(kind: CtcpKind, args: &str) -> String {
+    Ctcp {
+        kind,
+        params: (!args.is_empty()).then_some(args),
+    }
+    .to_string()
+}
+
+/// Reject text that would break out of a single IRC line.
+fn validate_single_line(text: &str) -> error::Result<()> {
+    match crate::format::find_illegal_text_char(text) {
+        Some(ch) => Err(error::ProtocolError::IllegalControlChar(ch)),
+        None => Ok(()),
+    }
+}
+
 impl From<Command> for Message {
     fn from(cmd: Command) -> Message {
         Message {
@@ -331,6 +849,53 @@ impl Tag {
     pub fn new(key: impl Into<String>, value: Option<String>) -> Self {
         Tag(Cow::Owned(key.into()), value)
     }
+
+    /// Whether this is a client-only tag (key starts with `+`).
+    ///
+    /// Client-only tags such as `+typing` are relayed by servers without
+    /// interpretation.
+    pub fn is_client_only(&self) -> bool {
+        self.0.starts_with('+')
+    }
+
+    /// Get the vendor namespace of the key, if any.
+    ///
+    /// Returns `draft` for `+draft/reply` and `example.com` for
+    /// `example.com/foo`.
+    pub fn vendor(&self) -> Option<&str> {
+        let key = self.0.strip_prefix('+').unwrap_or(&self.0);
+        key.rsplit_once('/').map(|(vendor, _)| vendor)
+    }
+
+    /// Check a key against the IRCv3 grammar:
+    /// `['+'] [<vendor> '/'] <key_name>`.
+    ///
+    /// The vendor is a hostname (letters, digits, `-` and `.`) and the key
+    /// name a non-empty run of letters, digits and `-`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::message::Tag;
+    ///
+    /// assert!(Tag::is_valid_key("+draft/reply"));
+    /// assert!(!Tag::is_valid_key("bad key"));
+    /// ```
+    pub fn is_valid_key(key: &str) -> bool {
+        let key = key.strip_prefix('+').unwrap_or(key);
+        let (vendor, name) = match key.split_once('/') {
+            Some((vendor, name)) => (Some(vendor), name),
+            None => (None, key),
+        };
+        let vendor_ok = vendor.map_or(true, |v| {
+            !v.is_empty()
+                && v.bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'.')
+        });
+        vendor_ok
+            && !name.is_empty()
+            && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    }
 }
 
 #[cfg(test)]
@@ -352,6 +917,216 @@ mod tests {
         assert!(msg.prefix.is_none());
     }
 
+    #[test]
+    fn test_typing_react_tags() {
+        let msg: Message = "@+typing=active;+draft/react=ok :nick TAGMSG #ch"
+            .parse()
+            .unwrap();
+        assert_eq!(msg.typing(), Some("active"));
+        assert_eq!(msg.client_tag_value("+typing"), Some("active"));
+        assert_eq!(msg.react(), Some("ok"));
+
+        let msg = Message::from(Command::TAGMSG("#ch".to_string()))
+            .with_typing("paused")
+            .with_react("ok");
+        assert_eq!(
+            msg.to_string(),
+            "@+typing=paused;+draft/react=ok TAGMSG #ch\r\n"
+        );
+        let reparsed: Message = msg.to_string().parse().unwrap();
+        assert_eq!(reparsed.typing(), Some("paused"));
+    }
+
+    #[test]
+    fn test_edit_roundtrip() {
+        let msg = Message::edit("#ch", "fixed typo", "abc123");
+        assert_eq!(msg.edits(), Some("abc123"));
+        assert_eq!(
+            msg.to_string(),
+            "@+draft/edit=abc123 PRIVMSG #ch :fixed typo\r\n"
+        );
+
+        let raw = msg.to_string();
+        let reparsed: Message = raw.parse().unwrap();
+        assert_eq!(reparsed, msg);
+        let msg_ref = crate::MessageRef::parse(raw.trim_end()).unwrap();
+        assert_eq!(msg_ref.edits(), Some("abc123"));
+
+        let msg: Message = "@+edit=xyz;+draft/channel-context=#rust PRIVMSG bob :hi"
+            .parse()
+            .unwrap();
+        assert_eq!(msg.edits(), Some("xyz"));
+        assert_eq!(msg.channel_context(), Some("#rust"));
+    }
+
+    #[test]
+    fn test_eq_ignoring_tag_order() {
+        let built = Message::privmsg("#rust", "hi")
+            .with_tag("msgid", Some("abc"))
+            .with_tag("+typing", None::<String>)
+            .with_tag("time", Some("2024-01-01T00:00:00Z"));
+        let parsed: Message = "@time=2024-01-01T00:00:00Z;msgid=abc;+typing PRIVMSG #rust :hi"
+            .parse()
+            .unwrap();
+        assert_ne!(built, parsed);
+        assert!(built.eq_ignoring_tag_order(&parsed));
+        assert!(parsed.eq_ignoring_tag_order(&built));
+
+        // Values, duplicates and the rest of the message still matter
+        let other_value: Message = "@time=2024-01-01T00:00:00Z;msgid=xyz;+typing PRIVMSG #rust :hi"
+            .parse()
+            .unwrap();
+        assert!(!built.eq_ignoring_tag_order(&other_value));
+        let duplicated = parsed.clone().with_tag("msgid", Some("abc"));
+        assert!(!built.eq_ignoring_tag_order(&duplicated));
+        let other_text =
+            Message::privmsg("#rust", "bye").with_message_tags(parsed.tags.clone().unwrap());
+        assert!(!built.eq_ignoring_tag_order(&other_text));
+
+        let untagged = Message::privmsg("#rust", "hi");
+        assert!(untagged.eq_ignoring_tag_order(&untagged.clone().with_message_tags(vec![])));
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let msg = Message::from_parts(
+            Some(vec![
+                Tag::new("time", Some("2024-01-01T00:00:00Z".to_string())),
+                Tag::new("+typing", None),
+            ]),
+            Some(Prefix::Nickname(
+                "nick".to_string(),
+                "user".to_string(),
+                "host".to_string(),
+            )),
+            Command::NOTICE("#rust".to_string(), "hello there".to_string()),
+        );
+        let raw = msg.to_string();
+        assert_eq!(
+            raw,
+            "@time=2024-01-01T00:00:00Z;+typing :nick!user@host NOTICE #rust :hello there\r\n"
+        );
+        assert_eq!(raw.parse::<Message>().unwrap(), msg);
+
+        let bare = Message::from_parts(None, None, Command::PING("srv".to_string(), None));
+        assert_eq!(bare, Message::from(Command::PING("srv".to_string(), None)));
+    }
+
+    #[test]
+    fn test_tag_lookup() {
+        let mut msg: Message = "@time=2024-01-01T00:00:00Z;+draft/reply=abc;+typing=active;bot \
+             :nick!u@h PRIVMSG #ch :hi"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            msg.tag("time"),
+            Some(&Tag::new("time", Some("2024-01-01T00:00:00Z".to_string())))
+        );
+        assert_eq!(msg.tag_value("+draft/reply"), Some("abc"));
+        assert_eq!(msg.client_tag_value("draft/reply"), Some("abc"));
+        assert_eq!(
+            msg.tag("typing").and_then(|t| t.1.as_deref()),
+            Some("active")
+        );
+        assert!(msg.has_tag("+typing"));
+
+        // Value-less tag
+        assert_eq!(msg.tag_value("bot"), None);
+        assert!(msg.has_tag("bot"));
+
+        // Absent tag
+        assert_eq!(msg.tag("msgid"), None);
+        assert_eq!(msg.tag_value("msgid"), None);
+        assert!(!msg.has_tag("msgid"));
+        assert!(!msg.has_tag("reply"));
+
+        assert_eq!(
+            msg.remove_tag("typing"),
+            Some(Tag::new("+typing", Some("active".to_string())))
+        );
+        assert!(!msg.has_tag("typing"));
+        assert_eq!(msg.remove_tag("typing"), None);
+        assert_eq!(
+            msg.to_string(),
+            "@time=2024-01-01T00:00:00Z;+draft/reply=abc;bot :nick!u@h PRIVMSG #ch :hi\r\n"
+        );
+
+        for key in ["time", "draft/reply", "bot"] {
+            assert!(msg.remove_tag(key).is_some());
+        }
+        assert_eq!(msg.tags, None);
+        assert_eq!(msg.to_string(), ":nick!u@h PRIVMSG #ch :hi\r\n");
+    }
+
+    #[test]
+    fn test_client_tags_do_not_spoof_server_tags() {
+        let msg: Message =
+            "@+account=admin;+time=2000-01-01T00:00:00.000Z;+msgid=x PRIVMSG #ch :hi"
+                .parse()
+                .unwrap();
+        assert_eq!(msg.account_tag(), None);
+        assert_eq!(msg.server_time(), None);
+        assert_eq!(msg.msgid(), None);
+        assert_eq!(msg.tag_value("account"), Some("admin"));
+        assert_eq!(msg.client_tag_value("account"), Some("admin"));
+        assert!(msg.has_tag("account"));
+
+        let msg: Message = "@+account=admin;account=alice PRIVMSG #ch :hi"
+            .parse()
+            .unwrap();
+        assert_eq!(msg.account_tag(), Some("alice"));
+    }
+
+    #[test]
+    fn test_tag_key_kinds() {
+        let typing = Tag::new("+typing", Some("active".to_string()));
+        assert!(typing.is_client_only());
+        assert_eq!(typing.vendor(), None);
+
+        let reply = Tag::new("+draft/reply", Some("abc".to_string()));
+        assert!(reply.is_client_only());
+        assert_eq!(reply.vendor(), Some("draft"));
+
+        let draft = Tag::new("draft/reply", None);
+        assert!(!draft.is_client_only());
+        assert_eq!(draft.vendor(), Some("draft"));
+
+        let vendored = Tag::new("example.com/foo", None);
+        assert!(!vendored.is_client_only());
+        assert_eq!(vendored.vendor(), Some("example.com"));
+
+        for key in ["+typing", "draft/reply", "example.com/foo", "msgid"] {
+            assert!(Tag::is_valid_key(key), "{}", key);
+        }
+        for key in ["", "+", "bad key", "a/b/c", "/foo", "foo/", "++x", "a_b"] {
+            assert!(!Tag::is_valid_key(key), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn test_invalid_tag_key_rejected() {
+        let err = "@+typing=active;bad_key=1 TAGMSG #ch"
+            .parse::<Message>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            error::ProtocolError::InvalidMessage {
+                cause: MessageParseError::InvalidTagKey(ref key),
+                ..
+            } if key == "bad_key"
+        ));
+        assert!(matches!(
+            crate::MessageRef::parse("@a=1;=2 PING x"),
+            Err(MessageParseError::InvalidTagKey(ref key)) if key.is_empty()
+        ));
+
+        // Lenient parsing accepts it
+        let options = crate::message::ParseOptions::lenient();
+        let msg = Message::parse_with_options("@bad_key=1 PING x", &options).unwrap();
+        assert_eq!(msg.tags.unwrap()[0].0, "bad_key");
+    }
+
     #[test]
     fn test_notice_constructor() {
         let msg = Message::notice("nickname", "This is a notice");
@@ -436,6 +1211,99 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_topic_checked() {
+        let msg = Message::topic_checked("#rust", "Rust discussion", 20).unwrap();
+        assert_eq!(msg.to_string(), "TOPIC #rust :Rust discussion\r\n");
+
+        let exact = "x".repeat(20);
+        assert!(Message::topic_checked("#rust", exact, 20).is_ok());
+
+        let err = Message::topic_checked("#rust", "x".repeat(21), 20).unwrap_err();
+        assert!(matches!(
+            err,
+            error::ProtocolError::LengthExceeded {
+                kind: LimitKind::Topic,
+                actual: 21,
+                limit: 20
+            }
+        ));
+        assert_eq!(err.to_string(), "TOPICLEN exceeded: 21 bytes (limit: 20)");
+
+        let err = Message::topic_checked("#rust", "hi\r\nQUIT", 20).unwrap_err();
+        assert!(matches!(
+            err,
+            error::ProtocolError::IllegalControlChar('\r')
+        ));
+    }
+
+    #[test]
+    fn test_kick_checked() {
+        let isupport = crate::isupport::parse_params(&["KICKLEN=16"]);
+        let kicklen = isupport.kicklen().unwrap();
+
+        let msg = Message::kick_checked("#chan", "bob", "Take a break", kicklen).unwrap();
+        assert_eq!(msg.to_string(), "KICK #chan bob :Take a break\r\n");
+        assert!(Message::kick_checked("#chan", "bob", "x".repeat(16), kicklen).is_ok());
+
+        let err = Message::kick_checked("#chan", "bob", "x".repeat(17), kicklen).unwrap_err();
+        assert!(matches!(
+            err,
+            error::ProtocolError::LengthExceeded {
+                kind: LimitKind::Kick,
+                actual: 17,
+                limit: 16
+            }
+        ));
+
+        let err = Message::kick_checked("#chan", "bob", "bye\nKICK #chan alice", 64).unwrap_err();
+        assert!(matches!(
+            err,
+            error::ProtocolError::IllegalControlChar('\n')
+        ));
+    }
+
+    #[test]
+    fn test_away_checked() {
+        let isupport = crate::isupport::parse_params(&["AWAYLEN=200"]);
+        let awaylen = isupport.awaylen().unwrap();
+
+        let msg = Message::away_checked("Gone fishing", awaylen).unwrap();
+        assert_eq!(msg.to_string(), "AWAY :Gone fishing\r\n");
+        assert!(Message::away_checked("x".repeat(200), awaylen).is_ok());
+
+        let err = Message::away_checked("x".repeat(201), awaylen).unwrap_err();
+        assert!(matches!(
+            err,
+            error::ProtocolError::LengthExceeded {
+                kind: LimitKind::Away,
+                actual: 201,
+                limit: 200
+            }
+        ));
+
+        let err = Message::away_checked("brb\0", awaylen).unwrap_err();
+        assert!(matches!(
+            err,
+            error::ProtocolError::IllegalControlChar('\0')
+        ));
+    }
+
+    #[test]
+    fn test_user_with_mode_bitmask() {
+        let mode_of = |msg: Message| match msg.command {
+            Command::USER(_, mode, _) => mode,
+            _ => panic!("Expected USER command"),
+        };
+        assert_eq!(mode_of(Message::user_with_mode("u", "R", true, true)), "12");
+        assert_eq!(mode_of(Message::user_with_mode("u", "R", true, false)), "8");
+        assert_eq!(mode_of(Message::user_with_mode("u", "R", false, true)), "4");
+        assert_eq!(mode_of(Message::user("u", "R")), "0");
+
+        let msg = Message::user_with_mode("testuser", "Test User", true, true);
+        assert_eq!(msg.to_string(), "USER testuser 12 * :Test User\r\n");
+    }
+
     #[test]
     fn test_ping_constructor() {
         let msg = Message::ping("irc.example.com");
@@ -494,6 +1362,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_quit_checked() {
+        let msg = Message::quit_checked(Some("Gone to lunch")).unwrap();
+        assert_eq!(msg.to_string(), "QUIT :Gone to lunch\r\n");
+        assert_eq!(msg.to_string().parse::<Message>().unwrap(), msg);
+
+        let msg = Message::quit_checked(None).unwrap();
+        assert_eq!(msg.to_string(), "QUIT\r\n");
+        assert_eq!(msg, Message::quit());
+
+        assert!(matches!(
+            Message::quit_checked(Some("bye\r\nPRIVMSG #rust :pwned")),
+            Err(error::ProtocolError::IllegalControlChar('\r'))
+        ));
+        assert!(Message::quit_checked(Some("bye\n")).is_err());
+    }
+
+    #[test]
+    fn test_ctcp_constructors() {
+        let msg = Message::action("#x", "waves");
+        assert_eq!(msg.to_string(), "PRIVMSG #x :\x01ACTION waves\x01\r\n");
+        assert_eq!(msg.to_string().parse::<Message>().unwrap(), msg);
+
+        let msg = Message::ctcp("bob", CtcpKind::Version, "");
+        assert_eq!(msg.to_string(), "PRIVMSG bob :\x01VERSION\x01\r\n");
+
+        let msg = Message::ctcp_reply("bob", CtcpKind::Version, "slirc 1.0");
+        assert_eq!(msg.to_string(), "NOTICE bob :\x01VERSION slirc 1.0\x01\r\n");
+        let Command::NOTICE(_, text) = &msg.command else {
+            panic!("Expected NOTICE command");
+        };
+        let ctcp = Ctcp::parse(text).unwrap();
+        assert_eq!(ctcp.kind, CtcpKind::Version);
+        assert_eq!(ctcp.params, Some("slirc 1.0"));
+    }
+
     #[test]
     fn test_kick_constructor() {
         let msg = Message::kick("#channel", "baduser");
@@ -542,6 +1446,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_invite_constructor() {
+        let msg = Message::invite("alice", "#rust").unwrap();
+        assert_eq!(
+            msg.command,
+            Command::INVITE("alice".to_string(), "#rust".to_string())
+        );
+
+        assert!(Message::invite("bad nick", "#rust").is_err());
+        assert!(Message::invite("alice", "rust").is_err());
+        assert!(Message::invite("alice", "#a,#b").is_err());
+        assert!(matches!(
+            Message::invite("alice", "rust"),
+            Err(error::ProtocolError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_oper_constructor() {
+        let msg = Message::oper("admin", "hunter2").unwrap();
+        assert_eq!(msg.to_string(), "OPER admin hunter2\r\n");
+        assert_eq!(msg.to_string().parse::<Message>().unwrap(), msg);
+
+        // Certificate-based oper with an empty password
+        let msg = Message::oper("certop", "").unwrap();
+        assert_eq!(msg.to_string(), "OPER certop :\r\n");
+        assert_eq!(msg.to_string().parse::<Message>().unwrap(), msg);
+
+        assert!(Message::oper("bad name", "pw").is_err());
+        assert!(Message::oper("", "pw").is_err());
+        assert!(Message::oper(":admin", "pw").is_err());
+        assert!(matches!(
+            Message::oper("admin", "pw\r\nQUIT"),
+            Err(error::ProtocolError::IllegalControlChar('\r'))
+        ));
+    }
+
+    #[test]
+    fn test_protoctl_constructor() {
+        let msg = Message::protoctl(["NAMESX", "UHNAMES"]);
+        assert_eq!(msg.to_string(), "PROTOCTL NAMESX UHNAMES\r\n");
+        assert_eq!(msg.to_string().parse::<Message>().unwrap(), msg);
+        assert_eq!(
+            crate::encode::IrcEncode::to_bytes(&msg),
+            msg.to_string().into_bytes()
+        );
+    }
+
+    #[test]
+    fn test_wallops_constructor() {
+        let msg = Message::wallops("Server restarting soon").unwrap();
+        assert_eq!(msg.to_string(), "WALLOPS :Server restarting soon\r\n");
+
+        let parsed: Message = msg.to_string().parse().unwrap();
+        assert_eq!(parsed, msg);
+    }
+
+    #[test]
+    fn test_globops_constructor() {
+        let msg = Message::globops("Routing split detected").unwrap();
+        assert_eq!(msg.to_string(), "GLOBOPS :Routing split detected\r\n");
+
+        let parsed: Message = msg.to_string().parse().unwrap();
+        assert_eq!(parsed, msg);
+    }
+
+    #[test]
+    fn test_wallops_globops_reject_line_breaks() {
+        assert!(matches!(
+            Message::wallops("line one\r\nPRIVMSG #c :injected"),
+            Err(error::ProtocolError::IllegalControlChar('\r'))
+        ));
+        assert!(matches!(
+            Message::globops("line one\nline two"),
+            Err(error::ProtocolError::IllegalControlChar('\n'))
+        ));
+        assert!(matches!(
+            Message::wallops("nul\0byte"),
+            Err(error::ProtocolError::IllegalControlChar('\0'))
+        ));
+    }
+
     #[test]
     fn test_with_message_tags() {
         let msg = Message::privmsg("#test", "Hello").with_message_tags(vec![
@@ -0,0 +1,201 @@
+//! Computing and batching the mode changes between two channel states.
+
+use super::{ChannelMode, Mode};
+use crate::command::Command;
+
+/// Compute the mode changes that turn `current` into `desired`.
+///
+/// Both slices describe the modes set on a channel; `+` and unprefixed
+/// entries count as set and `-` entries are ignored. List modes (`b`, `e`,
+/// `I`, `q`) and prefix modes (`o`, `v`, ...) are compared as sets of
+/// mode/argument pairs, so individual masks or nicknames are added or
+/// removed. Single-value modes (`k`, `l`) are set when missing or changed
+/// and cleared when no longer wanted; `-k` carries the current key, as many
+/// servers require. Other modes are compared by presence.
+///
+/// Removals come first, then additions, each in input order.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::mode::{diff, ChannelMode, Mode};
+///
+/// let current = [Mode::plus(ChannelMode::Ban, Some("*!*@spam")), Mode::plus(ChannelMode::NoExternalMessages, None)];
+/// let desired = [Mode::plus(ChannelMode::NoExternalMessages, None), Mode::plus(ChannelMode::Moderated, None)];
+/// assert_eq!(
+///     diff(&current, &desired),
+///     [Mode::minus(ChannelMode::Ban, Some("*!*@spam")), Mode::plus(ChannelMode::Moderated, None)]
+/// );
+/// ```
+pub fn diff(
+    current: &[Mode<ChannelMode>],
+    desired: &[Mode<ChannelMode>],
+) -> Vec<Mode<ChannelMode>> {
+    let current = set_modes(current);
+    let desired = set_modes(desired);
+    let mut changes = Vec::new();
+
+    for &(mode, arg) in &current {
+        let kept = if is_single_value(mode) {
+            desired.iter().any(|&(m, _)| m == mode)
+        } else {
+            desired.contains(&(mode, arg))
+        };
+        if !kept {
+            let arg = if *mode == ChannelMode::Limit {
+                None
+            } else {
+                arg
+            };
+            changes.push(Mode::minus(mode.clone(), arg));
+        }
+    }
+    for &(mode, arg) in &desired {
+        if !current.contains(&(mode, arg)) {
+            changes.push(Mode::plus(mode.clone(), arg));
+        }
+    }
+    changes
+}
+
+/// The modes a state lists as set, with their arguments.
+fn set_modes(modes: &[Mode<ChannelMode>]) -> Vec<(&ChannelMode, Option<&str>)> {
+    modes
+        .iter()
+        .filter_map(|m| match m {
+            Mode::Plus(mode, arg) => Some((mode, arg.as_deref())),
+            Mode::NoPrefix(mode) => Some((mode, None)),
+            Mode::Minus(..) => None,
+        })
+        .collect()
+}
+
+/// Whether a mode holds a single value that a new `+` replaces.
+fn is_single_value(mode: &ChannelMode) -> bool {
+    matches!(mode, ChannelMode::Key | ChannelMode::Limit)
+}
+
+impl Command {
+    /// Build `MODE` commands applying `modes` to `channel`.
+    ///
+    /// Each command carries at most `max_modes` modes with an argument, as
+    /// advertised by the ISUPPORT `MODES` token; modes without an argument do
+    /// not count towards the limit. A limit of `0` is treated as `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::mode::{ChannelMode, Mode};
+    /// use slirc_proto::Command;
+    ///
+    /// let modes = [Mode::plus(ChannelMode::Voice, Some("alice")), Mode::plus(ChannelMode::Voice, Some("bob"))];
+    /// let commands = Command::modes_for("#rust", &modes, 1);
+    /// assert_eq!(commands.len(), 2);
+    /// ```
+    pub fn modes_for(channel: &str, modes: &[Mode<ChannelMode>], max_modes: usize) -> Vec<Command> {
+        let max_modes = max_modes.max(1);
+        let mut commands = Vec::new();
+        let mut chunk = Vec::new();
+        let mut with_args = 0;
+
+        for mode in modes {
+            if mode.arg().is_some() {
+                if with_args == max_modes {
+                    commands.push(Command::ChannelMODE(
+                        channel.to_string(),
+                        std::mem::take(&mut chunk),
+                    ));
+                    with_args = 0;
+                }
+                with_args += 1;
+            }
+            chunk.push(mode.clone());
+        }
+        if !chunk.is_empty() {
+            commands.push(Command::ChannelMODE(channel.to_string(), chunk));
+        }
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ban(mask: &str) -> Mode<ChannelMode> {
+        Mode::plus(ChannelMode::Ban, Some(mask))
+    }
+
+    #[test]
+    fn test_remove_ban_add_moderated() {
+        let current = [
+            Mode::plus(ChannelMode::NoExternalMessages, None),
+            ban("*!*@spam.example"),
+            ban("*!*@keep.example"),
+        ];
+        let desired = [
+            Mode::no_prefix(ChannelMode::NoExternalMessages),
+            ban("*!*@keep.example"),
+            Mode::plus(ChannelMode::Moderated, None),
+        ];
+        let changes = diff(&current, &desired);
+        assert_eq!(
+            changes,
+            [
+                Mode::minus(ChannelMode::Ban, Some("*!*@spam.example")),
+                Mode::plus(ChannelMode::Moderated, None),
+            ]
+        );
+
+        let commands = Command::modes_for("#rust", &changes, 4);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].to_string(), "MODE #rust -b+m *!*@spam.example");
+    }
+
+    #[test]
+    fn test_single_value_modes() {
+        let current = [
+            Mode::plus(ChannelMode::Key, Some("old")),
+            Mode::plus(ChannelMode::Limit, Some("10")),
+        ];
+
+        // Changed key is replaced; dropped limit is cleared without argument
+        let desired = [Mode::plus(ChannelMode::Key, Some("new"))];
+        assert_eq!(
+            diff(&current, &desired),
+            [
+                Mode::minus(ChannelMode::Limit, None),
+                Mode::plus(ChannelMode::Key, Some("new")),
+            ]
+        );
+
+        // Dropped key is cleared with the current key
+        let desired = [Mode::plus(ChannelMode::Limit, Some("10"))];
+        assert_eq!(
+            diff(&current, &desired),
+            [Mode::minus(ChannelMode::Key, Some("old"))]
+        );
+
+        assert!(diff(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_chunking_by_max_modes() {
+        let changes = [
+            ban("a!*@*"),
+            Mode::plus(ChannelMode::Moderated, None),
+            ban("b!*@*"),
+            Mode::minus(ChannelMode::Voice, Some("carol")),
+            ban("c!*@*"),
+        ];
+        let commands = Command::modes_for("#rust", &changes, 3);
+        let lines: Vec<String> = commands.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            ["MODE #rust +bmb-v a!*@* b!*@* carol", "MODE #rust +b c!*@*",]
+        );
+
+        assert!(Command::modes_for("#rust", &[], 3).is_empty());
+        assert_eq!(Command::modes_for("#rust", &changes, 0).len(), 4);
+    }
+}
@@ -1,6 +1,8 @@
 //! IRC user and channel mode types.
 
+mod diff;
 mod parse;
 mod types;
 
+pub use self::diff::diff;
 pub use self::types::{ChannelMode, Mode, ModeType, UserMode};
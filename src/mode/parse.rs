@@ -3,6 +3,7 @@
 use std::iter::Peekable;
 
 use crate::error::MessageParseError;
+use crate::isupport::ChanModes;
 
 use super::types::{ChannelMode, Mode, ModeType, UserMode};
 
@@ -12,6 +13,29 @@ enum PlusMinus {
     NoPrefix,
 }
 
+/// How a mode character consumes arguments.
+enum ArgRule {
+    /// Never takes an argument.
+    Never,
+    /// Requires an argument.
+    Required,
+    /// Takes an argument if one is available (list mode queries).
+    Optional,
+}
+
+impl ArgRule {
+    /// Classify a mode using its built-in [`ModeType`] knowledge.
+    fn of<T: ModeType>(mode: &T) -> Self {
+        if !mode.takes_arg() {
+            ArgRule::Never
+        } else if mode.is_list_mode() {
+            ArgRule::Optional
+        } else {
+            ArgRule::Required
+        }
+    }
+}
+
 impl Mode<UserMode> {
     /// Parse user mode strings like `+iw` into a vector of modes.
     pub fn as_user_modes(pieces: &[&str]) -> Result<Vec<Mode<UserMode>>, MessageParseError> {
@@ -24,6 +48,46 @@ impl Mode<ChannelMode> {
     pub fn as_channel_modes(pieces: &[&str]) -> Result<Vec<Mode<ChannelMode>>, MessageParseError> {
         parse_modes(pieces)
     }
+
+    /// Parse channel mode strings using the server's `CHANMODES` token to
+    /// decide which modes take arguments.
+    ///
+    /// Type A (list) modes take an argument when one is available, so
+    /// `+b` without a mask is still a list query; type B modes always
+    /// require one and type C modes only when added. Modes missing from
+    /// `CHANMODES`, such as the status modes advertised in `PREFIX`, fall
+    /// back to the built-in classification used by
+    /// [`as_channel_modes`](Self::as_channel_modes).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::isupport::ChanModes;
+    /// use slirc_proto::mode::{ChannelMode, Mode};
+    ///
+    /// let chanmodes = ChanModes::parse("eIbq,k,flj,CFLMPQScgimnprstuz").unwrap();
+    /// let modes = Mode::as_channel_modes_with(&["+f-l", "#overflow"], &chanmodes).unwrap();
+    /// assert_eq!(
+    ///     modes,
+    ///     [
+    ///         Mode::Plus(ChannelMode::Unknown('f'), Some("#overflow".to_string())),
+    ///         Mode::Minus(ChannelMode::Limit, None),
+    ///     ]
+    /// );
+    /// ```
+    pub fn as_channel_modes_with(
+        pieces: &[&str],
+        chanmodes: &ChanModes<'_>,
+    ) -> Result<Vec<Mode<ChannelMode>>, MessageParseError> {
+        parse_modes_with(pieces, |mode: &ChannelMode, c, adding| {
+            match chanmodes.mode_takes_arg(c, adding) {
+                Some(false) => ArgRule::Never,
+                Some(true) if chanmodes.a.contains(c) => ArgRule::Optional,
+                Some(true) => ArgRule::Required,
+                None => ArgRule::of(mode),
+            }
+        })
+    }
 }
 
 /// Resolve the argument for a mode character, if required.
@@ -31,22 +95,21 @@ impl Mode<ChannelMode> {
 /// Returns `Some(arg)` if the mode takes an argument and one is available,
 /// `None` if the mode doesn't take an argument or is a list mode query,
 /// or an error if the mode requires an argument but none was provided.
-fn resolve_mode_arg<'a, T, I>(
-    mode: &T,
+fn resolve_mode_arg<'a, I>(
+    rule: ArgRule,
     mode_char: char,
     args: &mut Peekable<I>,
 ) -> Result<Option<String>, MessageParseError>
 where
-    T: ModeType,
     I: Iterator<Item = &'a str>,
 {
-    if !mode.takes_arg() {
+    if let ArgRule::Never = rule {
         return Ok(None);
     }
 
     match args.next() {
         Some(arg) => Ok(Some(arg.to_string())),
-        None if mode.is_list_mode() => Ok(None), // List mode query (e.g., MODE #channel +b)
+        None if matches!(rule, ArgRule::Optional) => Ok(None), // List mode query (e.g., MODE #channel +b)
         None => Err(MessageParseError::InvalidModeArg(format!(
             "Mode '{}' requires an argument but none provided",
             mode_char
@@ -57,6 +120,16 @@ where
 fn parse_modes<T>(pieces: &[&str]) -> Result<Vec<Mode<T>>, MessageParseError>
 where
     T: ModeType,
+{
+    parse_modes_with(pieces, |mode: &T, _, _| ArgRule::of(mode))
+}
+
+/// Parse a mode string, asking `rule` how each mode (given its character
+/// and whether it is being added) consumes arguments.
+fn parse_modes_with<T, F>(pieces: &[&str], rule: F) -> Result<Vec<Mode<T>>, MessageParseError>
+where
+    T: ModeType,
+    F: Fn(&T, char, bool) -> ArgRule,
 {
     use self::PlusMinus::*;
 
@@ -85,7 +158,8 @@ where
             '-' => cur_mod = Minus,
             _ => {
                 let mode = T::from_char(c);
-                let arg = resolve_mode_arg(&mode, c, &mut args)?;
+                let adding = !matches!(cur_mod, Minus);
+                let arg = resolve_mode_arg(rule(&mode, c, adding), c, &mut args)?;
                 res.push(match cur_mod {
                     Plus => Mode::Plus(mode, arg),
                     Minus => Mode::Minus(mode, arg),
@@ -198,6 +272,47 @@ mod tests {
         assert_eq!(modes[0], Mode::Plus(ChannelMode::Key, Some(" ".to_string())));
     }
 
+    #[test]
+    fn test_chanmodes_limit_arg_only_when_adding() {
+        let chanmodes = ChanModes::parse("eIbq,k,flj,CFLMPQScgimnprstuz").unwrap();
+
+        let modes = Mode::as_channel_modes_with(&["+l-l+o", "25", "alice"], &chanmodes).unwrap();
+        assert_eq!(
+            modes,
+            [
+                Mode::Plus(ChannelMode::Limit, Some("25".to_string())),
+                Mode::Minus(ChannelMode::Limit, None),
+                Mode::Plus(ChannelMode::Oper, Some("alice".to_string())),
+            ]
+        );
+
+        assert!(Mode::as_channel_modes_with(&["+l"], &chanmodes).is_err());
+        assert!(Mode::as_channel_modes_with(&["-l", "25"], &chanmodes).is_err());
+    }
+
+    #[test]
+    fn test_chanmodes_unknown_modes_take_args() {
+        // 'f' and 'j' are unknown to the built-in table but take arguments
+        let chanmodes = ChanModes::parse("eIbq,k,flj,CFLMPQScgimnprstuz").unwrap();
+        let pieces = ["+bj-k+S", "*!*@spam", "3:5", "secret"];
+        assert!(Mode::as_channel_modes(&pieces).is_err());
+
+        let modes = Mode::as_channel_modes_with(&pieces, &chanmodes).unwrap();
+        assert_eq!(
+            modes,
+            [
+                Mode::Plus(ChannelMode::Ban, Some("*!*@spam".to_string())),
+                Mode::Plus(ChannelMode::Unknown('j'), Some("3:5".to_string())),
+                Mode::Minus(ChannelMode::Key, Some("secret".to_string())),
+                Mode::Plus(ChannelMode::Unknown('S'), None),
+            ]
+        );
+
+        // List modes can still be queried without a mask
+        let modes = Mode::as_channel_modes_with(&["+b"], &chanmodes).unwrap();
+        assert_eq!(modes, [Mode::Plus(ChannelMode::Ban, None)]);
+    }
+
     #[test]
     fn test_key_mode_with_empty_arg() {
         // MODE #channel +k "" - empty key should also parse
@@ -8,7 +8,9 @@
 
 use std::str::FromStr;
 
+use crate::casemap::CaseMapping;
 use crate::error::MessageParseError;
+use crate::util::{wildcard_match, wildcard_match_with};
 
 /// IRC message prefix - identifies the origin of a message.
 ///
@@ -125,6 +127,41 @@ impl Prefix {
             _ => None,
         }
     }
+
+    /// Check whether this prefix matches a `nick!user@host` ban mask.
+    ///
+    /// Each part of the mask may use `*` and `?` wildcards. The nick and user
+    /// parts are compared with the RFC 1459 case mapping, the host part with
+    /// ASCII case folding. A mask without `!` or `@` is completed with `*`
+    /// for the missing parts (`nick` is `nick!*@*`, `*@host` is `*!*@host`).
+    ///
+    /// Server name prefixes never match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::Prefix;
+    ///
+    /// let prefix = Prefix::new("Nick", "user", "host.Example.com");
+    /// assert!(prefix.matches_mask("*!*@*.example.com"));
+    /// assert!(!prefix.matches_mask("*!admin@*"));
+    /// ```
+    pub fn matches_mask(&self, mask: &str) -> bool {
+        let Prefix::Nickname(nick, user, host) = self else {
+            return false;
+        };
+
+        let (nick_mask, rest) = match mask.split_once('!') {
+            Some((nick_mask, rest)) => (nick_mask, rest),
+            None if mask.contains('@') => ("*", mask),
+            None => (mask, "*"),
+        };
+        let (user_mask, host_mask) = rest.split_once('@').unwrap_or((rest, "*"));
+
+        wildcard_match(nick_mask, nick)
+            && wildcard_match(user_mask, user)
+            && wildcard_match_with(host_mask, host, CaseMapping::Ascii)
+    }
 }
 
 impl FromStr for Prefix {
@@ -403,4 +440,38 @@ mod tests {
         assert_eq!(s.nick(), None);
         assert_eq!(s.host(), Some("irc.test.com"));
     }
+
+    #[test]
+    fn test_matches_mask_wildcard_all() {
+        let prefix = Prefix::new("nick", "user", "host.example.com");
+        assert!(prefix.matches_mask("*!*@*"));
+        assert!(prefix.matches_mask("*"));
+        assert!(Prefix::new_from_str("nick").matches_mask("*!*@*"));
+        assert!(!Prefix::ServerName("irc.example.com".into()).matches_mask("*!*@*"));
+    }
+
+    #[test]
+    fn test_matches_mask_exact() {
+        let prefix = Prefix::new("Nick[a]", "User", "Host.Example.COM");
+        assert!(prefix.matches_mask("nick{a}!user@host.example.com"));
+        assert!(prefix.matches_mask("*!*@*.example.com"));
+        assert!(prefix.matches_mask("Nick[a]"));
+        assert!(prefix.matches_mask("*@host.example.com"));
+        assert!(!prefix.matches_mask("nick{a}!user@other.example.com"));
+        assert!(!prefix.matches_mask("*!nobody@*"));
+
+        // Host comparison folds ASCII case only
+        let prefix = Prefix::new("nick", "user", "host[1]");
+        assert!(!prefix.matches_mask("*!*@host{1}"));
+        assert!(prefix.matches_mask("*!*@HOST[1]"));
+    }
+
+    #[test]
+    fn test_matches_mask_single_char() {
+        let prefix = Prefix::new("bot1", "ident", "10.0.0.5");
+        assert!(prefix.matches_mask("bot?!?dent@10.0.0.?"));
+        assert!(prefix.matches_mask("b??1!*@*"));
+        assert!(!prefix.matches_mask("bot??!*@*"));
+        assert!(!prefix.matches_mask("bot?!*@10.0.0.??"));
+    }
 }
@@ -0,0 +1,204 @@
+//! Parsing of channel information numerics.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::isupport::ChanModes;
+use crate::mode::{ChannelMode, Mode, ModeType};
+
+/// Who set a channel topic and when, from `RPL_TOPICWHOTIME` (333).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TopicWhoTime<'a> {
+    /// The channel name.
+    pub channel: &'a str,
+    /// Nickname or hostmask of whoever set the topic.
+    pub setter: &'a str,
+    /// When the topic was set.
+    pub set_at: SystemTime,
+}
+
+/// Parse a unix timestamp (seconds) as sent in numeric replies.
+pub(super) fn parse_unix_time(s: &str) -> Option<SystemTime> {
+    s.parse()
+        .ok()
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Parse the arguments of an `RPL_CREATIONTIME` (329) reply.
+///
+/// The reply has the form `<client> <channel> <creationtime>`; returns the
+/// channel and its creation time.
+///
+/// # Example
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use slirc_proto::response::parse_creation_time;
+///
+/// let args = ["me", "#rust", "1700000000"];
+/// let (channel, created) = parse_creation_time(&args).unwrap();
+/// assert_eq!(channel, "#rust");
+/// assert_eq!(created, UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+/// ```
+pub fn parse_creation_time<S: AsRef<str>>(args: &[S]) -> Option<(&str, SystemTime)> {
+    match args {
+        [_, channel, time, ..] => Some((channel.as_ref(), parse_unix_time(time.as_ref())?)),
+        _ => None,
+    }
+}
+
+/// Parse the arguments of an `RPL_TOPICWHOTIME` (333) reply.
+///
+/// The reply has the form `<client> <channel> <setter> <setat>`.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::response::parse_topic_who_time;
+///
+/// let args = ["me", "#rust", "alice!a@host", "1700000000"];
+/// let info = parse_topic_who_time(&args).unwrap();
+/// assert_eq!(info.channel, "#rust");
+/// assert_eq!(info.setter, "alice!a@host");
+/// ```
+pub fn parse_topic_who_time<S: AsRef<str>>(args: &[S]) -> Option<TopicWhoTime<'_>> {
+    match args {
+        [_, channel, setter, time, ..] => Some(TopicWhoTime {
+            channel: channel.as_ref(),
+            setter: setter.as_ref(),
+            set_at: parse_unix_time(time.as_ref())?,
+        }),
+        _ => None,
+    }
+}
+
+/// Parse the arguments of an `RPL_CHANNELMODEIS` (324) reply.
+///
+/// The reply has the form `<client> <channel> <modestring> [<params>...]`.
+/// Parameters are attached using the server's `CHANMODES` token: modes of
+/// types A, B and C take one, type D and unlisted modes take none. Servers
+/// often hide the key from non-members, so a mode whose parameter is missing
+/// gets `None`.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::isupport::ChanModes;
+/// use slirc_proto::mode::{ChannelMode, Mode};
+/// use slirc_proto::response::parse_channel_mode_is;
+///
+/// let chanmodes = ChanModes::parse("beI,k,l,imnpst").unwrap();
+/// let args = ["me", "#rust", "+nl", "50"];
+/// let (channel, modes) = parse_channel_mode_is(&args, &chanmodes).unwrap();
+/// assert_eq!(channel, "#rust");
+/// assert_eq!(modes[1], Mode::Plus(ChannelMode::Limit, Some("50".to_string())));
+/// ```
+pub fn parse_channel_mode_is<'a, S: AsRef<str>>(
+    args: &'a [S],
+    chanmodes: &ChanModes<'_>,
+) -> Option<(&'a str, Vec<Mode<ChannelMode>>)> {
+    let [_, channel, modestring, params @ ..] = args else {
+        return None;
+    };
+
+    let takes_param =
+        |c: char| chanmodes.a.contains(c) || chanmodes.b.contains(c) || chanmodes.c.contains(c);
+    let mut params = params.iter().map(|p| p.as_ref().to_string());
+    let mut plus = true;
+    let mut modes = Vec::new();
+    for c in modestring.as_ref().chars() {
+        match c {
+            '+' => plus = true,
+            '-' => plus = false,
+            _ => {
+                let arg = if takes_param(c) { params.next() } else { None };
+                let mode = ChannelMode::from_char(c);
+                modes.push(if plus {
+                    Mode::Plus(mode, arg)
+                } else {
+                    Mode::Minus(mode, arg)
+                });
+            }
+        }
+    }
+    Some((channel.as_ref(), modes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::message::Message;
+    use crate::response::Response;
+
+    #[test]
+    fn test_parse_creation_time() {
+        let msg: Message = ":irc.test 329 me #rust 1600000000".parse().unwrap();
+        let Command::Response(Response::RPL_CREATIONTIME, args) = msg.command else {
+            panic!("Expected RPL_CREATIONTIME");
+        };
+        assert_eq!(
+            parse_creation_time(&args),
+            Some(("#rust", UNIX_EPOCH + Duration::from_secs(1_600_000_000)))
+        );
+
+        assert_eq!(parse_creation_time(&["me", "#rust"]), None);
+        assert_eq!(parse_creation_time(&["me", "#rust", "yesterday"]), None);
+    }
+
+    #[test]
+    fn test_parse_topic_who_time() {
+        let msg: Message = ":irc.test 333 me #rust alice!a@host 1700000000"
+            .parse()
+            .unwrap();
+        let Command::Response(Response::RPL_TOPICWHOTIME, args) = msg.command else {
+            panic!("Expected RPL_TOPICWHOTIME");
+        };
+        assert_eq!(
+            parse_topic_who_time(&args),
+            Some(TopicWhoTime {
+                channel: "#rust",
+                setter: "alice!a@host",
+                set_at: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            })
+        );
+
+        assert_eq!(parse_topic_who_time(&["me", "#rust", "alice"]), None);
+        assert_eq!(parse_topic_who_time(&["me", "#rust", "alice", "-5"]), None);
+    }
+
+    #[test]
+    fn test_parse_channel_mode_is() {
+        let msg: Message = ":irc.test 324 me #c +ntkl key 50".parse().unwrap();
+        let Command::Response(Response::RPL_CHANNELMODEIS, args) = msg.command else {
+            panic!("Expected RPL_CHANNELMODEIS");
+        };
+        let chanmodes = ChanModes::parse("beI,k,l,imnpst").unwrap();
+        let (channel, modes) = parse_channel_mode_is(&args, &chanmodes).unwrap();
+        assert_eq!(channel, "#c");
+        assert_eq!(
+            modes,
+            [
+                Mode::Plus(ChannelMode::NoExternalMessages, None),
+                Mode::Plus(ChannelMode::ProtectedTopic, None),
+                Mode::Plus(ChannelMode::Key, Some("key".to_string())),
+                Mode::Plus(ChannelMode::Limit, Some("50".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_channel_mode_is_hidden_key() {
+        // Parameters follow CHANMODES, not the built-in mode table
+        let chanmodes = ChanModes::parse("b,k,lf,imnst").unwrap();
+        let (_, modes) = parse_channel_mode_is(&["me", "#c", "+fk", "10:5"], &chanmodes).unwrap();
+        assert_eq!(
+            modes,
+            [
+                Mode::Plus(ChannelMode::Unknown('f'), Some("10:5".to_string())),
+                Mode::Plus(ChannelMode::Key, None),
+            ]
+        );
+
+        assert_eq!(parse_channel_mode_is(&["me", "#c"], &chanmodes), None);
+    }
+}
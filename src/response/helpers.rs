@@ -1,7 +1,7 @@
 //! Helper methods and trait implementations for IRC response codes.
 //!
 //! This module provides utility methods for Response enum including:
-//! - Code conversion (from_code, code)
+//! - Code conversion (code)
 //! - Type checking (is_error, is_reply, etc.)
 //! - Category classification
 //! - Display/parsing traits
@@ -16,19 +16,6 @@ impl Response {
         *self as u16
     }
 
-    /// Creates a Response from a numeric code
-    pub fn from_code(code: u16) -> Option<Response> {
-        // Try success codes first
-        if let Some(resp) = Response::from_success_code(code) {
-            return Some(resp);
-        }
-        // Try error codes
-        if let Some(resp) = Response::from_error_code(code) {
-            return Some(resp);
-        }
-        None
-    }
-
     /// Check if this is an error response (4xx, 5xx, or specific error codes)
     #[inline]
     pub fn is_error(&self) -> bool {
@@ -130,6 +117,103 @@ impl Response {
         )
     }
 
+    /// Check if this is part of a WHO or WHOX reply (352, 354 or 315)
+    #[inline]
+    pub fn is_who_reply(&self) -> bool {
+        matches!(
+            self,
+            Response::RPL_WHOREPLY | Response::RPL_WHOSPCRPL | Response::RPL_ENDOFWHO
+        )
+    }
+
+    /// Check if this is a `draft/metadata` response (760-761, 765-769)
+    #[inline]
+    pub fn is_metadata_related(&self) -> bool {
+        let code = self.code();
+        (760..=761).contains(&code) || (765..=769).contains(&code)
+    }
+
+    /// Check if this numeric ends a multi-reply sequence (e.g. `RPL_ENDOFWHO`)
+    #[inline]
+    pub fn is_terminator(&self) -> bool {
+        matches!(
+            self,
+            Response::RPL_ENDOFSTATS
+                | Response::RPL_SERVLISTEND
+                | Response::RPL_TRACEEND
+                | Response::RPL_ENDOFSILELIST
+                | Response::RPL_ENDOFACCEPT
+                | Response::RPL_ENDOFWHO
+                | Response::RPL_ENDOFWHOIS
+                | Response::RPL_LISTEND
+                | Response::RPL_ENDOFINVITELIST
+                | Response::RPL_ENDOFEXCEPTLIST
+                | Response::RPL_ENDOFLINKS
+                | Response::RPL_ENDOFNAMES
+                | Response::RPL_ENDOFBANLIST
+                | Response::RPL_ENDOFWHOWAS
+                | Response::RPL_ENDOFINFO
+                | Response::RPL_ENDOFMOTD
+                | Response::RPL_ENDOFUSERS
+                | Response::RPL_MAPEND
+                | Response::RPL_ENDOFRULES
+                | Response::RPL_ENDOFHELP
+                | Response::RPL_ENDOFQUIETLIST
+                | Response::RPL_ENDOFMONLIST
+        )
+    }
+
+    /// Returns the numeric that ends the sequence `start` belongs to
+    ///
+    /// `start` may be the opening numeric of a sequence (e.g. `RPL_MOTDSTART`)
+    /// or any reply inside it (e.g. `RPL_WHOREPLY`). Returns `None` for
+    /// terminators themselves and for numerics not part of a sequence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::Response;
+    ///
+    /// let end = Response::terminator_for(Response::RPL_NAMREPLY);
+    /// assert_eq!(end, Some(Response::RPL_ENDOFNAMES));
+    /// assert!(end.unwrap().is_terminator());
+    /// ```
+    pub fn terminator_for(start: Response) -> Option<Response> {
+        use Response::*;
+
+        let end = match start {
+            RPL_TRACELINK | RPL_TRACECONNECTING | RPL_TRACEHANDSHAKE | RPL_TRACEUNKNOWN
+            | RPL_TRACEOPERATOR | RPL_TRACEUSER | RPL_TRACESERVER | RPL_TRACESERVICE
+            | RPL_TRACENEWTYPE | RPL_TRACECLASS | RPL_TRACERECONNECT | RPL_TRACELOG => RPL_TRACEEND,
+            RPL_STATSLINKINFO | RPL_STATSCOMMANDS | RPL_STATSKLINE | RPL_STATSDLINE
+            | RPL_STATSSHUN | RPL_STATSUPTIME | RPL_STATSOLINE | RPL_STATSDEBUG
+            | RPL_STATSPLINE => RPL_ENDOFSTATS,
+            RPL_SERVLIST => RPL_SERVLISTEND,
+            RPL_SILELIST => RPL_ENDOFSILELIST,
+            RPL_ACCEPTLIST => RPL_ENDOFACCEPT,
+            RPL_WHOREPLY | RPL_WHOSPCRPL => RPL_ENDOFWHO,
+            RPL_WHOWASUSER => RPL_ENDOFWHOWAS,
+            RPL_LISTSTART | RPL_LIST => RPL_LISTEND,
+            RPL_INVITELIST => RPL_ENDOFINVITELIST,
+            RPL_EXCEPTLIST => RPL_ENDOFEXCEPTLIST,
+            RPL_LINKS => RPL_ENDOFLINKS,
+            RPL_NAMREPLY => RPL_ENDOFNAMES,
+            RPL_BANLIST => RPL_ENDOFBANLIST,
+            RPL_INFO => RPL_ENDOFINFO,
+            RPL_MOTDSTART | RPL_MOTD => RPL_ENDOFMOTD,
+            RPL_USERSSTART | RPL_USERS | RPL_NOUSERS => RPL_ENDOFUSERS,
+            RPL_MAP => RPL_MAPEND,
+            RPL_RULESTART | RPL_RULES => RPL_ENDOFRULES,
+            RPL_HELPSTART | RPL_HELPTXT => RPL_ENDOFHELP,
+            RPL_QUIETLIST => RPL_ENDOFQUIETLIST,
+            RPL_MONLIST => RPL_ENDOFMONLIST,
+            _ if start.is_terminator() => return None,
+            _ if start.is_whois_related() => RPL_ENDOFWHOIS,
+            _ => return None,
+        };
+        Some(end)
+    }
+
     /// Returns the RFC 2812 category name for this response
     pub fn category(&self) -> &'static str {
         let code = self.code();
@@ -170,6 +254,8 @@ pub enum ParseResponseError {
     InvalidFormat,
     /// The numeric code is not a known response
     UnknownCode(u16),
+    /// The numeric's arguments did not match the expected layout
+    InvalidArgs,
 }
 
 impl std::fmt::Display for ParseResponseError {
@@ -177,6 +263,7 @@ impl std::fmt::Display for ParseResponseError {
         match self {
             Self::InvalidFormat => write!(f, "invalid response code format"),
             Self::UnknownCode(code) => write!(f, "unknown response code: {}", code),
+            Self::InvalidArgs => write!(f, "malformed response arguments"),
         }
     }
 }
@@ -457,6 +544,91 @@ mod tests {
         assert!(!Response::RPL_NAMREPLY.is_whois_related());
     }
 
+    // ============================================================
+    // is_who_reply() tests
+    // ============================================================
+
+    #[test]
+    fn is_who_reply_covers_who_and_whox() {
+        assert!(Response::RPL_WHOREPLY.is_who_reply()); // 352
+        assert!(Response::RPL_WHOSPCRPL.is_who_reply()); // 354
+        assert!(Response::RPL_ENDOFWHO.is_who_reply()); // 315
+        assert!(!Response::RPL_WHOISUSER.is_who_reply());
+        assert!(!Response::RPL_NAMREPLY.is_who_reply());
+    }
+
+    // ============================================================
+    // is_metadata_related() tests
+    // ============================================================
+
+    #[test]
+    fn is_metadata_related_true_for_metadata_numerics() {
+        assert!(Response::RPL_WHOISKEYVALUE.is_metadata_related()); // 760
+        assert!(Response::RPL_KEYVALUE.is_metadata_related()); // 761
+        assert!(Response::ERR_TARGETINVALID.is_metadata_related()); // 765
+        assert!(Response::ERR_NOMATCHINGKEY.is_metadata_related()); // 766
+        assert!(Response::ERR_KEYINVALID.is_metadata_related()); // 767
+        assert!(Response::ERR_KEYNOTSET.is_metadata_related()); // 768
+        assert!(Response::ERR_KEYNOPERMISSION.is_metadata_related()); // 769
+    }
+
+    #[test]
+    fn is_metadata_related_false_for_other_responses() {
+        assert!(!Response::RPL_WELCOME.is_metadata_related());
+        assert!(!Response::RPL_WHOISUSER.is_metadata_related());
+        assert!(!Response::ERR_NOPRIVS.is_metadata_related());
+    }
+
+    // ============================================================
+    // is_terminator() / terminator_for() tests
+    // ============================================================
+
+    #[test]
+    fn terminator_for_maps_starts_to_ends() {
+        let pairs = [
+            (Response::RPL_WHOREPLY, Response::RPL_ENDOFWHO),
+            (Response::RPL_WHOSPCRPL, Response::RPL_ENDOFWHO),
+            (Response::RPL_WHOISUSER, Response::RPL_ENDOFWHOIS),
+            (Response::RPL_WHOISACCOUNT, Response::RPL_ENDOFWHOIS),
+            (Response::RPL_WHOWASUSER, Response::RPL_ENDOFWHOWAS),
+            (Response::RPL_LISTSTART, Response::RPL_LISTEND),
+            (Response::RPL_NAMREPLY, Response::RPL_ENDOFNAMES),
+            (Response::RPL_BANLIST, Response::RPL_ENDOFBANLIST),
+            (Response::RPL_INVITELIST, Response::RPL_ENDOFINVITELIST),
+            (Response::RPL_EXCEPTLIST, Response::RPL_ENDOFEXCEPTLIST),
+            (Response::RPL_MOTDSTART, Response::RPL_ENDOFMOTD),
+            (Response::RPL_MOTD, Response::RPL_ENDOFMOTD),
+            (Response::RPL_USERSSTART, Response::RPL_ENDOFUSERS),
+            (Response::RPL_MONLIST, Response::RPL_ENDOFMONLIST),
+        ];
+        for (start, end) in pairs {
+            assert_eq!(Response::terminator_for(start), Some(end), "{:?}", start);
+            assert!(end.is_terminator());
+            assert!(!start.is_terminator());
+        }
+    }
+
+    #[test]
+    fn terminator_for_none_outside_sequences() {
+        assert_eq!(Response::terminator_for(Response::RPL_WELCOME), None);
+        assert_eq!(Response::terminator_for(Response::RPL_TOPIC), None);
+        assert_eq!(Response::terminator_for(Response::ERR_NOSUCHNICK), None);
+        assert_eq!(Response::terminator_for(Response::RPL_ENDOFWHOIS), None);
+        assert_eq!(Response::terminator_for(Response::RPL_ENDOFNAMES), None);
+    }
+
+    #[test]
+    fn terminator_for_always_yields_terminator() {
+        for r in Response::all() {
+            if let Some(end) = Response::terminator_for(r) {
+                assert!(end.is_terminator(), "{:?} -> {:?}", r, end);
+            }
+            if r.is_terminator() {
+                assert_eq!(Response::terminator_for(r), None, "{:?}", r);
+            }
+        }
+    }
+
     // ============================================================
     // category() tests
     // ============================================================
@@ -574,6 +746,10 @@ mod tests {
             ParseResponseError::UnknownCode(9999).to_string(),
             "unknown response code: 9999"
         );
+        assert_eq!(
+            ParseResponseError::InvalidArgs.to_string(),
+            "malformed response arguments"
+        );
     }
 
     #[test]
@@ -0,0 +1,309 @@
+//! Reassembly of channel list replies.
+//!
+//! Servers answer list queries like `MODE #chan +b` with a stream of entry
+//! numerics followed by a terminator. The collectors here gather the
+//! entries per channel and hand them back once the terminator arrives.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use crate::command::Command;
+use crate::message::Message;
+use crate::response::Response;
+
+use super::channel_info::parse_unix_time;
+
+/// Parse the arguments of an `RPL_INVITING` (341) reply.
+///
+/// The reply has the form `<client> <nick> <channel>`; returns the invited
+/// nickname and the channel.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::response::parse_inviting;
+///
+/// let args = ["me", "alice", "#rust"];
+/// assert_eq!(parse_inviting(&args), Some(("alice", "#rust")));
+/// ```
+pub fn parse_inviting<S: AsRef<str>>(args: &[S]) -> Option<(&str, &str)> {
+    match args {
+        [_, nick, channel, ..] => Some((nick.as_ref(), channel.as_ref())),
+        _ => None,
+    }
+}
+
+/// An entry in a channel list mode (ban, exception, invite exception, quiet).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListEntry {
+    /// The hostmask on the list.
+    pub mask: String,
+    /// Who added the entry, if the server reported it.
+    pub set_by: Option<String>,
+    /// When the entry was added, if the server reported it.
+    pub set_at: Option<SystemTime>,
+}
+
+/// Collects a channel list mode reply stream into [`ListEntry`] values.
+///
+/// The collector is parameterized by its entry/terminator numeric pair.
+/// Feed every incoming message; once the terminator for a channel arrives,
+/// the channel and its entries are returned.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::response::ListModeCollector;
+/// use slirc_proto::Message;
+///
+/// let mut bans = ListModeCollector::bans();
+/// for line in [
+///     ":irc.test 367 me #rust *!*@spam.host op 1700000000",
+///     ":irc.test 368 me #rust :End of Channel Ban List",
+/// ] {
+///     let msg: Message = line.parse().unwrap();
+///     if let Some((channel, entries)) = bans.feed(&msg) {
+///         assert_eq!(channel, "#rust");
+///         assert_eq!(entries[0].mask, "*!*@spam.host");
+///         assert_eq!(entries[0].set_by.as_deref(), Some("op"));
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ListModeCollector {
+    entry: Response,
+    end: Response,
+    pending: HashMap<String, Vec<ListEntry>>,
+}
+
+impl ListModeCollector {
+    /// Create a collector for an arbitrary entry/terminator numeric pair.
+    #[must_use]
+    pub fn new(entry: Response, end: Response) -> Self {
+        Self {
+            entry,
+            end,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Ban list: `RPL_BANLIST` (367) / `RPL_ENDOFBANLIST` (368).
+    #[must_use]
+    pub fn bans() -> Self {
+        Self::new(Response::RPL_BANLIST, Response::RPL_ENDOFBANLIST)
+    }
+
+    /// Ban exception list: `RPL_EXCEPTLIST` (348) / `RPL_ENDOFEXCEPTLIST` (349).
+    #[must_use]
+    pub fn excepts() -> Self {
+        Self::new(Response::RPL_EXCEPTLIST, Response::RPL_ENDOFEXCEPTLIST)
+    }
+
+    /// Invite exception list: `RPL_INVITELIST` (346) / `RPL_ENDOFINVITELIST` (347).
+    #[must_use]
+    pub fn invites() -> Self {
+        Self::new(Response::RPL_INVITELIST, Response::RPL_ENDOFINVITELIST)
+    }
+
+    /// Quiet list: `RPL_QUIETLIST` (728) / `RPL_ENDOFQUIETLIST` (729).
+    #[must_use]
+    pub fn quiets() -> Self {
+        Self::new(Response::RPL_QUIETLIST, Response::RPL_ENDOFQUIETLIST)
+    }
+
+    /// Feed a message, returning the completed list on its terminator.
+    ///
+    /// Messages other than this collector's numerics are ignored.
+    pub fn feed(&mut self, msg: &Message) -> Option<(String, Vec<ListEntry>)> {
+        let Command::Response(response, ref args) = msg.command else {
+            return None;
+        };
+
+        if response == self.entry {
+            let channel = args.get(1)?;
+            // The quiet list carries the mode character before the mask
+            let rest = match response {
+                Response::RPL_QUIETLIST => args.get(3..)?,
+                _ => args.get(2..)?,
+            };
+            let (mask, set_by, set_at) = match rest {
+                [mask, set_by, set_at, ..] => (mask, Some(set_by.clone()), parse_unix_time(set_at)),
+                [mask, set_by] => (mask, Some(set_by.clone()), None),
+                [mask] => (mask, None, None),
+                [] => return None,
+            };
+            self.pending
+                .entry(channel.clone())
+                .or_default()
+                .push(ListEntry {
+                    mask: mask.clone(),
+                    set_by,
+                    set_at,
+                });
+            None
+        } else if response == self.end {
+            let channel = args.get(1)?;
+            let entries = self.pending.remove(channel).unwrap_or_default();
+            Some((channel.clone(), entries))
+        } else {
+            None
+        }
+    }
+}
+
+/// Collects an invite-exception list (`RPL_INVITELIST` / `RPL_ENDOFINVITELIST`).
+///
+/// A convenience over [`ListModeCollector::invites`] that yields only the
+/// invited masks.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::response::InviteListCollector;
+/// use slirc_proto::Message;
+///
+/// let mut collector = InviteListCollector::new();
+/// for line in [
+///     ":irc.test 346 me #rust *!*@trusted.host",
+///     ":irc.test 347 me #rust :End of Channel Invite List",
+/// ] {
+///     let msg: Message = line.parse().unwrap();
+///     if let Some((channel, masks)) = collector.feed(&msg) {
+///         assert_eq!(channel, "#rust");
+///         assert_eq!(masks, vec!["*!*@trusted.host"]);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct InviteListCollector {
+    inner: ListModeCollector,
+}
+
+impl Default for InviteListCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InviteListCollector {
+    /// Create an empty collector.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: ListModeCollector::invites(),
+        }
+    }
+
+    /// Feed a message, returning the completed list on its terminator.
+    ///
+    /// Messages other than 346/347 are ignored.
+    pub fn feed(&mut self, msg: &Message) -> Option<(String, Vec<String>)> {
+        let (channel, entries) = self.inner.feed(msg)?;
+        Some((channel, entries.into_iter().map(|e| e.mask).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn msg(raw: &str) -> Message {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_inviting() {
+        let args = vec!["me".to_string(), "bob".to_string(), "#chan".to_string()];
+        assert_eq!(parse_inviting(&args), Some(("bob", "#chan")));
+        assert_eq!(parse_inviting(&["me", "bob"]), None);
+    }
+
+    #[test]
+    fn test_invite_list_sequence() {
+        let mut collector = InviteListCollector::new();
+        assert!(collector
+            .feed(&msg(":irc.test 346 me #rust *!*@a.host"))
+            .is_none());
+        assert!(collector
+            .feed(&msg(":irc.test 346 me #other *!*@other"))
+            .is_none());
+        assert!(collector
+            .feed(&msg(":irc.test 346 me #rust *!*@b.host op 1700000000"))
+            .is_none());
+        assert!(collector.feed(&msg(":nick PRIVMSG #rust :hi")).is_none());
+
+        let (channel, masks) = collector
+            .feed(&msg(":irc.test 347 me #rust :End of Channel Invite List"))
+            .unwrap();
+        assert_eq!(channel, "#rust");
+        assert_eq!(masks, vec!["*!*@a.host", "*!*@b.host"]);
+    }
+
+    #[test]
+    fn test_invite_list_empty() {
+        let mut collector = InviteListCollector::new();
+        let (channel, masks) = collector
+            .feed(&msg(":irc.test 347 me #empty :End of Channel Invite List"))
+            .unwrap();
+        assert_eq!(channel, "#empty");
+        assert!(masks.is_empty());
+    }
+
+    #[test]
+    fn test_ban_list_sequence() {
+        let mut bans = ListModeCollector::bans();
+        assert!(bans
+            .feed(&msg(
+                ":irc.test 367 me #rust *!*@spam.host op!u@h 1700000000"
+            ))
+            .is_none());
+        assert!(bans.feed(&msg(":irc.test 367 me #rust bad!*@*")).is_none());
+        // Other list numerics are ignored
+        assert!(bans.feed(&msg(":irc.test 348 me #rust *!*@ex")).is_none());
+        assert!(bans
+            .feed(&msg(
+                ":irc.test 349 me #rust :End of Channel Exception List"
+            ))
+            .is_none());
+
+        let (channel, entries) = bans
+            .feed(&msg(":irc.test 368 me #rust :End of Channel Ban List"))
+            .unwrap();
+        assert_eq!(channel, "#rust");
+        assert_eq!(
+            entries,
+            vec![
+                ListEntry {
+                    mask: "*!*@spam.host".to_string(),
+                    set_by: Some("op!u@h".to_string()),
+                    set_at: Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+                },
+                ListEntry {
+                    mask: "bad!*@*".to_string(),
+                    set_by: None,
+                    set_at: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quiet_list_sequence() {
+        let mut quiets = ListModeCollector::quiets();
+        assert!(quiets
+            .feed(&msg(":irc.test 728 me #rust q *!*@loud.host op 1600000000"))
+            .is_none());
+        let (channel, entries) = quiets
+            .feed(&msg(":irc.test 729 me #rust q :End of Channel Quiet List"))
+            .unwrap();
+        assert_eq!(channel, "#rust");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].mask, "*!*@loud.host");
+        assert_eq!(entries[0].set_by.as_deref(), Some("op"));
+        assert_eq!(
+            entries[0].set_at,
+            Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000))
+        );
+    }
+}
@@ -10,14 +10,29 @@
 
 #![allow(non_camel_case_types)]
 
+mod channel_info;
 mod constructors;
-mod errors;
 mod helpers;
+mod lists;
+mod names;
 mod numerics;
+pub(crate) mod umode;
+mod who;
+mod whowas;
+mod whox;
 
 // Re-export error type
 pub use helpers::ParseResponseError;
 
+pub use channel_info::{
+    parse_channel_mode_is, parse_creation_time, parse_topic_who_time, TopicWhoTime,
+};
+pub use lists::{parse_inviting, InviteListCollector, ListEntry, ListModeCollector};
+pub use umode::parse_umode_is;
+pub use who::{parse_who_reply, WhoCollector, WhoEntry, WhoReply};
+pub use whowas::{WhowasCollector, WhowasEntry};
+pub use whox::WhoxReply;
+
 /// IRC server response code.
 ///
 /// Response codes are categorized as:
@@ -540,6 +555,44 @@ mod tests {
         assert_eq!(Response::from_code(9999), None);
     }
 
+    #[test]
+    fn test_name_roundtrip() {
+        assert_eq!(Response::RPL_WELCOME.name(), "RPL_WELCOME");
+        assert_eq!(Response::ERR_NICKNAMEINUSE.name(), "ERR_NICKNAMEINUSE");
+        assert_eq!(
+            Response::from_name("RPL_ENDOFMOTD"),
+            Some(Response::RPL_ENDOFMOTD)
+        );
+        assert_eq!(Response::from_name("rpl_welcome"), None);
+        assert_eq!(Response::from_name("001"), None);
+
+        for resp in Response::all() {
+            assert!(!resp.name().is_empty());
+            assert_eq!(Response::from_name(resp.name()), Some(resp));
+            assert_eq!(format!("{:?}", resp), resp.name());
+        }
+    }
+
+    #[test]
+    fn test_all_is_sorted_and_consistent() {
+        // Every variant is listed, which the table's exhaustive match checks
+        // at compile time; here check the order and the derived lookups.
+        let codes: Vec<u16> = Response::all().map(|r| r.code()).collect();
+        assert!(
+            codes.windows(2).all(|w| w[0] < w[1]),
+            "not in ascending order"
+        );
+
+        for resp in Response::all() {
+            assert_eq!(Response::from_code(resp.code()), Some(resp));
+            assert_eq!(format!("{:?}", resp), resp.name());
+        }
+        assert_eq!(
+            (0..1000).filter_map(Response::from_code).count(),
+            codes.len()
+        );
+    }
+
     #[test]
     fn test_is_error() {
         assert!(!Response::RPL_WELCOME.is_error());
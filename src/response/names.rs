@@ -0,0 +1,36 @@
+//! Symbolic names of IRC response codes.
+//!
+//! This module maps each [`Response`] variant to and from its canonical
+//! `RPL_*`/`ERR_*` name, using the names stored in the response table.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::numerics::ALL;
+use super::Response;
+
+impl Response {
+    /// Returns the canonical symbolic name of this response (e.g. `RPL_WELCOME`).
+    ///
+    /// The name matches the enum variant name.
+    pub fn name(&self) -> &'static str {
+        let code = self.code();
+        ALL.binary_search_by_key(&code, |(response, _)| response.code())
+            .map_or("", |i| ALL[i].1)
+    }
+
+    /// Creates a Response from its canonical symbolic name (e.g. `ERR_NICKNAMEINUSE`).
+    ///
+    /// Names are matched exactly, as returned by [`name`](Self::name).
+    pub fn from_name(name: &str) -> Option<Response> {
+        static BY_NAME: OnceLock<HashMap<&'static str, Response>> = OnceLock::new();
+        BY_NAME
+            .get_or_init(|| {
+                ALL.iter()
+                    .map(|&(response, name)| (name, response))
+                    .collect()
+            })
+            .get(name)
+            .copied()
+    }
+}
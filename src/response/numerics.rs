@@ -1,154 +1,268 @@
-//! IRC success response code definitions (RPL_* numerics).
+//! Table of all known IRC response codes.
 //!
-//! This module contains the enum variants for non-error IRC server responses,
-//! organized by category.
-
-// This file is auto-generated from response/mod.rs refactoring.
-// All RPL_* (success/reply) numeric codes.
+//! `ALL` lists every [`Response`] variant with its symbolic name, in
+//! ascending code order. Code lookup, name lookup and enumeration are all
+//! derived from it, so adding a variant only requires adding it here.
 
 use super::Response;
 
+/// Build [`ALL`] from the variant names, listed in ascending code order.
+///
+/// The generated exhaustive match makes a variant missing from the list a
+/// compile error, and a duplicate an unreachable pattern.
+macro_rules! response_table {
+    ($($name:ident),* $(,)?) => {
+        /// Every [`Response`] variant with its symbolic name, sorted by numeric code.
+        pub(super) static ALL: &[(Response, &str)] = &[$((Response::$name, stringify!($name))),*];
+
+        #[allow(dead_code)]
+        fn assert_listed(response: Response) {
+            match response {
+                $(Response::$name)|* => {}
+            }
+        }
+    };
+}
+
+response_table! {
+    RPL_WELCOME,
+    RPL_YOURHOST,
+    RPL_CREATED,
+    RPL_MYINFO,
+    RPL_ISUPPORT,
+    RPL_BOUNCE,
+    RPL_YOURID,
+    RPL_TRACELINK,
+    RPL_TRACECONNECTING,
+    RPL_TRACEHANDSHAKE,
+    RPL_TRACEUNKNOWN,
+    RPL_TRACEOPERATOR,
+    RPL_TRACEUSER,
+    RPL_TRACESERVER,
+    RPL_TRACESERVICE,
+    RPL_TRACENEWTYPE,
+    RPL_TRACECLASS,
+    RPL_TRACERECONNECT,
+    RPL_STATSLINKINFO,
+    RPL_STATSCOMMANDS,
+    RPL_STATSKLINE,
+    RPL_ENDOFSTATS,
+    RPL_STATSDLINE,
+    RPL_UMODEIS,
+    RPL_STATSSHUN,
+    RPL_SERVLIST,
+    RPL_SERVLISTEND,
+    RPL_STATSUPTIME,
+    RPL_STATSOLINE,
+    RPL_STATSDEBUG,
+    RPL_LUSERCLIENT,
+    RPL_LUSEROP,
+    RPL_LUSERUNKNOWN,
+    RPL_LUSERCHANNELS,
+    RPL_LUSERME,
+    RPL_ADMINME,
+    RPL_ADMINLOC1,
+    RPL_ADMINLOC2,
+    RPL_ADMINEMAIL,
+    RPL_TRACELOG,
+    RPL_TRACEEND,
+    RPL_TRYAGAIN,
+    RPL_LOCALUSERS,
+    RPL_GLOBALUSERS,
+    RPL_SILELIST,
+    RPL_ENDOFSILELIST,
+    RPL_WHOISCERTFP,
+    RPL_ACCEPTLIST,
+    RPL_ENDOFACCEPT,
+    RPL_NONE,
+    RPL_AWAY,
+    RPL_USERHOST,
+    RPL_ISON,
+    RPL_UNAWAY,
+    RPL_NOWAWAY,
+    RPL_WHOISUSER,
+    RPL_WHOISSERVER,
+    RPL_WHOISOPERATOR,
+    RPL_WHOWASUSER,
+    RPL_ENDOFWHO,
+    RPL_WHOISIDLE,
+    RPL_ENDOFWHOIS,
+    RPL_WHOISCHANNELS,
+    RPL_LISTSTART,
+    RPL_LIST,
+    RPL_LISTEND,
+    RPL_CHANNELMODEIS,
+    RPL_UNIQOPIS,
+    RPL_CREATIONTIME,
+    RPL_WHOISACCOUNT,
+    RPL_NOTOPIC,
+    RPL_TOPIC,
+    RPL_TOPICWHOTIME,
+    RPL_WHOISBOT,
+    RPL_WHOISACTUALLY,
+    RPL_USERIP,
+    RPL_INVITING,
+    RPL_SUMMONING,
+    RPL_INVITELIST,
+    RPL_ENDOFINVITELIST,
+    RPL_EXCEPTLIST,
+    RPL_ENDOFEXCEPTLIST,
+    RPL_VERSION,
+    RPL_WHOREPLY,
+    RPL_NAMREPLY,
+    RPL_WHOSPCRPL,
+    RPL_LINKS,
+    RPL_ENDOFLINKS,
+    RPL_ENDOFNAMES,
+    RPL_BANLIST,
+    RPL_ENDOFBANLIST,
+    RPL_ENDOFWHOWAS,
+    RPL_INFO,
+    RPL_MOTD,
+    RPL_ENDOFINFO,
+    RPL_MOTDSTART,
+    RPL_ENDOFMOTD,
+    RPL_WHOISHOST,
+    RPL_WHOISMODES,
+    RPL_YOUREOPER,
+    RPL_REHASHING,
+    RPL_YOURESERVICE,
+    RPL_TIME,
+    RPL_USERSSTART,
+    RPL_USERS,
+    RPL_ENDOFUSERS,
+    RPL_NOUSERS,
+    RPL_HOSTHIDDEN,
+    ERR_UNKNOWNERROR,
+    ERR_NOSUCHNICK,
+    ERR_NOSUCHSERVER,
+    ERR_NOSUCHCHANNEL,
+    ERR_CANNOTSENDTOCHAN,
+    ERR_TOOMANYCHANNELS,
+    ERR_WASNOSUCHNICK,
+    ERR_TOOMANYTARGETS,
+    ERR_NOSUCHSERVICE,
+    ERR_NOORIGIN,
+    ERR_INVALIDCAPCMD,
+    ERR_NORECIPIENT,
+    ERR_NOTEXTTOSEND,
+    ERR_NOTOPLEVEL,
+    ERR_WILDTOPLEVEL,
+    ERR_BADMASK,
+    ERR_INPUTTOOLONG,
+    ERR_UNKNOWNCOMMAND,
+    ERR_NOMOTD,
+    ERR_NOADMININFO,
+    ERR_FILEERROR,
+    ERR_NONICKNAMEGIVEN,
+    ERR_ERRONEOUSNICKNAME,
+    ERR_NICKNAMEINUSE,
+    ERR_NICKCOLLISION,
+    ERR_UNAVAILRESOURCE,
+    ERR_USERNOTINCHANNEL,
+    ERR_NOTONCHANNEL,
+    ERR_USERONCHANNEL,
+    ERR_NOLOGIN,
+    ERR_SUMMONDISABLED,
+    ERR_USERSDISABLED,
+    ERR_NONICKCHANGE,
+    ERR_NOTREGISTERED,
+    ERR_ACCEPTFULL,
+    ERR_ACCEPTEXIST,
+    ERR_ACCEPTNOT,
+    ERR_NEEDMOREPARAMS,
+    ERR_ALREADYREGISTERED,
+    ERR_NOPERMFORHOST,
+    ERR_PASSWDMISMATCH,
+    ERR_YOUREBANNEDCREEP,
+    ERR_YOUWILLBEBANNED,
+    ERR_KEYSET,
+    ERR_CHANNELISFULL,
+    ERR_UNKNOWNMODE,
+    ERR_INVITEONLYCHAN,
+    ERR_BANNEDFROMCHAN,
+    ERR_BADCHANNELKEY,
+    ERR_BADCHANMASK,
+    ERR_NEEDREGGEDNICK,
+    ERR_BANLISTFULL,
+    ERR_BADCHANNAME,
+    ERR_NOPRIVILEGES,
+    ERR_CHANOPRIVSNEEDED,
+    ERR_CANTKILLSERVER,
+    ERR_RESTRICTED,
+    ERR_UNIQOPPRIVSNEEDED,
+    ERR_SECUREONLYCHAN,
+    ERR_NOOPERHOST,
+    ERR_UMODEUNKNOWNFLAG,
+    ERR_USERSDONTMATCH,
+    ERR_SILELISTFULL,
+    ERR_OPERONLY,
+    ERR_HELPNOTFOUND,
+    ERR_INVALIDKEY,
+    RPL_MAP,
+    RPL_MAPEND,
+    RPL_RULESTART,
+    RPL_RULES,
+    RPL_ENDOFRULES,
+    ERR_NORULES,
+    RPL_STATSPLINE,
+    RPL_STARTTLS,
+    RPL_WHOISSECURE,
+    ERR_STARTTLS,
+    ERR_INVALIDMODEPARAM,
+    RPL_HELPSTART,
+    RPL_HELPTXT,
+    RPL_ENDOFHELP,
+    RPL_KNOCK,
+    RPL_KNOCKDLVR,
+    ERR_TOOMANYKNOCK,
+    ERR_CHANOPEN,
+    ERR_KNOCKONCHAN,
+    ERR_NOPRIVS,
+    RPL_QUIETLIST,
+    RPL_ENDOFQUIETLIST,
+    RPL_MONONLINE,
+    RPL_MONOFFLINE,
+    RPL_MONLIST,
+    RPL_ENDOFMONLIST,
+    ERR_MONLISTFULL,
+    RPL_WHOISKEYVALUE,
+    RPL_KEYVALUE,
+    ERR_TARGETINVALID,
+    ERR_NOMATCHINGKEY,
+    ERR_KEYINVALID,
+    ERR_KEYNOTSET,
+    ERR_KEYNOPERMISSION,
+    RPL_LOGGEDIN,
+    RPL_LOGGEDOUT,
+    ERR_NICKLOCKED,
+    RPL_SASLSUCCESS,
+    ERR_SASLFAIL,
+    ERR_SASLTOOLONG,
+    ERR_SASLABORT,
+    ERR_SASLALREADY,
+    RPL_SASLMECHS,
+}
+
 impl Response {
-    /// Returns all RPL_* (success) response variants from code mapping.
+    /// Iterate over every known response, in ascending code order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::Response;
     ///
-    /// Used by `from_code()` to map numeric codes to Response enum variants.
-    pub(super) fn from_success_code(code: u16) -> Option<Response> {
-        Some(match code {
-            1 => Response::RPL_WELCOME,
-            2 => Response::RPL_YOURHOST,
-            3 => Response::RPL_CREATED,
-            4 => Response::RPL_MYINFO,
-            5 => Response::RPL_ISUPPORT,
-            10 => Response::RPL_BOUNCE,
-            42 => Response::RPL_YOURID,
-            200 => Response::RPL_TRACELINK,
-            201 => Response::RPL_TRACECONNECTING,
-            202 => Response::RPL_TRACEHANDSHAKE,
-            203 => Response::RPL_TRACEUNKNOWN,
-            204 => Response::RPL_TRACEOPERATOR,
-            205 => Response::RPL_TRACEUSER,
-            206 => Response::RPL_TRACESERVER,
-            207 => Response::RPL_TRACESERVICE,
-            208 => Response::RPL_TRACENEWTYPE,
-            209 => Response::RPL_TRACECLASS,
-            210 => Response::RPL_TRACERECONNECT,
-            211 => Response::RPL_STATSLINKINFO,
-            212 => Response::RPL_STATSCOMMANDS,
-            216 => Response::RPL_STATSKLINE,
-            219 => Response::RPL_ENDOFSTATS,
-            220 => Response::RPL_STATSDLINE,
-            221 => Response::RPL_UMODEIS,
-            226 => Response::RPL_STATSSHUN,
-            234 => Response::RPL_SERVLIST,
-            235 => Response::RPL_SERVLISTEND,
-            242 => Response::RPL_STATSUPTIME,
-            243 => Response::RPL_STATSOLINE,
-            249 => Response::RPL_STATSDEBUG,
-            251 => Response::RPL_LUSERCLIENT,
-            252 => Response::RPL_LUSEROP,
-            253 => Response::RPL_LUSERUNKNOWN,
-            254 => Response::RPL_LUSERCHANNELS,
-            255 => Response::RPL_LUSERME,
-            256 => Response::RPL_ADMINME,
-            257 => Response::RPL_ADMINLOC1,
-            258 => Response::RPL_ADMINLOC2,
-            259 => Response::RPL_ADMINEMAIL,
-            261 => Response::RPL_TRACELOG,
-            262 => Response::RPL_TRACEEND,
-            263 => Response::RPL_TRYAGAIN,
-            265 => Response::RPL_LOCALUSERS,
-            266 => Response::RPL_GLOBALUSERS,
-            271 => Response::RPL_SILELIST,
-            272 => Response::RPL_ENDOFSILELIST,
-            276 => Response::RPL_WHOISCERTFP,
-            281 => Response::RPL_ACCEPTLIST,
-            282 => Response::RPL_ENDOFACCEPT,
-            300 => Response::RPL_NONE,
-            301 => Response::RPL_AWAY,
-            302 => Response::RPL_USERHOST,
-            303 => Response::RPL_ISON,
-            305 => Response::RPL_UNAWAY,
-            306 => Response::RPL_NOWAWAY,
-            311 => Response::RPL_WHOISUSER,
-            312 => Response::RPL_WHOISSERVER,
-            313 => Response::RPL_WHOISOPERATOR,
-            314 => Response::RPL_WHOWASUSER,
-            315 => Response::RPL_ENDOFWHO,
-            317 => Response::RPL_WHOISIDLE,
-            318 => Response::RPL_ENDOFWHOIS,
-            319 => Response::RPL_WHOISCHANNELS,
-            321 => Response::RPL_LISTSTART,
-            322 => Response::RPL_LIST,
-            323 => Response::RPL_LISTEND,
-            324 => Response::RPL_CHANNELMODEIS,
-            325 => Response::RPL_UNIQOPIS,
-            329 => Response::RPL_CREATIONTIME,
-            330 => Response::RPL_WHOISACCOUNT,
-            331 => Response::RPL_NOTOPIC,
-            332 => Response::RPL_TOPIC,
-            333 => Response::RPL_TOPICWHOTIME,
-            335 => Response::RPL_WHOISBOT,
-            338 => Response::RPL_WHOISACTUALLY,
-            340 => Response::RPL_USERIP,
-            341 => Response::RPL_INVITING,
-            342 => Response::RPL_SUMMONING,
-            346 => Response::RPL_INVITELIST,
-            347 => Response::RPL_ENDOFINVITELIST,
-            348 => Response::RPL_EXCEPTLIST,
-            349 => Response::RPL_ENDOFEXCEPTLIST,
-            351 => Response::RPL_VERSION,
-            352 => Response::RPL_WHOREPLY,
-            353 => Response::RPL_NAMREPLY,
-            354 => Response::RPL_WHOSPCRPL,
-            364 => Response::RPL_LINKS,
-            365 => Response::RPL_ENDOFLINKS,
-            366 => Response::RPL_ENDOFNAMES,
-            367 => Response::RPL_BANLIST,
-            368 => Response::RPL_ENDOFBANLIST,
-            369 => Response::RPL_ENDOFWHOWAS,
-            371 => Response::RPL_INFO,
-            372 => Response::RPL_MOTD,
-            374 => Response::RPL_ENDOFINFO,
-            375 => Response::RPL_MOTDSTART,
-            376 => Response::RPL_ENDOFMOTD,
-            378 => Response::RPL_WHOISHOST,
-            379 => Response::RPL_WHOISMODES,
-            381 => Response::RPL_YOUREOPER,
-            382 => Response::RPL_REHASHING,
-            383 => Response::RPL_YOURESERVICE,
-            391 => Response::RPL_TIME,
-            392 => Response::RPL_USERSSTART,
-            393 => Response::RPL_USERS,
-            394 => Response::RPL_ENDOFUSERS,
-            395 => Response::RPL_NOUSERS,
-            396 => Response::RPL_HOSTHIDDEN,
-            606 => Response::RPL_MAP,
-            607 => Response::RPL_MAPEND,
-            632 => Response::RPL_RULESTART,
-            633 => Response::RPL_RULES,
-            634 => Response::RPL_ENDOFRULES,
-            646 => Response::RPL_STATSPLINE,
-            670 => Response::RPL_STARTTLS,
-            671 => Response::RPL_WHOISSECURE,
-            691 => Response::ERR_STARTTLS,
-            704 => Response::RPL_HELPSTART,
-            705 => Response::RPL_HELPTXT,
-            706 => Response::RPL_ENDOFHELP,
-            710 => Response::RPL_KNOCK,
-            711 => Response::RPL_KNOCKDLVR,
-            728 => Response::RPL_QUIETLIST,
-            729 => Response::RPL_ENDOFQUIETLIST,
-            730 => Response::RPL_MONONLINE,
-            731 => Response::RPL_MONOFFLINE,
-            732 => Response::RPL_MONLIST,
-            733 => Response::RPL_ENDOFMONLIST,
-            760 => Response::RPL_WHOISKEYVALUE,
-            761 => Response::RPL_KEYVALUE,
-            900 => Response::RPL_LOGGEDIN,
-            901 => Response::RPL_LOGGEDOUT,
-            903 => Response::RPL_SASLSUCCESS,
-            908 => Response::RPL_SASLMECHS,
-            _ => return None,
-        })
+    /// assert_eq!(Response::all().next(), Some(Response::RPL_WELCOME));
+    /// assert!(Response::all().all(|r| Response::from_code(r.code()) == Some(r)));
+    /// ```
+    pub fn all() -> impl Iterator<Item = Response> {
+        ALL.iter().map(|&(response, _)| response)
+    }
+
+    /// Creates a Response from a numeric code
+    pub fn from_code(code: u16) -> Option<Response> {
+        ALL.binary_search_by_key(&code, |(response, _)| response.code())
+            .ok()
+            .map(|i| ALL[i].0)
     }
 }
@@ -0,0 +1,103 @@
+//! Parsing of `RPL_UMODEIS` (221) replies.
+
+use crate::mode::{Mode, ModeType, UserMode};
+
+/// Parse the arguments of an `RPL_UMODEIS` (221) reply.
+///
+/// The reply has the form `<client> <modestring> [<params>...]`. Every mode
+/// listed is set, so each is returned as [`Mode::Plus`]. A server notice mask
+/// following `+s` is attached to it when present. Returns an empty list if
+/// the modestring is missing.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::mode::{Mode, UserMode};
+/// use slirc_proto::response::parse_umode_is;
+///
+/// let modes = parse_umode_is(&["me", "+iw"]);
+/// assert_eq!(
+///     modes,
+///     [Mode::Plus(UserMode::Invisible, None), Mode::Plus(UserMode::Wallops, None)]
+/// );
+/// ```
+pub fn parse_umode_is<S: AsRef<str>>(args: &[S]) -> Vec<Mode<UserMode>> {
+    match args {
+        [_, modestring, params @ ..] => parse_user_mode_changes(modestring.as_ref(), params),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse a user modestring and its parameters into mode changes.
+///
+/// Unlike [`Mode::as_user_modes`], a missing parameter is tolerated rather
+/// than treated as an error, since servers do not always echo the server
+/// notice mask.
+pub(crate) fn parse_user_mode_changes<S: AsRef<str>>(
+    modestring: &str,
+    params: &[S],
+) -> Vec<Mode<UserMode>> {
+    let mut params = params.iter().map(|p| p.as_ref().to_string());
+    let mut plus = true;
+    let mut modes = Vec::new();
+    for c in modestring.chars() {
+        match c {
+            '+' => plus = true,
+            '-' => plus = false,
+            _ => {
+                let mode = UserMode::from_char(c);
+                let arg = if mode.takes_arg() {
+                    params.next()
+                } else {
+                    None
+                };
+                modes.push(if plus {
+                    Mode::Plus(mode, arg)
+                } else {
+                    Mode::Minus(mode, arg)
+                });
+            }
+        }
+    }
+    modes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::message::Message;
+    use crate::response::Response;
+
+    #[test]
+    fn test_parse_umode_is() {
+        let msg: Message = ":irc.test 221 me +iwx".parse().unwrap();
+        let Command::Response(Response::RPL_UMODEIS, args) = msg.command else {
+            panic!("Expected RPL_UMODEIS");
+        };
+        assert_eq!(
+            parse_umode_is(&args),
+            [
+                Mode::Plus(UserMode::Invisible, None),
+                Mode::Plus(UserMode::Wallops, None),
+                Mode::Plus(UserMode::MaskedHost, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_umode_is_snomask() {
+        assert_eq!(
+            parse_umode_is(&["me", "+is", "+cF"]),
+            [
+                Mode::Plus(UserMode::Invisible, None),
+                Mode::Plus(UserMode::ServerNotices, Some("+cF".to_string())),
+            ]
+        );
+        assert_eq!(
+            parse_umode_is(&["me", "+s"]),
+            [Mode::Plus(UserMode::ServerNotices, None)]
+        );
+        assert!(parse_umode_is(&["me"]).is_empty());
+    }
+}
@@ -0,0 +1,409 @@
+//! Parsing of `RPL_WHOREPLY` (352) numerics.
+
+use crate::command::Command;
+use crate::message::Message;
+
+use super::{ParseResponseError, Response, WhoxReply};
+
+/// A single entry from a WHO reply, as sent in `RPL_WHOREPLY` (352).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WhoReply {
+    /// The channel the reply refers to, or `*` if none.
+    pub channel: String,
+    /// Username (ident).
+    pub username: String,
+    /// Hostname.
+    pub host: String,
+    /// Server the user is connected to.
+    pub server: String,
+    /// Nickname.
+    pub nick: String,
+    /// Whether the user is marked away (`G` rather than `H`).
+    pub away: bool,
+    /// Whether the user is an IRC operator (`*`).
+    pub is_oper: bool,
+    /// Channel status prefixes held by the user (e.g. `@`, `+`), in order.
+    pub prefixes: Vec<char>,
+    /// Hop count between the queried server and the user's server.
+    pub hopcount: u32,
+    /// Real name / GECOS.
+    pub realname: String,
+}
+
+impl WhoReply {
+    /// Parse the arguments of an `RPL_WHOREPLY` (352) reply.
+    ///
+    /// The reply has the form
+    /// `<client> <channel> <username> <host> <server> <nick> <flags> :<hopcount> <realname>`.
+    /// Flag characters other than `H`, `G`, `*` and channel status prefixes
+    /// (such as a bot marker) are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::response::WhoReply;
+    ///
+    /// let args = ["me", "#rust", "ali", "host.example", "irc.example.net", "alice", "G@", "0 Alice"];
+    /// let reply = WhoReply::parse(&args).unwrap();
+    /// assert_eq!(reply.nick, "alice");
+    /// assert!(reply.away);
+    /// assert_eq!(reply.prefixes, ['@']);
+    /// assert_eq!(reply.realname, "Alice");
+    /// ```
+    pub fn parse(args: &[&str]) -> Result<WhoReply, ParseResponseError> {
+        let [_, channel, username, host, server, nick, flags, trailing] = args else {
+            return Err(ParseResponseError::InvalidArgs);
+        };
+
+        let (hopcount, realname) = trailing.split_once(' ').unwrap_or((trailing, ""));
+        let hopcount = hopcount
+            .parse()
+            .map_err(|_| ParseResponseError::InvalidArgs)?;
+
+        let mut chars = flags.chars();
+        let away = match chars.next() {
+            Some('H') => false,
+            Some('G') => true,
+            _ => return Err(ParseResponseError::InvalidArgs),
+        };
+        let mut is_oper = false;
+        let mut prefixes = Vec::new();
+        for c in chars {
+            match c {
+                '*' => is_oper = true,
+                c if c.is_ascii_punctuation() => prefixes.push(c),
+                _ => {}
+            }
+        }
+
+        Ok(WhoReply {
+            channel: channel.to_string(),
+            username: username.to_string(),
+            host: host.to_string(),
+            server: server.to_string(),
+            nick: nick.to_string(),
+            away,
+            is_oper,
+            prefixes,
+            hopcount,
+            realname: realname.to_string(),
+        })
+    }
+}
+
+/// Parse the arguments of an `RPL_WHOREPLY` (352) reply.
+///
+/// Accepts the arguments as stored in [`Command::Response`](crate::Command::Response)
+/// and returns `None` if they are malformed; see [`WhoReply::parse`] for the
+/// layout and error details.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::response::parse_who_reply;
+///
+/// let args: Vec<String> = ["me", "#rust", "ali", "host", "irc.example.net", "alice", "H", "3 Alice"]
+///     .iter()
+///     .map(|s| s.to_string())
+///     .collect();
+/// let reply = parse_who_reply(&args).unwrap();
+/// assert_eq!(reply.hopcount, 3);
+/// assert_eq!(reply.realname, "Alice");
+/// ```
+pub fn parse_who_reply<S: AsRef<str>>(args: &[S]) -> Option<WhoReply> {
+    let args: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+    WhoReply::parse(&args).ok()
+}
+
+/// A single WHO result, from either a standard WHO or a WHOX query.
+///
+/// Collected by [`WhoCollector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WhoEntry {
+    /// A standard `RPL_WHOREPLY` (352) reply.
+    Who(WhoReply),
+    /// A WHOX `RPL_WHOSPCRPL` (354) reply.
+    Whox(WhoxReply),
+}
+
+impl WhoEntry {
+    /// Parse a WHO result, choosing the parser from the numeric.
+    ///
+    /// `RPL_WHOREPLY` is parsed with [`WhoReply::parse`] and `RPL_WHOSPCRPL`
+    /// with [`WhoxReply::parse`] using the requested `fields` (ignored for
+    /// standard replies). Returns `Ok(None)` for any other numeric, including
+    /// `RPL_ENDOFWHO`, so every reply of a WHO query can be fed through it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::response::{Response, WhoEntry};
+    ///
+    /// let args = ["me", "152", "alice"];
+    /// let entry = WhoEntry::parse(Response::RPL_WHOSPCRPL, "%tn,152", &args).unwrap();
+    /// assert!(matches!(entry, Some(WhoEntry::Whox(ref r)) if r.nick.as_deref() == Some("alice")));
+    ///
+    /// let end = WhoEntry::parse(Response::RPL_ENDOFWHO, "", &["me", "#rust", "End"]);
+    /// assert_eq!(end, Ok(None));
+    /// ```
+    pub fn parse(
+        response: Response,
+        fields: &str,
+        args: &[&str],
+    ) -> Result<Option<WhoEntry>, ParseResponseError> {
+        match response {
+            Response::RPL_WHOREPLY => WhoReply::parse(args).map(|r| Some(WhoEntry::Who(r))),
+            Response::RPL_WHOSPCRPL => {
+                WhoxReply::parse(fields, args).map(|r| Some(WhoEntry::Whox(r)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// The nickname of the user this entry describes, if known.
+    pub fn nick(&self) -> Option<&str> {
+        match self {
+            WhoEntry::Who(reply) => Some(&reply.nick),
+            WhoEntry::Whox(reply) => reply.nick.as_deref(),
+        }
+    }
+}
+
+/// Collects the replies to a WHO or WHOX query into [`WhoEntry`] values.
+///
+/// Feed every incoming message; `RPL_WHOREPLY` (352) and `RPL_WHOSPCRPL`
+/// (354) replies are buffered and returned together with the queried mask
+/// once `RPL_ENDOFWHO` (315) arrives. WHOX replies are only decoded by a
+/// collector created with [`whox`](Self::whox), since their layout depends
+/// on the requested fields. Malformed replies are skipped.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::response::{WhoCollector, WhoEntry};
+/// use slirc_proto::Message;
+///
+/// let mut who = WhoCollector::whox("%tnf,42");
+/// for line in [
+///     ":irc.test 354 me 42 alice H@",
+///     ":irc.test 315 me #rust :End of WHO list",
+/// ] {
+///     let msg: Message = line.parse().unwrap();
+///     if let Some((mask, entries)) = who.feed(&msg) {
+///         assert_eq!(mask, "#rust");
+///         assert_eq!(entries[0].nick(), Some("alice"));
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct WhoCollector {
+    fields: Option<String>,
+    entries: Vec<WhoEntry>,
+}
+
+impl WhoCollector {
+    /// Create a collector for a standard WHO query.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a collector for a WHOX query sent with the given field string
+    /// (e.g. `%tcuhnfar,152`).
+    #[must_use]
+    pub fn whox(fields: &str) -> Self {
+        Self {
+            fields: Some(fields.to_string()),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Feed a message, returning the mask and entries on `RPL_ENDOFWHO`.
+    ///
+    /// Messages other than WHO replies are ignored.
+    pub fn feed(&mut self, msg: &Message) -> Option<(String, Vec<WhoEntry>)> {
+        let Command::Response(response, ref args) = msg.command else {
+            return None;
+        };
+
+        match response {
+            Response::RPL_ENDOFWHO => {
+                let mask = args.get(1)?.clone();
+                Some((mask, std::mem::take(&mut self.entries)))
+            }
+            Response::RPL_WHOSPCRPL if self.fields.is_none() => None,
+            _ => {
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                let fields = self.fields.as_deref().unwrap_or_default();
+                if let Ok(Some(entry)) = WhoEntry::parse(response, fields, &args) {
+                    self.entries.push(entry);
+                }
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::message::Message;
+
+    fn parse_line(raw: &str) -> Result<WhoReply, ParseResponseError> {
+        let msg: Message = raw.parse().unwrap();
+        let Command::Response(Response::RPL_WHOREPLY, args) = msg.command else {
+            panic!("Expected RPL_WHOREPLY");
+        };
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        WhoReply::parse(&args)
+    }
+
+    #[test]
+    fn test_away_user() {
+        let reply =
+            parse_line(":srv 352 me #rust ali host.example irc.example.net alice G :2 Alice Smith")
+                .unwrap();
+        assert_eq!(
+            reply,
+            WhoReply {
+                channel: "#rust".to_string(),
+                username: "ali".to_string(),
+                host: "host.example".to_string(),
+                server: "irc.example.net".to_string(),
+                nick: "alice".to_string(),
+                away: true,
+                is_oper: false,
+                prefixes: Vec::new(),
+                hopcount: 2,
+                realname: "Alice Smith".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_oper() {
+        let reply =
+            parse_line(":srv 352 me * root staff.example irc.example.net admin H* :0 Admin")
+                .unwrap();
+        assert!(!reply.away);
+        assert!(reply.is_oper);
+        assert!(reply.prefixes.is_empty());
+        assert_eq!(reply.channel, "*");
+    }
+
+    #[test]
+    fn test_multiple_prefixes() {
+        let reply =
+            parse_line(":srv 352 me #rust b host irc.example.net bob H*~@+ :0 Bob").unwrap();
+        assert!(reply.is_oper);
+        assert_eq!(reply.prefixes, vec!['~', '@', '+']);
+
+        // Non-prefix flag letters such as a bot marker are skipped
+        let reply = parse_line(":srv 352 me #rust b host irc.example.net bot HB% :0 Bot").unwrap();
+        assert_eq!(reply.prefixes, vec!['%']);
+    }
+
+    #[test]
+    fn test_empty_realname() {
+        let reply = parse_line(":srv 352 me #rust b host irc.example.net bob H :0").unwrap();
+        assert_eq!(reply.hopcount, 0);
+        assert_eq!(reply.realname, "");
+    }
+
+    #[test]
+    fn test_parse_who_reply() {
+        let msg: Message =
+            ":irc.example.net 352 me #rust ~bob bob.example irc.example.net bob H@ :1 Bob Builder"
+                .parse()
+                .unwrap();
+        let Command::Response(Response::RPL_WHOREPLY, args) = msg.command else {
+            panic!("Expected RPL_WHOREPLY");
+        };
+        let reply = parse_who_reply(&args).unwrap();
+        assert_eq!(reply.channel, "#rust");
+        assert_eq!(reply.username, "~bob");
+        assert_eq!(reply.host, "bob.example");
+        assert_eq!(reply.server, "irc.example.net");
+        assert_eq!(reply.nick, "bob");
+        assert_eq!(reply.prefixes, ['@']);
+        assert_eq!(reply.hopcount, 1);
+        assert_eq!(reply.realname, "Bob Builder");
+
+        assert_eq!(parse_who_reply(&args[..7]), None);
+    }
+
+    #[test]
+    fn test_entry_dispatch() {
+        let args = [
+            "me",
+            "#rust",
+            "ali",
+            "host.example",
+            "irc.example.net",
+            "alice",
+            "H",
+            "0 Alice",
+        ];
+        let entry = WhoEntry::parse(Response::RPL_WHOREPLY, "", &args)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(entry, WhoEntry::Who(ref r) if r.host == "host.example"));
+        assert_eq!(entry.nick(), Some("alice"));
+
+        let args = ["me", "152", "#rust", "alice"];
+        let entry = WhoEntry::parse(Response::RPL_WHOSPCRPL, "%tcn,152", &args)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(entry, WhoEntry::Whox(ref r) if r.querytype.as_deref() == Some("152")));
+        assert_eq!(entry.nick(), Some("alice"));
+
+        // Each numeric uses its own parser
+        assert_eq!(
+            WhoEntry::parse(Response::RPL_WHOSPCRPL, "n", &args),
+            Err(ParseResponseError::InvalidArgs)
+        );
+        assert_eq!(
+            WhoEntry::parse(Response::RPL_ENDOFWHO, "", &["me", "#rust", "End of WHO"]),
+            Ok(None)
+        );
+        assert_eq!(WhoEntry::parse(Response::RPL_TOPIC, "", &args), Ok(None));
+    }
+
+    #[test]
+    fn test_collector_standard() {
+        let mut who = WhoCollector::new();
+        for line in [
+            ":srv 352 me #rust ali host srv alice H@ :0 Alice",
+            ":srv 352 me #rust b host srv bob broken :0 Bob",
+            ":srv 354 me 152 carol",
+            ":srv 352 me #rust c host srv carol G :1 Carol",
+        ] {
+            assert_eq!(who.feed(&line.parse().unwrap()), None);
+        }
+        let (mask, entries) = who
+            .feed(&":srv 315 me #rust :End of WHO list".parse().unwrap())
+            .unwrap();
+        assert_eq!(mask, "#rust");
+        let nicks: Vec<_> = entries.iter().filter_map(WhoEntry::nick).collect();
+        assert_eq!(nicks, ["alice", "carol"]);
+
+        // The buffer is emptied for the next query
+        let (_, entries) = who
+            .feed(&":srv 315 me bob :End of WHO list".parse().unwrap())
+            .unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_malformed() {
+        let args = ["me", "#rust", "b", "host", "srv", "bob", "H"];
+        assert_eq!(WhoReply::parse(&args), Err(ParseResponseError::InvalidArgs));
+
+        let args = ["me", "#rust", "b", "host", "srv", "bob", "X", "0 Bob"];
+        assert_eq!(WhoReply::parse(&args), Err(ParseResponseError::InvalidArgs));
+
+        let args = ["me", "#rust", "b", "host", "srv", "bob", "H", "far Bob"];
+        assert_eq!(WhoReply::parse(&args), Err(ParseResponseError::InvalidArgs));
+    }
+}
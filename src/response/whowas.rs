@@ -0,0 +1,199 @@
+//! Collection of WHOWAS reply sequences.
+//!
+//! A WHOWAS query is answered with one `RPL_WHOWASUSER` (314) per historical
+//! entry, each optionally followed by `RPL_WHOISSERVER` (312), and terminated
+//! by `RPL_ENDOFWHOWAS` (369).
+
+use crate::casemap::irc_eq;
+use crate::message::MessageRef;
+
+use super::Response;
+
+/// A single historical entry from a WHOWAS reply.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WhowasEntry {
+    /// The nickname the entry was recorded under.
+    pub nick: String,
+    /// Username (ident).
+    pub user: String,
+    /// Hostname.
+    pub host: String,
+    /// Real name / GECOS.
+    pub realname: String,
+    /// Server the user was connected to, if reported by `RPL_WHOISSERVER`.
+    pub server: Option<String>,
+}
+
+/// Sans-IO collector for the replies to a single WHOWAS query.
+///
+/// Feed every incoming message to [`feed`](Self::feed); replies about other
+/// nicknames are ignored. The collected entries are returned once
+/// `RPL_ENDOFWHOWAS` arrives. An unknown nickname (`ERR_WASNOSUCHNICK`)
+/// yields an empty list at the terminator.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::response::WhowasCollector;
+/// use slirc_proto::MessageRef;
+///
+/// let mut collector = WhowasCollector::new("alice");
+/// let mut result = None;
+/// for raw in [
+///     ":srv 314 me alice ali host.example * :Alice",
+///     ":srv 312 me alice irc.example.net :Mon Jan 1 00:00:00 2024",
+///     ":srv 369 me alice :End of WHOWAS",
+/// ] {
+///     result = collector.feed(&MessageRef::parse(raw).unwrap());
+/// }
+///
+/// let entries = result.unwrap();
+/// assert_eq!(entries[0].host, "host.example");
+/// assert_eq!(entries[0].server.as_deref(), Some("irc.example.net"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct WhowasCollector {
+    nick: String,
+    entries: Vec<WhowasEntry>,
+}
+
+impl WhowasCollector {
+    /// Create a collector for replies about `nick`.
+    #[must_use]
+    pub fn new<N: Into<String>>(nick: N) -> Self {
+        Self {
+            nick: nick.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Get the nickname being looked up.
+    #[must_use]
+    pub fn nick(&self) -> &str {
+        &self.nick
+    }
+
+    /// Get the entries collected so far.
+    #[must_use]
+    pub fn entries(&self) -> &[WhowasEntry] {
+        &self.entries
+    }
+
+    /// Feed an incoming message.
+    ///
+    /// Returns the collected entries when the message is the
+    /// `RPL_ENDOFWHOWAS` terminator for this nickname, leaving the collector
+    /// empty and ready for another query.
+    pub fn feed(&mut self, msg: &MessageRef<'_>) -> Option<Vec<WhowasEntry>> {
+        let response = msg.numeric_code().and_then(Response::from_code)?;
+        let args = msg.args();
+        if !args.get(1).is_some_and(|nick| irc_eq(nick, &self.nick)) {
+            return None;
+        }
+
+        match response {
+            // <client> <nick> <user> <host> * :<realname>
+            Response::RPL_WHOWASUSER => {
+                if let [_, nick, user, host, .., realname] = args {
+                    self.entries.push(WhowasEntry {
+                        nick: nick.to_string(),
+                        user: user.to_string(),
+                        host: host.to_string(),
+                        realname: realname.to_string(),
+                        server: None,
+                    });
+                }
+                None
+            }
+            // <client> <nick> <server> :<server info>
+            Response::RPL_WHOISSERVER => {
+                if let (Some(entry), Some(server)) = (self.entries.last_mut(), args.get(2)) {
+                    entry.server = Some(server.to_string());
+                }
+                None
+            }
+            Response::RPL_ENDOFWHOWAS => Some(std::mem::take(&mut self.entries)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(collector: &mut WhowasCollector, lines: &[&str]) -> Option<Vec<WhowasEntry>> {
+        let mut result = None;
+        for raw in lines {
+            let msg = MessageRef::parse(raw).unwrap();
+            if let Some(entries) = collector.feed(&msg) {
+                result = Some(entries);
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_two_entries_and_terminator() {
+        let mut collector = WhowasCollector::new("Alice");
+        let entries = feed_all(
+            &mut collector,
+            &[
+                ":srv 314 me alice ali old.example * :Alice Old",
+                ":srv 312 me alice hub.example.net :Sun Dec 31 2023",
+                ":srv 314 me alice alice new.example * :Alice New",
+                ":srv 369 me alice :End of WHOWAS",
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                WhowasEntry {
+                    nick: "alice".to_string(),
+                    user: "ali".to_string(),
+                    host: "old.example".to_string(),
+                    realname: "Alice Old".to_string(),
+                    server: Some("hub.example.net".to_string()),
+                },
+                WhowasEntry {
+                    nick: "alice".to_string(),
+                    user: "alice".to_string(),
+                    host: "new.example".to_string(),
+                    realname: "Alice New".to_string(),
+                    server: None,
+                },
+            ]
+        );
+        assert!(collector.entries().is_empty());
+    }
+
+    #[test]
+    fn test_ignores_other_nicks() {
+        let mut collector = WhowasCollector::new("alice");
+        let entries = feed_all(
+            &mut collector,
+            &[
+                ":srv 314 me bob bob host * :Bob",
+                ":srv 369 me bob :End of WHOWAS",
+                ":srv PRIVMSG me :hi",
+            ],
+        );
+        assert!(entries.is_none());
+        assert!(collector.entries().is_empty());
+    }
+
+    #[test]
+    fn test_no_such_nick() {
+        let mut collector = WhowasCollector::new("ghost");
+        let entries = feed_all(
+            &mut collector,
+            &[
+                ":srv 406 me ghost :There was no such nickname",
+                ":srv 369 me ghost :End of WHOWAS",
+            ],
+        );
+        assert_eq!(entries, Some(Vec::new()));
+    }
+}
@@ -0,0 +1,184 @@
+//! Parsing of WHOX `RPL_WHOSPCRPL` (354) numerics.
+//!
+//! A WHOX query such as `WHO #chan %tcuhnfar,152` selects which columns the
+//! server returns. The server always sends the selected columns in a fixed
+//! order (`t c u i h s n f d l a o r`), regardless of their order in the
+//! request, so the requested field string is needed to decode a reply.
+
+use super::ParseResponseError;
+
+/// Column letters in the order servers send them.
+const FIELD_ORDER: &str = "tcuihsnfdlaor";
+
+/// A single WHOX reply, as sent in `RPL_WHOSPCRPL` (354).
+///
+/// Each field is `Some` only if it was requested in the query.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WhoxReply {
+    /// Query type token echoed back by the server (`t`).
+    pub querytype: Option<String>,
+    /// Channel name, or `*` (`c`).
+    pub channel: Option<String>,
+    /// Username (ident) (`u`).
+    pub user: Option<String>,
+    /// IP address (`i`).
+    pub ip: Option<String>,
+    /// Hostname (`h`).
+    pub host: Option<String>,
+    /// Server name (`s`).
+    pub server: Option<String>,
+    /// Nickname (`n`).
+    pub nick: Option<String>,
+    /// Status flags such as `H`, `G`, `*`, `@` (`f`).
+    pub flags: Option<String>,
+    /// Hop count (`d`).
+    pub hopcount: Option<u32>,
+    /// Idle time in seconds (`l`).
+    pub idle: Option<u64>,
+    /// Services account name, `0` if not logged in (`a`).
+    pub account: Option<String>,
+    /// Channel op level (`o`).
+    pub oplevel: Option<String>,
+    /// Real name / GECOS (`r`).
+    pub realname: Option<String>,
+}
+
+impl WhoxReply {
+    /// Parse the arguments of an `RPL_WHOSPCRPL` (354) reply.
+    ///
+    /// `fields` is the field string sent in the WHO request. A leading `%`
+    /// and a trailing `,<querytype>` are accepted and ignored, so the same
+    /// string used to build the query can be passed back. `args` are the
+    /// numeric's arguments, starting with the client nickname.
+    ///
+    /// Returns [`ParseResponseError::InvalidArgs`] if the number of values
+    /// does not match the number of requested fields, or a numeric column
+    /// fails to parse.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slirc_proto::response::WhoxReply;
+    ///
+    /// let args = ["me", "152", "#rust", "alice", "Alice"];
+    /// let reply = WhoxReply::parse("%tcnr,152", &args).unwrap();
+    /// assert_eq!(reply.querytype.as_deref(), Some("152"));
+    /// assert_eq!(reply.nick.as_deref(), Some("alice"));
+    /// assert_eq!(reply.host, None);
+    /// ```
+    pub fn parse(fields: &str, args: &[&str]) -> Result<WhoxReply, ParseResponseError> {
+        let fields = fields.strip_prefix('%').unwrap_or(fields);
+        let fields = fields.split(',').next().unwrap_or_default();
+
+        let values = args.get(1..).unwrap_or_default();
+        let requested = FIELD_ORDER.chars().filter(|&c| fields.contains(c));
+        if requested.clone().count() != values.len() {
+            return Err(ParseResponseError::InvalidArgs);
+        }
+
+        let mut reply = WhoxReply::default();
+        for (field, value) in requested.zip(values) {
+            let value = value.to_string();
+            match field {
+                't' => reply.querytype = Some(value),
+                'c' => reply.channel = Some(value),
+                'u' => reply.user = Some(value),
+                'i' => reply.ip = Some(value),
+                'h' => reply.host = Some(value),
+                's' => reply.server = Some(value),
+                'n' => reply.nick = Some(value),
+                'f' => reply.flags = Some(value),
+                'd' => {
+                    reply.hopcount =
+                        Some(value.parse().map_err(|_| ParseResponseError::InvalidArgs)?)
+                }
+                'l' => {
+                    reply.idle = Some(value.parse().map_err(|_| ParseResponseError::InvalidArgs)?)
+                }
+                'a' => reply.account = Some(value),
+                'o' => reply.oplevel = Some(value),
+                'r' => reply.realname = Some(value),
+                _ => unreachable!(),
+            }
+        }
+        Ok(reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::message::Message;
+    use crate::response::Response;
+
+    fn parse_line(fields: &str, raw: &str) -> Result<WhoxReply, ParseResponseError> {
+        let msg: Message = raw.parse().unwrap();
+        let Command::Response(Response::RPL_WHOSPCRPL, args) = msg.command else {
+            panic!("Expected RPL_WHOSPCRPL");
+        };
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        WhoxReply::parse(fields, &args)
+    }
+
+    #[test]
+    fn test_with_querytype() {
+        let reply = parse_line(
+            "%tcuhnfar,152",
+            ":srv 354 me 152 #rust ali host.example alice H@ alice_acct :Alice Smith",
+        )
+        .unwrap();
+        assert_eq!(
+            reply,
+            WhoxReply {
+                querytype: Some("152".to_string()),
+                channel: Some("#rust".to_string()),
+                user: Some("ali".to_string()),
+                host: Some("host.example".to_string()),
+                nick: Some("alice".to_string()),
+                flags: Some("H@".to_string()),
+                account: Some("alice_acct".to_string()),
+                realname: Some("Alice Smith".to_string()),
+                ..WhoxReply::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_without_querytype() {
+        let reply = parse_line("nuhdl", ":srv 354 me ali host.example alice 0 42").unwrap();
+        assert_eq!(reply.querytype, None);
+        assert_eq!(reply.nick.as_deref(), Some("alice"));
+        assert_eq!(reply.user.as_deref(), Some("ali"));
+        assert_eq!(reply.host.as_deref(), Some("host.example"));
+        assert_eq!(reply.hopcount, Some(0));
+        assert_eq!(reply.idle, Some(42));
+    }
+
+    #[test]
+    fn test_field_order_is_canonical() {
+        // Requested out of order; the server still replies in t c u ... r order
+        let reply = parse_line("%rna", ":srv 354 me alice * :Alice").unwrap();
+        assert_eq!(reply.nick.as_deref(), Some("alice"));
+        assert_eq!(reply.account.as_deref(), Some("*"));
+        assert_eq!(reply.realname.as_deref(), Some("Alice"));
+    }
+
+    #[test]
+    fn test_arg_count_mismatch() {
+        let args = ["me", "152", "#rust"];
+        assert_eq!(
+            WhoxReply::parse("%tcn,152", &args),
+            Err(ParseResponseError::InvalidArgs)
+        );
+        let args = ["me", "alice", "extra"];
+        assert_eq!(
+            WhoxReply::parse("n", &args),
+            Err(ParseResponseError::InvalidArgs)
+        );
+        assert_eq!(
+            WhoxReply::parse("d", &["me", "far"]),
+            Err(ParseResponseError::InvalidArgs)
+        );
+    }
+}
@@ -37,6 +37,9 @@
 
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 
+use crate::command::Command;
+use crate::Message;
+
 mod external;
 mod plain;
 mod scram;
@@ -153,6 +156,34 @@ pub fn needs_chunking(encoded: &str) -> bool {
     encoded.len() > SASL_CHUNK_SIZE
 }
 
+/// Build the `AUTHENTICATE` messages that send an encoded SASL response.
+///
+/// The payload is split into [`SASL_CHUNK_SIZE`]-byte chunks. When its
+/// length is an exact multiple of the chunk size (including zero), a final
+/// `AUTHENTICATE +` tells the server the response is complete.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::sasl::{authenticate_messages, encode_plain};
+///
+/// let messages = authenticate_messages(&encode_plain("user", "pass"));
+/// assert_eq!(messages.len(), 1);
+/// assert_eq!(messages[0].to_string(), "AUTHENTICATE AHVzZXIAcGFzcw==\r\n");
+///
+/// let empty = authenticate_messages("");
+/// assert_eq!(empty[0].to_string(), "AUTHENTICATE +\r\n");
+/// ```
+pub fn authenticate_messages(payload: &str) -> Vec<Message> {
+    let mut messages: Vec<Message> = chunk_response(payload)
+        .map(|chunk| Command::AUTHENTICATE(chunk.to_string()).into())
+        .collect();
+    if payload.len() % SASL_CHUNK_SIZE == 0 {
+        messages.push(Command::AUTHENTICATE("+".to_string()).into());
+    }
+    messages
+}
+
 /// Decode a base64-encoded SASL challenge or response.
 ///
 /// # Returns
@@ -210,6 +241,41 @@ mod tests {
         assert!(mechs.contains(&SaslMechanism::ScramSha256));
     }
 
+    fn authenticate_args(messages: &[Message]) -> Vec<&str> {
+        messages
+            .iter()
+            .map(|m| match &m.command {
+                Command::AUTHENTICATE(arg) => arg.as_str(),
+                other => panic!("unexpected command: {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_authenticate_messages_chunking() {
+        let payload = "a".repeat(399);
+        assert_eq!(
+            authenticate_args(&authenticate_messages(&payload)),
+            [payload]
+        );
+
+        let payload = "b".repeat(400);
+        assert_eq!(
+            authenticate_args(&authenticate_messages(&payload)),
+            [payload.as_str(), "+"]
+        );
+
+        let payload = format!("{}{}c", "a".repeat(400), "b".repeat(400));
+        let messages = authenticate_messages(&payload);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            authenticate_args(&messages),
+            [&payload[..400], &payload[400..800], "c"]
+        );
+
+        assert_eq!(authenticate_args(&authenticate_messages("")), ["+"]);
+    }
+
     #[test]
     fn test_choose_mechanism_prefers_external() {
         let available = vec![SaslMechanism::Plain, SaslMechanism::External];
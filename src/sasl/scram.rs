@@ -274,32 +274,25 @@ impl ScramClient {
     /// # Errors
     ///
     /// - `ScramError::CryptoNotAvailable` if `scram` feature not enabled
-    /// - `ScramError::ServerVerificationFailed` if signature doesn't match
+    /// - `ScramError::ServerVerificationFailed` if the client-final-message has
+    ///   not been sent, the server reported an error (`e=`), or the signature
+    ///   doesn't match
+    ///
+    /// Any error after the client-final-message was sent moves the state to
+    /// [`ScramState::Failed`].
     pub fn verify_server_final(&mut self, server_final: &str) -> Result<(), ScramError> {
         #[cfg(feature = "scram")]
         {
-            let decoded = decode_base64(server_final).map_err(|_| ScramError::InvalidEncoding)?;
-            let message = String::from_utf8(decoded).map_err(|_| ScramError::InvalidEncoding)?;
-
-            // Parse v=verifier
-            let verifier = message
-                .strip_prefix("v=")
-                .ok_or(ScramError::ServerVerificationFailed)?;
-
-            let server_sig = decode_base64(verifier).map_err(|_| ScramError::InvalidEncoding)?;
-
-            let expected = self
-                .server_signature
-                .as_ref()
-                .ok_or(ScramError::ServerVerificationFailed)?;
-
-            if server_sig == *expected {
-                self.state = ScramState::Complete;
-                Ok(())
-            } else {
-                self.state = ScramState::Failed("server verification failed".to_string());
-                Err(ScramError::ServerVerificationFailed)
+            if self.state != ScramState::ClientFinalSent {
+                return Err(ScramError::ServerVerificationFailed);
             }
+
+            let result = self.check_server_final(server_final);
+            self.state = match &result {
+                Ok(()) => ScramState::Complete,
+                Err(e) => ScramState::Failed(e.to_string()),
+            };
+            result
         }
 
         #[cfg(not(feature = "scram"))]
@@ -310,6 +303,33 @@ impl ScramClient {
     }
 }
 
+#[cfg(feature = "scram")]
+impl ScramClient {
+    /// Check a server-final-message against the expected server signature.
+    fn check_server_final(&self, server_final: &str) -> Result<(), ScramError> {
+        let decoded = decode_base64(server_final).map_err(|_| ScramError::InvalidEncoding)?;
+        let message = String::from_utf8(decoded).map_err(|_| ScramError::InvalidEncoding)?;
+
+        // Parse v=verifier (or e=server-error)
+        let verifier = message
+            .strip_prefix("v=")
+            .ok_or(ScramError::ServerVerificationFailed)?;
+
+        let server_sig = decode_base64(verifier).map_err(|_| ScramError::InvalidEncoding)?;
+
+        let expected = self
+            .server_signature
+            .as_ref()
+            .ok_or(ScramError::ServerVerificationFailed)?;
+
+        if server_sig == *expected {
+            Ok(())
+        } else {
+            Err(ScramError::ServerVerificationFailed)
+        }
+    }
+}
+
 /// Errors that can occur during SCRAM authentication.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ScramError {
@@ -471,13 +491,10 @@ mod tests {
         );
     }
 
-    /// RFC 7677 test vector.
-    /// Username: user, Password: pencil
+    /// Client with the exact nonce from the RFC 7677 example.
     #[cfg(feature = "scram")]
-    #[test]
-    fn test_scram_sha256_rfc7677_vector() {
-        // Create client with the exact nonce from RFC 7677
-        let mut client = ScramClient {
+    fn rfc7677_client() -> ScramClient {
+        ScramClient {
             username: "user".to_string(),
             password: "pencil".to_string(),
             client_nonce: "rOprNGfwEbeRWgbNEkqO".to_string(),
@@ -485,7 +502,19 @@ mod tests {
             server_first_message: String::new(),
             state: ScramState::Initial,
             server_signature: None,
-        };
+        }
+    }
+
+    #[cfg(feature = "scram")]
+    const RFC7677_SERVER_FIRST: &[u8] =
+        b"r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+
+    /// RFC 7677 test vector.
+    /// Username: user, Password: pencil
+    #[cfg(feature = "scram")]
+    #[test]
+    fn test_scram_sha256_rfc7677_vector() {
+        let mut client = rfc7677_client();
 
         // Step 1: Client first message
         let client_first = client.client_first_message();
@@ -493,10 +522,9 @@ mod tests {
         assert_eq!(decoded_first, "n,,n=user,r=rOprNGfwEbeRWgbNEkqO");
 
         // Step 2: Server first message (from RFC 7677 example)
-        let server_first = BASE64.encode(
-            b"r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096",
-        );
+        let server_first = BASE64.encode(RFC7677_SERVER_FIRST);
         let client_final = client.process_server_first(&server_first).unwrap();
+        assert!(matches!(client.state(), ScramState::ClientFinalSent));
 
         // Decode and verify client-final structure
         let decoded_final = String::from_utf8(BASE64.decode(&client_final).unwrap()).unwrap();
@@ -514,6 +542,40 @@ mod tests {
         assert!(matches!(client.state(), ScramState::Complete));
     }
 
+    #[cfg(feature = "scram")]
+    #[test]
+    fn test_server_final_mismatch_fails() {
+        let mut client = rfc7677_client();
+        let _ = client.client_first_message();
+
+        // Not yet sent the client-final-message
+        let server_final = BASE64.encode(b"v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=");
+        assert_eq!(
+            client.verify_server_final(&server_final),
+            Err(ScramError::ServerVerificationFailed)
+        );
+        assert!(matches!(client.state(), ScramState::ClientFirstSent));
+
+        client
+            .process_server_first(&BASE64.encode(RFC7677_SERVER_FIRST))
+            .unwrap();
+        let forged = BASE64.encode(b"v=AAAATRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=");
+        assert_eq!(
+            client.verify_server_final(&forged),
+            Err(ScramError::ServerVerificationFailed)
+        );
+        assert!(matches!(client.state(), ScramState::Failed(_)));
+
+        let mut client = rfc7677_client();
+        let _ = client.client_first_message();
+        client
+            .process_server_first(&BASE64.encode(RFC7677_SERVER_FIRST))
+            .unwrap();
+        let error = BASE64.encode(b"e=invalid-proof");
+        assert!(client.verify_server_final(&error).is_err());
+        assert!(matches!(client.state(), ScramState::Failed(_)));
+    }
+
     #[cfg(feature = "scram")]
     #[test]
     fn test_hi_pbkdf2() {
@@ -6,6 +6,8 @@
 //! This is useful for IRC servers that need to reject or redirect
 //! connections from clients using the wrong protocol.
 
+use std::fmt;
+
 /// Detected protocol type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
@@ -46,6 +48,30 @@ impl DetectedProtocol {
     }
 }
 
+impl fmt::Display for DetectedProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How certain [`detect_protocol`] is about its answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Confidence {
+    /// The input carries an unambiguous protocol signature.
+    Definite,
+    /// The input is too short or too ambiguous to be sure; a server may
+    /// prefer to wait for more bytes before acting on it.
+    Heuristic,
+}
+
+impl Confidence {
+    /// Returns true if the detection is definite.
+    pub fn is_definite(&self) -> bool {
+        matches!(self, Self::Definite)
+    }
+}
+
 /// HTTP method prefixes.
 const HTTP_METHODS: &[&str] = &[
     "GET ", "POST ", "PUT ", "DELETE ", "HEAD ", "OPTIONS ", "PATCH ", "CONNECT ", "TRACE ",
@@ -56,49 +82,74 @@ const SMTP_COMMANDS: &[&str] = &["HELO ", "EHLO ", "MAIL ", "RCPT ", "DATA "];
 
 /// Detect the protocol from the first line of input.
 ///
+/// Returns the detected protocol along with how certain the detection is.
+/// Short inputs that could still grow into another protocol's signature
+/// are reported with [`Confidence::Heuristic`].
+///
 /// # Example
 ///
 /// ```
-/// use slirc_proto::scanner::{detect_protocol, DetectedProtocol};
+/// use slirc_proto::scanner::{detect_protocol, Confidence, DetectedProtocol};
 ///
-/// assert_eq!(detect_protocol("NICK foo"), DetectedProtocol::Irc);
-/// assert_eq!(detect_protocol("GET / HTTP/1.1"), DetectedProtocol::Http);
-/// assert_eq!(detect_protocol("SSH-2.0-OpenSSH"), DetectedProtocol::Ssh);
+/// assert_eq!(detect_protocol("NICK foo"), (DetectedProtocol::Irc, Confidence::Definite));
+/// assert_eq!(detect_protocol("GET / HTTP/1.1").0, DetectedProtocol::Http);
+/// assert_eq!(detect_protocol("SSH-2.0-OpenSSH").0, DetectedProtocol::Ssh);
+/// assert_eq!(detect_protocol("SS"), (DetectedProtocol::Irc, Confidence::Heuristic));
 /// ```
-pub fn detect_protocol(line: &str) -> DetectedProtocol {
+pub fn detect_protocol(line: &str) -> (DetectedProtocol, Confidence) {
+    use Confidence::{Definite, Heuristic};
+
     if line.is_empty() {
-        return DetectedProtocol::Irc; // Empty line, assume IRC
+        return (DetectedProtocol::Irc, Heuristic); // Empty line, assume IRC
     }
 
     // Check for SSH
     if line.starts_with("SSH-") {
-        return DetectedProtocol::Ssh;
+        return (DetectedProtocol::Ssh, Definite);
     }
 
-    // Check for TLS ClientHello (0x16 = handshake record type)
-    if line.as_bytes()[0] == 0x16 {
-        return DetectedProtocol::Tls;
+    // Check for TLS ClientHello (0x16 = handshake record type, then 0x03 = major version)
+    let bytes = line.as_bytes();
+    if bytes[0] == 0x16 {
+        let confidence = if bytes.get(1) == Some(&0x03) {
+            Definite
+        } else {
+            Heuristic
+        };
+        return (DetectedProtocol::Tls, confidence);
     }
 
     // Check for HTTP methods
     if HTTP_METHODS.iter().any(|method| line.starts_with(method)) {
-        return DetectedProtocol::Http;
+        let confidence = if line.contains(" HTTP/") {
+            Definite
+        } else {
+            Heuristic
+        };
+        return (DetectedProtocol::Http, confidence);
     }
 
     // Check for SMTP commands
     if SMTP_COMMANDS.iter().any(|cmd| line.starts_with(cmd)) {
-        return DetectedProtocol::Smtp;
+        return (DetectedProtocol::Smtp, Definite);
     }
 
     // Check for Telnet negotiation (IAC = 0xFF, or replacement char from bad encoding)
-    if let Some(first_char) = line.chars().next() {
-        if first_char == '\u{FFFD}' || line.as_bytes()[0] == 0xFF {
-            return DetectedProtocol::Telnet;
-        }
+    if bytes[0] == 0xFF {
+        return (DetectedProtocol::Telnet, Definite);
+    }
+    if line.starts_with('\u{FFFD}') {
+        return (DetectedProtocol::Telnet, Heuristic);
     }
 
-    // Assume IRC if no other protocol detected
-    DetectedProtocol::Irc
+    // Assume IRC if no other protocol detected, unless the input may be
+    // the start of another protocol's signature
+    let incomplete = std::iter::once("SSH-")
+        .chain(HTTP_METHODS.iter().copied())
+        .chain(SMTP_COMMANDS.iter().copied())
+        .any(|signature| signature.len() > line.len() && signature.starts_with(line));
+    let confidence = if incomplete { Heuristic } else { Definite };
+    (DetectedProtocol::Irc, confidence)
 }
 
 /// Check if a line appears to be a non-IRC protocol.
@@ -115,7 +166,7 @@ pub fn detect_protocol(line: &str) -> DetectedProtocol {
 /// ```
 #[inline]
 pub fn is_non_irc_protocol(line: &str) -> bool {
-    detect_protocol(line).is_non_irc()
+    detect_protocol(line).0.is_non_irc()
 }
 
 #[cfg(test)]
@@ -124,33 +175,36 @@ mod tests {
 
     #[test]
     fn test_detect_irc() {
-        assert_eq!(detect_protocol("NICK foo"), DetectedProtocol::Irc);
+        assert_eq!(detect_protocol("NICK foo").0, DetectedProtocol::Irc);
         assert_eq!(
-            detect_protocol("USER guest 0 * :Real"),
+            detect_protocol("USER guest 0 * :Real").0,
             DetectedProtocol::Irc
         );
-        assert_eq!(detect_protocol("PING :server"), DetectedProtocol::Irc);
-        assert_eq!(detect_protocol(""), DetectedProtocol::Irc);
+        assert_eq!(detect_protocol("PING :server").0, DetectedProtocol::Irc);
+        assert_eq!(detect_protocol("").0, DetectedProtocol::Irc);
     }
 
     #[test]
     fn test_detect_http() {
-        assert_eq!(detect_protocol("GET / HTTP/1.1"), DetectedProtocol::Http);
+        assert_eq!(detect_protocol("GET / HTTP/1.1").0, DetectedProtocol::Http);
         assert_eq!(
-            detect_protocol("POST /api HTTP/1.1"),
+            detect_protocol("POST /api HTTP/1.1").0,
             DetectedProtocol::Http
         );
         assert_eq!(
-            detect_protocol("HEAD /index.html HTTP/1.0"),
+            detect_protocol("HEAD /index.html HTTP/1.0").0,
             DetectedProtocol::Http
         );
     }
 
     #[test]
     fn test_detect_smtp() {
-        assert_eq!(detect_protocol("HELO example.com"), DetectedProtocol::Smtp);
         assert_eq!(
-            detect_protocol("EHLO mail.server.com"),
+            detect_protocol("HELO example.com").0,
+            DetectedProtocol::Smtp
+        );
+        assert_eq!(
+            detect_protocol("EHLO mail.server.com").0,
             DetectedProtocol::Smtp
         );
     }
@@ -158,23 +212,58 @@ mod tests {
     #[test]
     fn test_detect_ssh() {
         assert_eq!(
-            detect_protocol("SSH-2.0-OpenSSH_8.0"),
+            detect_protocol("SSH-2.0-OpenSSH_8.0").0,
             DetectedProtocol::Ssh
         );
-        assert_eq!(detect_protocol("SSH-1.99-PuTTY"), DetectedProtocol::Ssh);
+        assert_eq!(detect_protocol("SSH-1.99-PuTTY").0, DetectedProtocol::Ssh);
     }
 
     #[test]
     fn test_detect_tls() {
         // 0x16 is TLS handshake record type
-        assert_eq!(detect_protocol("\x16\x03\x01"), DetectedProtocol::Tls);
+        assert_eq!(detect_protocol("\x16\x03\x01").0, DetectedProtocol::Tls);
     }
 
     #[test]
     fn test_detect_telnet() {
         // 0xFF is Telnet IAC - use byte string
         let telnet_data = String::from_utf8_lossy(&[0xFF, 0xFD, 0x18]);
-        assert_eq!(detect_protocol(&telnet_data), DetectedProtocol::Telnet);
+        assert_eq!(detect_protocol(&telnet_data).0, DetectedProtocol::Telnet);
+    }
+
+    #[test]
+    fn test_detect_tls_definite() {
+        // Record type 0x16 followed by TLS major version 0x03
+        assert_eq!(
+            detect_protocol("\x16\x03\x01\x02\x00"),
+            (DetectedProtocol::Tls, Confidence::Definite)
+        );
+    }
+
+    #[test]
+    fn test_detect_heuristic_short_buffer() {
+        // A lone 0x16 byte looks like TLS but the version byte hasn't arrived
+        assert_eq!(
+            detect_protocol("\x16"),
+            (DetectedProtocol::Tls, Confidence::Heuristic)
+        );
+        // Could still become "GET " or "SSH-"
+        assert_eq!(
+            detect_protocol("GE"),
+            (DetectedProtocol::Irc, Confidence::Heuristic)
+        );
+        assert_eq!(
+            detect_protocol("SSH"),
+            (DetectedProtocol::Irc, Confidence::Heuristic)
+        );
+        assert_eq!(
+            detect_protocol("GET /"),
+            (DetectedProtocol::Http, Confidence::Heuristic)
+        );
+        assert_eq!(
+            detect_protocol("NICK foo"),
+            (DetectedProtocol::Irc, Confidence::Definite)
+        );
     }
 
     #[test]
@@ -190,4 +279,10 @@ mod tests {
         assert_eq!(DetectedProtocol::Http.as_str(), "HTTP");
         assert_eq!(DetectedProtocol::Ssh.as_str(), "SSH");
     }
+
+    #[test]
+    fn test_protocol_display() {
+        assert_eq!(DetectedProtocol::Tls.to_string(), "TLS/SSL");
+        assert_eq!(format!("{}", DetectedProtocol::Irc), "IRC");
+    }
 }
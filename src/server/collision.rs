@@ -0,0 +1,141 @@
+//! TS6 nickname collision resolution.
+//!
+//! When a server learns of a client (via `UID` or a `NICK` change) whose
+//! nickname is already in use, both sides apply the same deterministic
+//! rules so the network converges without further negotiation:
+//!
+//! - Equal timestamps: both clients are killed.
+//! - Different `user@host`: the older client (lower TS) keeps the nick.
+//! - Same `user@host`: the newer client keeps the nick, since the older
+//!   one is most likely a ghost of the same user reconnecting.
+
+use std::cmp::Ordering;
+
+/// One side of a nickname collision.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NickEntry {
+    /// The colliding nickname.
+    pub nick: String,
+    /// Nickname timestamp (TS) from `UID`/`NICK`.
+    pub ts: u64,
+    /// Username (ident).
+    pub user: String,
+    /// Hostname.
+    pub host: String,
+    /// The client's TS6 UID, used as the target of any resulting `KILL`.
+    pub uid: String,
+}
+
+impl NickEntry {
+    fn same_userhost(&self, other: &NickEntry) -> bool {
+        self.user == other.user && self.host.eq_ignore_ascii_case(&other.host)
+    }
+}
+
+/// Result of [`resolve_nick_collision`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollisionOutcome {
+    /// The local client keeps the nick; kill the remote client.
+    KeepLocal,
+    /// The remote client keeps the nick; kill the local client.
+    KeepRemote,
+    /// Neither can be preferred; kill both.
+    KillBoth,
+}
+
+/// Decide which client keeps a nickname under TS6 collision rules.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::server::{resolve_nick_collision, CollisionOutcome, NickEntry};
+///
+/// let entry = |ts, host: &str, uid: &str| NickEntry {
+///     nick: "alice".to_string(),
+///     ts,
+///     user: "alice".to_string(),
+///     host: host.to_string(),
+///     uid: uid.to_string(),
+/// };
+///
+/// let local = entry(1000, "a.example", "001AAAAAA");
+/// let remote = entry(2000, "b.example", "002AAAAAA");
+/// assert_eq!(resolve_nick_collision(&local, &remote), CollisionOutcome::KeepLocal);
+/// ```
+#[must_use]
+pub fn resolve_nick_collision(local: &NickEntry, remote: &NickEntry) -> CollisionOutcome {
+    let older = match local.ts.cmp(&remote.ts) {
+        Ordering::Equal => return CollisionOutcome::KillBoth,
+        Ordering::Less => CollisionOutcome::KeepLocal,
+        Ordering::Greater => CollisionOutcome::KeepRemote,
+    };
+
+    if !local.same_userhost(remote) {
+        return older;
+    }
+
+    // Same user@host: the newer connection wins
+    match older {
+        CollisionOutcome::KeepLocal => CollisionOutcome::KeepRemote,
+        _ => CollisionOutcome::KeepLocal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ts: u64, user: &str, host: &str, uid: &str) -> NickEntry {
+        NickEntry {
+            nick: "alice".to_string(),
+            ts,
+            user: user.to_string(),
+            host: host.to_string(),
+            uid: uid.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_older_wins_different_userhost() {
+        let local = entry(1000, "alice", "a.example", "001AAAAAA");
+        let remote = entry(2000, "bob", "b.example", "002AAAAAA");
+        assert_eq!(
+            resolve_nick_collision(&local, &remote),
+            CollisionOutcome::KeepLocal
+        );
+        assert_eq!(
+            resolve_nick_collision(&remote, &local),
+            CollisionOutcome::KeepRemote
+        );
+    }
+
+    #[test]
+    fn test_newer_wins_same_userhost() {
+        let local = entry(1000, "alice", "a.example", "001AAAAAA");
+        let remote = entry(2000, "alice", "A.Example", "002AAAAAA");
+        assert_eq!(
+            resolve_nick_collision(&local, &remote),
+            CollisionOutcome::KeepRemote
+        );
+        assert_eq!(
+            resolve_nick_collision(&remote, &local),
+            CollisionOutcome::KeepLocal
+        );
+    }
+
+    #[test]
+    fn test_equal_ts_kills_both() {
+        let local = entry(1000, "alice", "a.example", "001AAAAAA");
+        let remote = entry(1000, "bob", "b.example", "002AAAAAA");
+        assert_eq!(
+            resolve_nick_collision(&local, &remote),
+            CollisionOutcome::KillBoth
+        );
+
+        let remote = entry(1000, "alice", "a.example", "002AAAAAA");
+        assert_eq!(
+            resolve_nick_collision(&local, &remote),
+            CollisionOutcome::KillBoth
+        );
+    }
+}
@@ -0,0 +1,9 @@
+//! Server-to-server (linking) helpers.
+//!
+//! These build on the TS6 command variants (`UID`, `SID`, `SJOIN`, ...) to
+//! implement the decisions a linked server has to make itself, such as
+//! which client survives a nickname collision.
+
+mod collision;
+
+pub use collision::{resolve_nick_collision, CollisionOutcome, NickEntry};
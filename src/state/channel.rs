@@ -0,0 +1,473 @@
+//! Channel membership and topic tracking.
+//!
+//! [`ChannelState`] follows a single channel through its lifecycle: the
+//! `RPL_NAMREPLY` burst sent on join, incremental JOIN/PART/KICK/QUIT/NICK
+//! traffic, status mode changes, and the topic and creation time numerics.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::casemap::{irc_eq_with, irc_to_lower_with, CaseMapping};
+use crate::ircv3::parse_server_time;
+use crate::isupport::{ChanModes, PrefixSpec};
+use crate::message::MessageRef;
+use crate::response::{parse_creation_time, parse_topic_who_time, Response};
+
+/// A member of a channel tracked by [`ChannelState`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelMember {
+    /// The member's nickname, as last seen.
+    pub nick: String,
+    /// Status prefixes held by the member, highest rank first (e.g. `@+`).
+    pub prefixes: String,
+}
+
+impl ChannelMember {
+    /// Get the member's highest-ranked status prefix, if any.
+    #[must_use]
+    pub fn highest_prefix(&self) -> Option<char> {
+        self.prefixes.chars().next()
+    }
+
+    /// Check whether the member holds a status prefix (e.g. `@`).
+    #[must_use]
+    pub fn has_prefix(&self, prefix: char) -> bool {
+        self.prefixes.contains(prefix)
+    }
+}
+
+/// Sans-IO model of a joined channel's members and topic.
+///
+/// Feed every incoming message to [`feed`](Self::feed); messages about other
+/// channels are ignored. Nicknames are keyed with the server's casemapping
+/// (RFC 1459 unless [`set_casemapping`](Self::set_casemapping) is called),
+/// and status prefixes are ranked by the server's `PREFIX` token.
+///
+/// When the client itself parts or is kicked, the state is left as-is; drop
+/// it (or start a fresh one on rejoin).
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::isupport::PrefixSpec;
+/// use slirc_proto::state::ChannelState;
+/// use slirc_proto::MessageRef;
+///
+/// let prefix = PrefixSpec::parse("(ov)@+").unwrap();
+/// let mut channel = ChannelState::new("#rust", prefix);
+///
+/// for raw in [
+///     ":srv 353 me = #rust :@alice +bob me",
+///     ":srv 366 me #rust :End of /NAMES list.",
+///     ":alice!a@host MODE #rust +o bob",
+/// ] {
+///     channel.feed(&MessageRef::parse(raw).unwrap());
+/// }
+///
+/// assert_eq!(channel.member_count(), 3);
+/// assert_eq!(channel.member("BOB").unwrap().prefixes, "@+");
+/// ```
+#[derive(Clone, Debug)]
+pub struct ChannelState {
+    name: String,
+    /// Status mode characters from `PREFIX`, highest rank first.
+    status_modes: String,
+    /// Status prefix symbols matching `status_modes`.
+    status_prefixes: String,
+    /// Non-status modes that always take a parameter (CHANMODES types A and B).
+    param_modes: String,
+    /// Non-status modes that take a parameter only when set (CHANMODES type C).
+    set_param_modes: String,
+    /// Casemapping used for member keys and channel name comparison.
+    casemapping: CaseMapping,
+    members: HashMap<String, ChannelMember>,
+    /// Whether an `RPL_NAMREPLY` burst is in progress.
+    in_names: bool,
+    topic: Option<String>,
+    topic_setter: Option<String>,
+    topic_set_at: Option<SystemTime>,
+    created_at: Option<SystemTime>,
+}
+
+impl ChannelState {
+    /// Create the state for `name`, ranking status prefixes by `prefix`.
+    #[must_use]
+    pub fn new<N: Into<String>>(name: N, prefix: PrefixSpec<'_>) -> Self {
+        Self {
+            name: name.into(),
+            status_modes: prefix.modes.to_string(),
+            status_prefixes: prefix.prefixes.to_string(),
+            param_modes: "beIk".to_string(),
+            set_param_modes: "l".to_string(),
+            casemapping: CaseMapping::Rfc1459,
+            members: HashMap::new(),
+            in_names: false,
+            topic: None,
+            topic_setter: None,
+            topic_set_at: None,
+            created_at: None,
+        }
+    }
+
+    /// Use the server's `CHANMODES` token to skip parameters of non-status
+    /// modes in MODE changes.
+    ///
+    /// Defaults to `beI,k,l,...` when not set.
+    pub fn set_chanmodes(&mut self, chanmodes: ChanModes<'_>) {
+        self.param_modes = format!("{}{}", chanmodes.a, chanmodes.b);
+        self.set_param_modes = chanmodes.c.to_string();
+    }
+
+    /// Use the server's `CASEMAPPING` for nickname and channel comparisons.
+    ///
+    /// Defaults to `rfc1459` when not set. Members already tracked are
+    /// re-keyed under the new mapping.
+    pub fn set_casemapping(&mut self, casemapping: CaseMapping) {
+        self.casemapping = casemapping;
+        self.members = std::mem::take(&mut self.members)
+            .into_values()
+            .map(|member| (irc_to_lower_with(&member.nick, casemapping), member))
+            .collect();
+    }
+
+    /// Get the channel name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the current topic, if one is set.
+    #[must_use]
+    pub fn topic(&self) -> Option<&str> {
+        self.topic.as_deref()
+    }
+
+    /// Get the nickname or hostmask that set the topic, if known.
+    #[must_use]
+    pub fn topic_setter(&self) -> Option<&str> {
+        self.topic_setter.as_deref()
+    }
+
+    /// Get when the topic was set, if known.
+    #[must_use]
+    pub fn topic_set_at(&self) -> Option<SystemTime> {
+        self.topic_set_at
+    }
+
+    /// Get when the channel was created (`RPL_CREATIONTIME`), if known.
+    #[must_use]
+    pub fn created_at(&self) -> Option<SystemTime> {
+        self.created_at
+    }
+
+    /// Look up a member by nickname (case-insensitive).
+    #[must_use]
+    pub fn member(&self, nick: &str) -> Option<&ChannelMember> {
+        self.members.get(&self.key(nick))
+    }
+
+    /// Check whether a nickname is in the channel.
+    #[must_use]
+    pub fn is_member(&self, nick: &str) -> bool {
+        self.members.contains_key(&self.key(nick))
+    }
+
+    /// Iterate over all members, in no particular order.
+    pub fn members(&self) -> impl Iterator<Item = &ChannelMember> {
+        self.members.values()
+    }
+
+    /// Number of members in the channel.
+    #[must_use]
+    pub fn member_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Feed an incoming message.
+    ///
+    /// Returns `true` if the message updated this channel's state.
+    pub fn feed(&mut self, msg: &MessageRef<'_>) -> bool {
+        if let Some(response) = msg.numeric_code().and_then(Response::from_code) {
+            return self.feed_numeric(response, msg.args());
+        }
+
+        let args = msg.args();
+        let source = msg.source_nickname();
+        match msg.command_name() {
+            name if name.eq_ignore_ascii_case("JOIN") => match (source, args.first()) {
+                (Some(nick), Some(channel)) if self.is_channel(channel) => {
+                    self.add_member(nick, String::new());
+                    true
+                }
+                _ => false,
+            },
+            name if name.eq_ignore_ascii_case("PART") => match (source, args.first()) {
+                (Some(nick), Some(channels)) if channels.split(',').any(|c| self.is_channel(c)) => {
+                    self.members.remove(&self.key(nick)).is_some()
+                }
+                _ => false,
+            },
+            name if name.eq_ignore_ascii_case("KICK") => match args {
+                [channel, nicks, ..] if self.is_channel(channel) => {
+                    let mut changed = false;
+                    for nick in nicks.split(',') {
+                        changed |= self.members.remove(&self.key(nick)).is_some();
+                    }
+                    changed
+                }
+                _ => false,
+            },
+            name if name.eq_ignore_ascii_case("QUIT") => {
+                source.is_some_and(|nick| self.members.remove(&self.key(nick)).is_some())
+            }
+            name if name.eq_ignore_ascii_case("NICK") => match (source, args.first()) {
+                (Some(old), Some(new)) => match self.members.remove(&self.key(old)) {
+                    Some(member) => {
+                        self.add_member(new, member.prefixes);
+                        true
+                    }
+                    None => false,
+                },
+                _ => false,
+            },
+            name if name.eq_ignore_ascii_case("MODE") => match args {
+                [channel, modes, params @ ..] if self.is_channel(channel) => {
+                    self.apply_modes(modes, params)
+                }
+                _ => false,
+            },
+            name if name.eq_ignore_ascii_case("TOPIC") => match args {
+                [channel, topic] if self.is_channel(channel) => {
+                    self.topic = Some(topic.to_string()).filter(|t| !t.is_empty());
+                    self.topic_setter = msg.raw_prefix().map(str::to_string);
+                    self.topic_set_at = msg.server_time().and_then(server_time_to_system);
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn feed_numeric(&mut self, response: Response, args: &[&str]) -> bool {
+        match response {
+            // <client> [<symbol>] <channel> :<names>
+            Response::RPL_NAMREPLY => match args {
+                [.., channel, names] if args.len() >= 3 && self.is_channel(channel) => {
+                    if !self.in_names {
+                        self.members.clear();
+                        self.in_names = true;
+                    }
+                    for entry in names.split_ascii_whitespace() {
+                        self.add_names_entry(entry);
+                    }
+                    true
+                }
+                _ => false,
+            },
+            Response::RPL_ENDOFNAMES => match args.get(1) {
+                Some(channel) if self.is_channel(channel) => {
+                    self.in_names = false;
+                    true
+                }
+                _ => false,
+            },
+            Response::RPL_TOPIC => match args {
+                [_, channel, topic, ..] if self.is_channel(channel) => {
+                    self.topic = Some(topic.to_string());
+                    true
+                }
+                _ => false,
+            },
+            Response::RPL_NOTOPIC => match args.get(1) {
+                Some(channel) if self.is_channel(channel) => {
+                    self.topic = None;
+                    self.topic_setter = None;
+                    self.topic_set_at = None;
+                    true
+                }
+                _ => false,
+            },
+            Response::RPL_TOPICWHOTIME => match parse_topic_who_time(args) {
+                Some(info) if self.is_channel(info.channel) => {
+                    self.topic_setter = Some(info.setter.to_string());
+                    self.topic_set_at = Some(info.set_at);
+                    true
+                }
+                _ => false,
+            },
+            Response::RPL_CREATIONTIME => match parse_creation_time(args) {
+                Some((channel, created)) if self.is_channel(channel) => {
+                    self.created_at = Some(created);
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn is_channel(&self, channel: &str) -> bool {
+        irc_eq_with(channel, &self.name, self.casemapping)
+    }
+
+    /// Member key for a nickname under the channel's casemapping.
+    fn key(&self, nick: &str) -> String {
+        irc_to_lower_with(nick, self.casemapping)
+    }
+
+    fn add_member(&mut self, nick: &str, prefixes: String) {
+        self.members.insert(
+            self.key(nick),
+            ChannelMember {
+                nick: nick.to_string(),
+                prefixes,
+            },
+        );
+    }
+
+    /// Add a `[prefixes]nick[!user@host]` entry from `RPL_NAMREPLY`.
+    fn add_names_entry(&mut self, entry: &str) {
+        let (prefixes, rest) = self.prefix_spec().split_all_prefixes(entry);
+        let nick = rest.split('!').next().unwrap_or(rest);
+        if nick.is_empty() {
+            return;
+        }
+        let prefixes = self.ranked(|p| prefixes.contains(&p));
+        self.add_member(nick, prefixes);
+    }
+
+    /// Apply the status changes in a MODE modestring and its parameters.
+    fn apply_modes(&mut self, modes: &str, params: &[&str]) -> bool {
+        let mut params = params.iter();
+        let mut adding = true;
+        let mut changed = false;
+
+        for c in modes.chars() {
+            match c {
+                '+' => adding = true,
+                '-' => adding = false,
+                _ => {
+                    if self.prefix_spec().is_prefix_mode(c) {
+                        let Some(nick) = params.next() else { continue };
+                        if let Some(prefix) = self.prefix_spec().prefix_for_mode(c) {
+                            changed |= self.set_prefix(nick, prefix, adding);
+                        }
+                    } else if self.param_modes.contains(c)
+                        || (adding && self.set_param_modes.contains(c))
+                    {
+                        params.next();
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    fn set_prefix(&mut self, nick: &str, prefix: char, adding: bool) -> bool {
+        let Some(current) = self.members.get(&self.key(nick)).map(|m| &m.prefixes) else {
+            return false;
+        };
+        let prefixes = if adding {
+            self.ranked(|p| p == prefix || current.contains(p))
+        } else {
+            self.ranked(|p| p != prefix && current.contains(p))
+        };
+        match self.members.get_mut(&self.key(nick)) {
+            Some(member) => {
+                member.prefixes = prefixes;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Collect the status prefixes matching `keep`, highest rank first.
+    fn ranked(&self, keep: impl Fn(char) -> bool) -> String {
+        self.status_prefixes.chars().filter(|&p| keep(p)).collect()
+    }
+
+    fn prefix_spec(&self) -> PrefixSpec<'_> {
+        PrefixSpec {
+            modes: &self.status_modes,
+            prefixes: &self.status_prefixes,
+        }
+    }
+}
+
+/// Convert an IRCv3 `time` tag to a [`SystemTime`].
+fn server_time_to_system(ts: &str) -> Option<SystemTime> {
+    let nanos = u64::try_from(parse_server_time(ts)).ok()?;
+    (nanos > 0).then(|| UNIX_EPOCH + Duration::from_nanos(nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(lines: &[&str]) -> ChannelState {
+        let prefix = PrefixSpec::parse("(qaohv)~&@%+").unwrap();
+        let mut state = ChannelState::new("#Rust", prefix);
+        for raw in lines {
+            state.feed(&MessageRef::parse(raw).unwrap());
+        }
+        state
+    }
+
+    #[test]
+    fn test_names_multi_prefix_and_userhost() {
+        let state = channel(&[
+            ":srv 353 me = #rust :+@alice ~bob!b@host carol",
+            ":srv 366 me #rust :End of /NAMES list.",
+        ]);
+        assert_eq!(state.member_count(), 3);
+        assert_eq!(state.member("alice").unwrap().prefixes, "@+");
+        assert_eq!(state.member("Bob").unwrap().nick, "bob");
+        assert_eq!(state.member("bob").unwrap().highest_prefix(), Some('~'));
+        assert_eq!(state.member("carol").unwrap().highest_prefix(), None);
+    }
+
+    #[test]
+    fn test_names_refresh_replaces_members() {
+        let mut state = channel(&[
+            ":srv 353 me = #rust :alice bob",
+            ":srv 366 me #rust :End of /NAMES list.",
+        ]);
+        state.feed(&MessageRef::parse(":srv 353 me = #rust :alice").unwrap());
+        assert_eq!(state.member_count(), 1);
+        assert!(!state.is_member("bob"));
+    }
+
+    #[test]
+    fn test_mode_skips_non_status_params() {
+        let mut state = channel(&[":srv 353 me = #rust :alice bob"]);
+        let raw = ":op MODE #rust +kbo-l key *!*@spam bob";
+        assert!(state.feed(&MessageRef::parse(raw).unwrap()));
+        assert!(state.member("bob").unwrap().has_prefix('@'));
+        assert!(!state.member("alice").unwrap().has_prefix('@'));
+    }
+
+    #[test]
+    fn test_other_channel_ignored() {
+        let mut state = channel(&[]);
+        assert!(!state.feed(&MessageRef::parse(":alice!a@h JOIN #other").unwrap()));
+        assert!(!state.feed(&MessageRef::parse(":srv 332 me #other :topic").unwrap()));
+        assert_eq!(state.member_count(), 0);
+        assert_eq!(state.topic(), None);
+    }
+
+    #[test]
+    fn test_ascii_casemapping_keeps_brackets_distinct() {
+        let mut state = channel(&[":srv 353 me = #rust :nick["]);
+        assert!(state.is_member("NICK{"));
+
+        state.set_casemapping(CaseMapping::Ascii);
+        assert!(state.is_member("NICK["));
+        assert!(!state.is_member("nick{"));
+
+        state.feed(&MessageRef::parse(":nick{!u@h JOIN #rust").unwrap());
+        assert_eq!(state.member_count(), 2);
+        state.feed(&MessageRef::parse(":nick{!u@h QUIT :bye").unwrap());
+        assert_eq!(state.member("nick[").unwrap().nick, "nick[");
+    }
+}
@@ -0,0 +1,109 @@
+//! Runtime gating of capability-dependent message features.
+
+use std::collections::HashSet;
+
+use crate::caps::Capability;
+
+/// Snapshot of the capabilities negotiated during the handshake.
+///
+/// Higher-level code should consult these predicates before using a feature
+/// that the server only accepts once the matching capability has been
+/// ACKed, such as sending `TAGMSG` or attaching a `label` tag.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::state::CapGuard;
+///
+/// let guard = CapGuard::new(["message-tags", "server-time"]);
+/// assert!(guard.can_tagmsg());
+/// assert!(!guard.can_label());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CapGuard {
+    caps: HashSet<String>,
+}
+
+impl CapGuard {
+    /// Create a guard from a set of enabled capability names.
+    #[must_use]
+    pub fn new<I, S>(caps: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            caps: caps.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Check whether a capability was negotiated.
+    #[must_use]
+    pub fn has(&self, cap: &Capability) -> bool {
+        self.caps.contains(cap.as_ref())
+    }
+
+    /// Whether client-only tags and `TAGMSG` may be sent (`message-tags`).
+    #[must_use]
+    pub fn can_tagmsg(&self) -> bool {
+        self.has(&Capability::MessageTags)
+    }
+
+    /// Whether outgoing commands may carry a `label` tag (`labeled-response`).
+    #[must_use]
+    pub fn can_label(&self) -> bool {
+        self.has(&Capability::LabeledResponse)
+    }
+
+    /// Whether multiline batches may be sent (`draft/multiline` and `batch`).
+    #[must_use]
+    pub fn can_multiline(&self) -> bool {
+        self.has(&Capability::Multiline) && self.has(&Capability::Batch)
+    }
+
+    /// Whether incoming messages carry a `time` tag (`server-time`).
+    #[must_use]
+    pub fn has_server_time(&self) -> bool {
+        self.has(&Capability::ServerTime)
+    }
+
+    /// Whether the server echoes our own messages back (`echo-message`).
+    #[must_use]
+    pub fn has_echo_message(&self) -> bool {
+        self.has(&Capability::EchoMessage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_tags_without_labeled_response() {
+        let guard = CapGuard::new(["message-tags", "batch"]);
+        assert!(guard.can_tagmsg());
+        assert!(!guard.can_label());
+        assert!(!guard.can_multiline());
+        assert!(!guard.has_server_time());
+    }
+
+    #[test]
+    fn test_multiline_requires_batch() {
+        assert!(!CapGuard::new(["draft/multiline"]).can_multiline());
+        assert!(CapGuard::new(["draft/multiline", "batch"]).can_multiline());
+    }
+
+    #[test]
+    fn test_custom_capability() {
+        let guard = CapGuard::new(["vendor/thing"]);
+        assert!(guard.has(&Capability::from("vendor/thing")));
+        assert!(!guard.can_tagmsg());
+    }
+
+    #[test]
+    fn test_default_is_empty() {
+        let guard = CapGuard::default();
+        assert!(!guard.can_tagmsg());
+        assert!(!guard.has_echo_message());
+    }
+}
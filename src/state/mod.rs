@@ -24,6 +24,8 @@
 //!     password: None,
 //!     request_caps: vec!["multi-prefix".to_string(), "sasl".to_string()],
 //!     sasl_credentials: None,
+//!     post_register: vec![],
+//!     auto_join: vec![],
 //! };
 //!
 //! let mut machine = HandshakeMachine::new(config);
@@ -40,10 +42,18 @@
 //! // Process actions...
 //! ```
 
+mod channel;
+mod guard;
+mod outgoing;
 mod sync;
 mod tracker;
+mod user_modes;
 
+pub use channel::{ChannelMember, ChannelState};
+pub use guard::CapGuard;
+pub use outgoing::{Correlation, CorrelationSource, OutgoingTracker};
 pub use tracker::HandshakeMachine;
+pub use user_modes::UserModeState;
 
 use crate::Message;
 
@@ -82,6 +92,20 @@ pub struct HandshakeConfig {
     pub request_caps: Vec<String>,
     /// SASL credentials, if SASL authentication is desired.
     pub sasl_credentials: Option<SaslCredentials>,
+    /// Messages to send once registered (e.g. AWAY, JOIN, MODE +i).
+    ///
+    /// Emitted as [`HandshakeAction::Send`] right after
+    /// [`HandshakeAction::Complete`], in order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub post_register: Vec<Message>,
+    /// Channels to join once registered, each with an optional key.
+    ///
+    /// The JOINs are emitted at the end of the MOTD, once `RPL_ISUPPORT` has
+    /// been seen, so they can be batched within the server's `TARGMAX` and
+    /// `CHANLIMIT` as well as the line length limit. Channels over the
+    /// `CHANLIMIT` are reported with [`HandshakeAction::AutoJoinSkipped`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_join: Vec<(String, Option<String>)>,
 }
 
 /// SASL authentication credentials.
@@ -105,6 +129,16 @@ pub enum HandshakeAction {
     Send(Box<Message>),
     /// Connection is complete, proceed to normal operation.
     Complete,
+    /// The `RPL_ISUPPORT` (005) burst has ended.
+    ///
+    /// Emitted once, on the first other numeric after the run of 005 replies,
+    /// so the caller knows its ISUPPORT view is final.
+    IsupportComplete,
+    /// Auto-join channels that were not joined because they exceed the
+    /// server's `CHANLIMIT`.
+    ///
+    /// Emitted after the JOIN lines, in configuration order.
+    AutoJoinSkipped(Vec<String>),
     /// An error occurred during handshake.
     Error(HandshakeError),
 }
@@ -190,6 +224,8 @@ mod tests {
             password: None,
             request_caps: vec!["multi-prefix".to_string()],
             sasl_credentials: None,
+            post_register: vec![],
+            auto_join: vec![],
         };
         assert_eq!(config.nickname, "testnick");
         assert_eq!(config.username, "testuser");
@@ -208,6 +244,8 @@ mod tests {
             password: Some("secret".to_string()),
             request_caps: vec![],
             sasl_credentials: None,
+            post_register: vec![],
+            auto_join: vec![],
         };
         assert_eq!(config.password, Some("secret".to_string()));
     }
@@ -225,6 +263,8 @@ mod tests {
             password: None,
             request_caps: vec!["sasl".to_string()],
             sasl_credentials: Some(creds),
+            post_register: vec![],
+            auto_join: vec![],
         };
         assert!(config.sasl_credentials.is_some());
         let creds = config.sasl_credentials.unwrap();
@@ -0,0 +1,282 @@
+//! Correlation of outgoing messages with server confirmations.
+//!
+//! Combines `labeled-response` and `echo-message`: every tracked message is
+//! given a `label` tag, and the server's answer (an echo of the message, an
+//! `ACK`, or any other labeled reply) is matched back to it. When the server
+//! echoes without a label, the echo is matched by target and text instead.
+
+use std::collections::VecDeque;
+
+use crate::casemap::irc_eq;
+use crate::command::Command;
+use crate::message::{Message, MessageRef};
+
+/// How a tracked message was correlated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CorrelationSource {
+    /// The server echoed the message back (`echo-message`).
+    Echo,
+    /// The server sent an `ACK` carrying the label.
+    Ack,
+    /// The server sent another reply carrying the label (a numeric,
+    /// `FAIL`, or the start of a labeled batch).
+    Response,
+}
+
+/// A server reply matched to a message sent through [`OutgoingTracker`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Correlation {
+    /// The label assigned when the message was tracked.
+    pub label: String,
+    /// What kind of reply completed the correlation.
+    pub source: CorrelationSource,
+    /// Whether the server accepted the message.
+    ///
+    /// `false` when the labeled reply is an error numeric or `FAIL`.
+    pub confirmed: bool,
+}
+
+/// A message awaiting correlation.
+#[derive(Clone, Debug)]
+struct Pending {
+    label: String,
+    /// `(target, text)` for PRIVMSG/NOTICE, used to match unlabeled echoes.
+    echo_key: Option<(String, String)>,
+}
+
+/// Sans-IO tracker assigning labels to outgoing messages and reporting
+/// when the server confirms them.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::state::{CorrelationSource, OutgoingTracker};
+/// use slirc_proto::{Message, MessageRef};
+///
+/// let mut tracker = OutgoingTracker::new("me");
+/// let msg = tracker.track(Message::privmsg("#rust", "hello"));
+/// let label = msg.label().unwrap().to_string();
+///
+/// let echo = format!("@label={} :me!u@h PRIVMSG #rust :hello", label);
+/// let correlation = tracker.feed(&MessageRef::parse(&echo).unwrap()).unwrap();
+/// assert_eq!(correlation.label, label);
+/// assert_eq!(correlation.source, CorrelationSource::Echo);
+/// assert!(correlation.confirmed);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OutgoingTracker {
+    nickname: String,
+    next_label: u64,
+    pending: VecDeque<Pending>,
+}
+
+impl OutgoingTracker {
+    /// Create a tracker for a client currently using `nickname`.
+    #[must_use]
+    pub fn new<N: Into<String>>(nickname: N) -> Self {
+        Self {
+            nickname: nickname.into(),
+            next_label: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Update our nickname after a NICK change, so unlabeled echoes still match.
+    pub fn set_nickname<N: Into<String>>(&mut self, nickname: N) {
+        self.nickname = nickname.into();
+    }
+
+    /// Number of tracked messages not yet correlated.
+    #[must_use]
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Check whether a label is still awaiting correlation.
+    #[must_use]
+    pub fn is_pending(&self, label: &str) -> bool {
+        self.pending.iter().any(|p| p.label == label)
+    }
+
+    /// Assign a fresh label to an outgoing message and start tracking it.
+    ///
+    /// Returns the message with its `label` tag set; read it back with
+    /// [`Message::label`].
+    #[must_use]
+    pub fn track(&mut self, msg: Message) -> Message {
+        self.next_label += 1;
+        let label = format!("sl{}", self.next_label);
+
+        let echo_key = match msg.command {
+            Command::PRIVMSG(ref target, ref text) | Command::NOTICE(ref target, ref text) => {
+                Some((target.clone(), text.clone()))
+            }
+            _ => None,
+        };
+        self.pending.push_back(Pending {
+            label: label.clone(),
+            echo_key,
+        });
+
+        msg.with_tag("label", Some(label))
+    }
+
+    /// Feed an incoming message.
+    ///
+    /// Returns a [`Correlation`] if the message completes a tracked send.
+    pub fn feed(&mut self, msg: &MessageRef<'_>) -> Option<Correlation> {
+        match msg.label() {
+            Some(label) => self.feed_labeled(msg, label),
+            None => self.feed_unlabeled(msg),
+        }
+    }
+
+    fn feed_labeled(&mut self, msg: &MessageRef<'_>, label: &str) -> Option<Correlation> {
+        let index = self.pending.iter().position(|p| p.label == label)?;
+
+        let (source, confirmed) = if self.is_own_echo(msg) {
+            (CorrelationSource::Echo, true)
+        } else if msg.command_eq("ACK") {
+            (CorrelationSource::Ack, true)
+        } else {
+            let failed = msg.command_eq("FAIL")
+                || msg
+                    .numeric_code()
+                    .is_some_and(|code| (400..600).contains(&code));
+            (CorrelationSource::Response, !failed)
+        };
+
+        let pending = self.pending.remove(index)?;
+        Some(Correlation {
+            label: pending.label,
+            source,
+            confirmed,
+        })
+    }
+
+    fn feed_unlabeled(&mut self, msg: &MessageRef<'_>) -> Option<Correlation> {
+        if !self.is_own_echo(msg) {
+            return None;
+        }
+        let (target, text) = (msg.arg(0)?, msg.arg(1)?);
+
+        // Oldest matching send first: echoes arrive in send order
+        let index = self.pending.iter().position(|p| {
+            p.echo_key
+                .as_ref()
+                .is_some_and(|(t, x)| irc_eq(t, target) && x == text)
+        })?;
+
+        let pending = self.pending.remove(index)?;
+        Some(Correlation {
+            label: pending.label,
+            source: CorrelationSource::Echo,
+            confirmed: true,
+        })
+    }
+
+    /// Whether this is a PRIVMSG/NOTICE sent by us.
+    fn is_own_echo(&self, msg: &MessageRef<'_>) -> bool {
+        (msg.is_privmsg() || msg.is_notice())
+            && msg
+                .source_nickname()
+                .is_some_and(|nick| irc_eq(nick, &self.nickname))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(tracker: &mut OutgoingTracker, raw: &str) -> Option<Correlation> {
+        tracker.feed(&MessageRef::parse(raw).unwrap())
+    }
+
+    #[test]
+    fn test_track_assigns_unique_labels() {
+        let mut tracker = OutgoingTracker::new("me");
+        let a = tracker.track(Message::privmsg("#rust", "one"));
+        let b = tracker.track(Message::join("#rust"));
+
+        assert_ne!(a.label(), b.label());
+        assert_eq!(tracker.pending_count(), 2);
+        assert!(tracker.is_pending(a.label().unwrap()));
+    }
+
+    #[test]
+    fn test_labeled_echo() {
+        let mut tracker = OutgoingTracker::new("me");
+        let msg = tracker.track(Message::privmsg("#rust", "hello"));
+        let label = msg.label().unwrap().to_string();
+
+        // Someone else's message is not a correlation
+        assert!(feed(&mut tracker, ":other!u@h PRIVMSG #rust :hello").is_none());
+
+        let raw = format!("@label={} :Me!u@h PRIVMSG #rust :hello", label);
+        let correlation = feed(&mut tracker, &raw).unwrap();
+        assert_eq!(
+            correlation,
+            Correlation {
+                label,
+                source: CorrelationSource::Echo,
+                confirmed: true,
+            }
+        );
+        assert_eq!(tracker.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_unlabeled_echo_matches_in_order() {
+        let mut tracker = OutgoingTracker::new("me");
+        let first = tracker.track(Message::privmsg("#rust", "same"));
+        let second = tracker.track(Message::privmsg("#rust", "same"));
+
+        let correlation = feed(&mut tracker, ":me!u@h PRIVMSG #RUST :same").unwrap();
+        assert_eq!(Some(correlation.label.as_str()), first.label());
+        let correlation = feed(&mut tracker, ":me!u@h PRIVMSG #rust :same").unwrap();
+        assert_eq!(Some(correlation.label.as_str()), second.label());
+        assert!(feed(&mut tracker, ":me!u@h PRIVMSG #rust :same").is_none());
+    }
+
+    #[test]
+    fn test_echo_after_nick_change() {
+        let mut tracker = OutgoingTracker::new("me");
+        let _ = tracker.track(Message::notice("bob", "hi"));
+        tracker.set_nickname("newme");
+
+        let correlation = feed(&mut tracker, ":newme!u@h NOTICE bob :hi").unwrap();
+        assert_eq!(correlation.source, CorrelationSource::Echo);
+    }
+
+    #[test]
+    fn test_labeled_ack() {
+        let mut tracker = OutgoingTracker::new("me");
+        let msg = tracker.track(Message::privmsg("#rust", "hello"));
+        let label = msg.label().unwrap().to_string();
+
+        // Unknown labels are ignored
+        assert!(feed(&mut tracker, "@label=other :irc.test ACK").is_none());
+
+        let correlation = feed(&mut tracker, &format!("@label={} :irc.test ACK", label)).unwrap();
+        assert_eq!(correlation.label, label);
+        assert_eq!(correlation.source, CorrelationSource::Ack);
+        assert!(correlation.confirmed);
+        assert!(!tracker.is_pending(&label));
+    }
+
+    #[test]
+    fn test_labeled_error_not_confirmed() {
+        let mut tracker = OutgoingTracker::new("me");
+        let msg = tracker.track(Message::privmsg("#secret", "hello"));
+        let label = msg.label().unwrap();
+
+        let raw = format!(
+            "@label={} :irc.test 404 me #secret :Cannot send to channel",
+            label
+        );
+        let correlation = feed(&mut tracker, &raw).unwrap();
+        assert_eq!(correlation.source, CorrelationSource::Response);
+        assert!(!correlation.confirmed);
+    }
+}
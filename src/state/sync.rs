@@ -1,7 +1,17 @@
 //! State transition handlers for handshake phases.
 
+use std::collections::HashMap;
+
+use crate::caps::build_cap_req;
 use crate::command::Command;
+use crate::isupport::Isupport;
 use crate::message::MessageRef;
+use crate::sasl::{
+    authenticate_messages, choose_mechanism, encode_external, encode_plain, parse_mechanisms,
+    SaslMechanism,
+};
+use crate::util::MAX_MESSAGE_BODY;
+use crate::Message;
 
 use super::tracker::HandshakeMachine;
 use super::{ConnectionState, HandshakeAction, HandshakeError};
@@ -44,17 +54,8 @@ impl HandshakeMachine {
                         .cloned()
                         .collect();
 
-                    if !to_request.is_empty() {
-                        let caps_str = to_request.join(" ");
-                        actions.push(HandshakeAction::Send(Box::new(
-                            Command::CAP(
-                                None,
-                                crate::command::CapSubCommand::REQ,
-                                None,
-                                Some(caps_str),
-                            )
-                            .into(),
-                        )));
+                    if let Some(req) = build_cap_req(&to_request) {
+                        actions.push(HandshakeAction::Send(Box::new(req)));
                     } else {
                         // No caps to request, proceed to registration
                         actions.extend(self.finish_cap_negotiation());
@@ -74,9 +75,7 @@ impl HandshakeMachine {
                     if self.enabled_caps.contains("sasl") && self.config.sasl_credentials.is_some()
                     {
                         self.state = ConnectionState::Authenticating;
-                        actions.push(HandshakeAction::Send(Box::new(
-                            Command::AUTHENTICATE("PLAIN".to_string()).into(),
-                        )));
+                        actions.push(self.start_sasl(SaslMechanism::Plain));
                     } else {
                         actions.extend(self.finish_cap_negotiation());
                     }
@@ -105,11 +104,19 @@ impl HandshakeMachine {
                 let param = msg.arg(0).unwrap_or("");
                 if param == "+" {
                     // Server ready for SASL payload
-                    if let Some(ref creds) = self.config.sasl_credentials {
-                        let payload = crate::sasl::encode_plain(&creds.account, &creds.password);
-                        actions.push(HandshakeAction::Send(Box::new(
-                            Command::AUTHENTICATE(payload).into(),
-                        )));
+                    let payload = match (&self.sasl_mechanism, &self.config.sasl_credentials) {
+                        (SaslMechanism::Plain, Some(creds)) => {
+                            Some(encode_plain(&creds.account, &creds.password))
+                        }
+                        (SaslMechanism::External, _) => Some(encode_external(None)),
+                        _ => None,
+                    };
+                    if let Some(payload) = payload {
+                        actions.extend(
+                            authenticate_messages(&payload)
+                                .into_iter()
+                                .map(|msg| HandshakeAction::Send(Box::new(msg))),
+                        );
                     }
                 }
             }
@@ -125,7 +132,19 @@ impl HandshakeMachine {
                             // RPL_SASLSUCCESS
                             actions.extend(self.finish_cap_negotiation());
                         }
+                        908 => {
+                            // RPL_SASLMECHS - sent before the 904 for a rejected mechanism
+                            self.sasl_offered = Some(parse_mechanisms(msg.arg(1).unwrap_or("")));
+                        }
                         902 | 904 | 905 | 906 | 907 => {
+                            // ERR_SASLFAIL - retry with a supported mechanism from 908
+                            if numeric == 904 {
+                                if let Some(mechanism) = self.sasl_fallback() {
+                                    self.sasl_offered = None;
+                                    actions.push(self.start_sasl(mechanism));
+                                    return actions;
+                                }
+                            }
                             // SASL failures
                             let reason = msg.arg(1).unwrap_or("unknown error").to_string();
                             actions
@@ -142,6 +161,30 @@ impl HandshakeMachine {
         actions
     }
 
+    /// Record and announce the SASL mechanism to attempt.
+    fn start_sasl(&mut self, mechanism: SaslMechanism) -> HandshakeAction {
+        let name = mechanism.as_str().to_string();
+        self.sasl_tried.push(mechanism.clone());
+        self.sasl_mechanism = mechanism;
+        HandshakeAction::Send(Box::new(Command::AUTHENTICATE(name).into()))
+    }
+
+    /// Pick an untried mechanism from the last `RPL_SASLMECHS` list.
+    ///
+    /// Only mechanisms the machine can drive on its own (PLAIN and EXTERNAL)
+    /// are considered.
+    fn sasl_fallback(&self) -> Option<SaslMechanism> {
+        let candidates: Vec<SaslMechanism> = self
+            .sasl_offered
+            .as_deref()?
+            .iter()
+            .filter(|m| matches!(m, SaslMechanism::Plain | SaslMechanism::External))
+            .filter(|m| !self.sasl_tried.contains(m))
+            .cloned()
+            .collect();
+        choose_mechanism(&candidates)
+    }
+
     pub(super) fn handle_registration(&mut self, msg: &MessageRef<'_>) -> Vec<HandshakeAction> {
         let mut actions = Vec::new();
 
@@ -150,6 +193,13 @@ impl HandshakeMachine {
                 // RPL_WELCOME - fully connected
                 self.state = ConnectionState::Connected;
                 actions.push(HandshakeAction::Complete);
+                actions.extend(
+                    self.config
+                        .post_register
+                        .iter()
+                        .map(|msg| HandshakeAction::Send(Box::new(msg.clone()))),
+                );
+                self.auto_join_pending = !self.config.auto_join.is_empty();
             }
             "433" | "432" => {
                 // ERR_NICKNAMEINUSE or ERR_ERRONEUSNICKNAME
@@ -167,6 +217,114 @@ impl HandshakeMachine {
         actions
     }
 
+    pub(super) fn handle_connected(&mut self, msg: &MessageRef<'_>) -> Vec<HandshakeAction> {
+        let mut actions = Vec::new();
+
+        let name = msg.command.name;
+        if name == "005" {
+            self.isupport_seen = true;
+        } else if self.isupport_seen
+            && !self.isupport_complete
+            && name.len() == 3
+            && name.bytes().all(|b| b.is_ascii_digit())
+        {
+            self.isupport_complete = true;
+            actions.push(HandshakeAction::IsupportComplete);
+        }
+        if matches!(name, "376" | "422") {
+            self.motd_done = true;
+        }
+
+        if !self.auto_join_pending {
+            return actions;
+        }
+
+        match name {
+            "005" => {
+                if let Some(isupport) = Isupport::from_response_args(&msg.command.args) {
+                    if let Some(limit) = isupport.targmax().and_then(|t| t.get("JOIN")).flatten() {
+                        self.join_targmax = Some(limit);
+                    }
+                    if let Some(chanlimit) = isupport.chanlimit() {
+                        self.chanlimit = Some(chanlimit);
+                    }
+                }
+            }
+            // RPL_ENDOFMOTD or ERR_NOMOTD - registration burst is over
+            "376" | "422" => {
+                self.auto_join_pending = false;
+                let (messages, skipped) = self.auto_join_messages();
+                actions.extend(
+                    messages
+                        .into_iter()
+                        .map(|msg| HandshakeAction::Send(Box::new(msg))),
+                );
+                if !skipped.is_empty() {
+                    actions.push(HandshakeAction::AutoJoinSkipped(skipped));
+                }
+            }
+            _ => {}
+        }
+        actions
+    }
+
+    /// Build the JOIN lines for the configured auto-join channels.
+    ///
+    /// Channels beyond the `CHANLIMIT` for their prefix group are returned
+    /// separately as skipped. Keyed channels are listed first so keys line up
+    /// positionally, and lines are split to stay within `TARGMAX` and the
+    /// message length limit.
+    fn auto_join_messages(&self) -> (Vec<Message>, Vec<String>) {
+        let mut per_group: HashMap<usize, u32> = HashMap::new();
+        let mut channels: Vec<&(String, Option<String>)> = Vec::new();
+        let mut skipped = Vec::new();
+        for entry in &self.config.auto_join {
+            let Some(prefix) = entry.0.chars().next() else {
+                continue;
+            };
+            let group = self
+                .chanlimit
+                .as_ref()
+                .and_then(|limits| limits.group_for(prefix));
+            if let Some((group, limit)) = group {
+                let count = per_group.entry(group).or_default();
+                if *count >= limit {
+                    skipped.push(entry.0.clone());
+                    continue;
+                }
+                *count += 1;
+            }
+            channels.push(entry);
+        }
+        channels.sort_by_key(|(_, key)| key.is_none());
+
+        let mut messages = Vec::new();
+        let mut names: Vec<&str> = Vec::new();
+        let mut keys: Vec<&str> = Vec::new();
+        for (channel, key) in channels {
+            names.push(channel);
+            keys.extend(key.as_deref());
+
+            let too_long = join_line_len(&names, &keys) > MAX_MESSAGE_BODY;
+            let too_many = self.join_targmax.is_some_and(|max| names.len() > max);
+            if (too_long || too_many) && names.len() > 1 {
+                names.pop();
+                if key.is_some() {
+                    keys.pop();
+                }
+                messages.push(join_message(&names, &keys));
+                names.clear();
+                keys.clear();
+                names.push(channel);
+                keys.extend(key.as_deref());
+            }
+        }
+        if !names.is_empty() {
+            messages.push(join_message(&names, &keys));
+        }
+        (messages, skipped)
+    }
+
     pub(super) fn finish_cap_negotiation(&mut self) -> Vec<HandshakeAction> {
         self.state = ConnectionState::Registering;
         let mut actions = Vec::new();
@@ -195,3 +353,14 @@ impl HandshakeMachine {
         actions
     }
 }
+
+/// Length of `JOIN <names> [<keys>]\r\n` in bytes.
+fn join_line_len(names: &[&str], keys: &[&str]) -> usize {
+    let list_len = |items: &[&str]| items.iter().map(|i| i.len() + 1).sum::<usize>();
+    "JOIN".len() + list_len(names) + list_len(keys) + "\r\n".len()
+}
+
+fn join_message(names: &[&str], keys: &[&str]) -> Message {
+    let keys = (!keys.is_empty()).then(|| keys.join(","));
+    Command::JOIN(names.join(","), keys, None).into()
+}
@@ -2,9 +2,11 @@
 
 use std::collections::HashSet;
 
+use crate::isupport::ChanLimit;
 use crate::message::MessageRef;
+use crate::sasl::SaslMechanism;
 
-use super::{ConnectionState, HandshakeAction, HandshakeConfig};
+use super::{CapGuard, ConnectionState, HandshakeAction, HandshakeConfig};
 
 /// Sans-IO state machine for IRC connection handshake.
 ///
@@ -21,6 +23,24 @@ pub struct HandshakeMachine {
     pub(super) registration_sent: bool,
     /// Whether we're waiting for more CAP LS (multiline).
     pub(super) waiting_for_more_caps: bool,
+    /// Whether an `RPL_ISUPPORT` (005) has been received.
+    pub(super) isupport_seen: bool,
+    /// Whether the end of the `RPL_ISUPPORT` burst has been signalled.
+    pub(super) isupport_complete: bool,
+    /// Whether auto-join is waiting for the end of the MOTD.
+    pub(super) auto_join_pending: bool,
+    /// Whether the end of the MOTD (`376` or `422`) has been seen.
+    pub(super) motd_done: bool,
+    /// Maximum JOIN targets per line (`TARGMAX`), if advertised.
+    pub(super) join_targmax: Option<usize>,
+    /// Per-prefix channel limits (`CHANLIMIT`), if advertised.
+    pub(super) chanlimit: Option<ChanLimit>,
+    /// SASL mechanism currently being attempted.
+    pub(super) sasl_mechanism: SaslMechanism,
+    /// SASL mechanisms already attempted.
+    pub(super) sasl_tried: Vec<SaslMechanism>,
+    /// Mechanisms advertised by `RPL_SASLMECHS` (908), not yet acted on.
+    pub(super) sasl_offered: Option<Vec<SaslMechanism>>,
 }
 
 impl HandshakeMachine {
@@ -34,6 +54,15 @@ impl HandshakeMachine {
             available_caps: HashSet::new(),
             registration_sent: false,
             waiting_for_more_caps: false,
+            isupport_seen: false,
+            isupport_complete: false,
+            auto_join_pending: false,
+            motd_done: false,
+            join_targmax: None,
+            chanlimit: None,
+            sasl_mechanism: SaslMechanism::Plain,
+            sasl_tried: Vec::new(),
+            sasl_offered: None,
         }
     }
 
@@ -43,12 +72,31 @@ impl HandshakeMachine {
         &self.state
     }
 
+    /// Whether feeding further messages can no longer produce actions.
+    ///
+    /// True once registration, the ISUPPORT burst and auto-join are all
+    /// finished (at the end of the MOTD), or the connection has terminated.
+    #[must_use]
+    pub fn is_settled(&self) -> bool {
+        match self.state {
+            ConnectionState::Connected => self.motd_done && !self.auto_join_pending,
+            ConnectionState::Terminated => true,
+            _ => false,
+        }
+    }
+
     /// Get the set of enabled capabilities.
     #[must_use]
     pub fn enabled_caps(&self) -> &HashSet<String> {
         &self.enabled_caps
     }
 
+    /// Get a [`CapGuard`] over the currently enabled capabilities.
+    #[must_use]
+    pub fn cap_guard(&self) -> CapGuard {
+        CapGuard::new(self.enabled_caps.iter().cloned())
+    }
+
     /// Get the set of available capabilities.
     #[must_use]
     pub fn available_caps(&self) -> &HashSet<String> {
@@ -92,7 +140,8 @@ impl HandshakeMachine {
             ConnectionState::CapabilityNegotiation => self.handle_cap_negotiation(msg),
             ConnectionState::Authenticating => self.handle_authentication(msg),
             ConnectionState::Registering => self.handle_registration(msg),
-            ConnectionState::Connected | ConnectionState::Terminated => vec![],
+            ConnectionState::Connected => self.handle_connected(msg),
+            ConnectionState::Terminated => vec![],
         }
     }
 }
@@ -100,7 +149,8 @@ impl HandshakeMachine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::state::HandshakeConfig;
+    use crate::state::{HandshakeConfig, HandshakeError, SaslCredentials};
+    use crate::Message;
 
     fn make_config() -> HandshakeConfig {
         HandshakeConfig {
@@ -110,6 +160,8 @@ mod tests {
             password: None,
             request_caps: vec!["multi-prefix".to_string()],
             sasl_credentials: None,
+            post_register: vec![],
+            auto_join: vec![],
         }
     }
 
@@ -170,6 +222,23 @@ mod tests {
         assert!(actions.len() >= 3);
     }
 
+    #[test]
+    fn test_cap_guard_reflects_ack() {
+        let mut config = make_config();
+        config.request_caps = vec!["message-tags".to_string()];
+        let mut machine = HandshakeMachine::new(config);
+        let _ = machine.start();
+
+        let cap_ls = MessageRef::parse(":server CAP * LS :message-tags labeled-response").unwrap();
+        let _ = machine.feed(&cap_ls);
+        let cap_ack = MessageRef::parse(":server CAP * ACK :message-tags").unwrap();
+        let _ = machine.feed(&cap_ack);
+
+        let guard = machine.cap_guard();
+        assert!(guard.can_tagmsg());
+        assert!(!guard.can_label());
+    }
+
     #[test]
     fn test_welcome_completes() {
         let mut machine = HandshakeMachine::new(make_config());
@@ -187,4 +256,254 @@ mod tests {
             .iter()
             .any(|a| matches!(a, HandshakeAction::Complete)));
     }
+
+    #[test]
+    fn test_post_register_after_welcome() {
+        let config = HandshakeConfig {
+            post_register: vec![
+                Message::away_with_message("Idling"),
+                Message::join("#rust"),
+                "MODE testbot +i".parse().unwrap(),
+            ],
+            ..make_config()
+        };
+        let mut machine = HandshakeMachine::new(config);
+        let _ = machine.start();
+        let cap_ls = MessageRef::parse(":server CAP * LS :").unwrap();
+        let actions = machine.feed(&cap_ls);
+        assert!(!actions.iter().any(|a| matches!(a, HandshakeAction::Send(m)
+            if m.to_string().starts_with("AWAY"))));
+
+        let welcome = MessageRef::parse(":server 001 testbot :Welcome").unwrap();
+        let actions = machine.feed(&welcome);
+
+        assert!(matches!(actions[0], HandshakeAction::Complete));
+        assert_eq!(
+            sent_lines(&actions[1..]),
+            ["AWAY :Idling\r\n", "JOIN #rust\r\n", "MODE testbot +i\r\n"]
+        );
+    }
+
+    fn connect(machine: &mut HandshakeMachine) {
+        let _ = machine.start();
+        for raw in [":server CAP * LS :", ":server 001 testbot :Welcome"] {
+            let _ = machine.feed(&MessageRef::parse(raw).unwrap());
+        }
+    }
+
+    fn sent_lines(actions: &[HandshakeAction]) -> Vec<String> {
+        actions
+            .iter()
+            .map(|a| match a {
+                HandshakeAction::Send(msg) => msg.to_string(),
+                other => panic!("unexpected action: {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_auto_join_with_key() {
+        let config = HandshakeConfig {
+            auto_join: vec![
+                ("#public".to_string(), None),
+                ("#secret".to_string(), Some("hunter2".to_string())),
+            ],
+            ..make_config()
+        };
+        let mut machine = HandshakeMachine::new(config);
+        connect(&mut machine);
+
+        let motd = MessageRef::parse(":server 375 testbot :- MOTD -").unwrap();
+        assert!(machine.feed(&motd).is_empty());
+
+        assert!(!machine.is_settled());
+        let end = MessageRef::parse(":server 376 testbot :End of MOTD").unwrap();
+        let actions = machine.feed(&end);
+        assert_eq!(sent_lines(&actions), ["JOIN #secret,#public hunter2\r\n"]);
+
+        assert!(machine.is_settled());
+
+        // Only sent once
+        let no_motd = MessageRef::parse(":server 422 testbot :No MOTD").unwrap();
+        assert!(machine.feed(&no_motd).is_empty());
+    }
+
+    #[test]
+    fn test_auto_join_respects_isupport() {
+        let config = HandshakeConfig {
+            auto_join: vec![
+                ("#a".to_string(), None),
+                ("#b".to_string(), Some("kb".to_string())),
+                ("#c".to_string(), None),
+                ("&local".to_string(), None),
+                ("#d".to_string(), None),
+            ],
+            ..make_config()
+        };
+        let mut machine = HandshakeMachine::new(config);
+        connect(&mut machine);
+
+        let isupport = MessageRef::parse(
+            ":server 005 testbot CHANLIMIT=#:3,&: TARGMAX=JOIN:2 :are supported by this server",
+        )
+        .unwrap();
+        assert!(machine.feed(&isupport).is_empty());
+
+        let no_motd = MessageRef::parse(":server 422 testbot :No MOTD").unwrap();
+        let actions = machine.feed(&no_motd);
+        assert!(matches!(actions[0], HandshakeAction::IsupportComplete));
+        assert_eq!(
+            sent_lines(&actions[1..3]),
+            ["JOIN #b,#a kb\r\n", "JOIN #c,&local\r\n"]
+        );
+        assert!(matches!(
+            &actions[3..],
+            [HandshakeAction::AutoJoinSkipped(skipped)] if skipped == &["#d"]
+        ));
+    }
+
+    #[test]
+    fn test_auto_join_chanlimit_shared_group() {
+        let config = HandshakeConfig {
+            auto_join: vec![
+                ("#a".to_string(), None),
+                ("&b".to_string(), None),
+                ("#c".to_string(), None),
+                ("+d".to_string(), None),
+            ],
+            ..make_config()
+        };
+        let mut machine = HandshakeMachine::new(config);
+        connect(&mut machine);
+
+        let isupport =
+            MessageRef::parse(":server 005 testbot CHANLIMIT=#&:2 :are supported by this server")
+                .unwrap();
+        assert!(machine.feed(&isupport).is_empty());
+
+        let end_motd = MessageRef::parse(":server 376 testbot :End of MOTD").unwrap();
+        let actions = machine.feed(&end_motd);
+        assert!(matches!(actions[0], HandshakeAction::IsupportComplete));
+        assert_eq!(sent_lines(&actions[1..2]), ["JOIN #a,&b,+d\r\n"]);
+        assert!(matches!(
+            &actions[2..],
+            [HandshakeAction::AutoJoinSkipped(skipped)] if skipped == &["#c"]
+        ));
+    }
+
+    #[test]
+    fn test_isupport_complete_after_005_run() {
+        let mut machine = HandshakeMachine::new(make_config());
+        connect(&mut machine);
+
+        for raw in [
+            ":server 002 testbot :Your host is server",
+            ":server 005 testbot CHANTYPES=# NICKLEN=30 :are supported by this server",
+            ":server 005 testbot NETWORK=Example :are supported by this server",
+        ] {
+            assert!(machine.feed(&MessageRef::parse(raw).unwrap()).is_empty());
+        }
+
+        let luserclient =
+            MessageRef::parse(":server 251 testbot :There are 3 users on 1 server").unwrap();
+        let actions = machine.feed(&luserclient);
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], HandshakeAction::IsupportComplete));
+
+        // Signalled only once
+        let luserme = MessageRef::parse(":server 255 testbot :I have 3 clients").unwrap();
+        assert!(machine.feed(&luserme).is_empty());
+    }
+
+    #[test]
+    fn test_auto_join_splits_long_lines() {
+        let auto_join: Vec<_> = (0..40)
+            .map(|i| (format!("#channel-name-{:02}", i), None))
+            .collect();
+        let config = HandshakeConfig {
+            auto_join: auto_join.clone(),
+            ..make_config()
+        };
+        let mut machine = HandshakeMachine::new(config);
+        connect(&mut machine);
+
+        let end = MessageRef::parse(":server 376 testbot :End of MOTD").unwrap();
+        let lines = sent_lines(&machine.feed(&end));
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| l.len() <= 512));
+
+        let joined: Vec<String> = lines
+            .iter()
+            .flat_map(|l| {
+                l.trim_end()
+                    .trim_start_matches("JOIN ")
+                    .split(',')
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let expected: Vec<String> = auto_join.into_iter().map(|(c, _)| c).collect();
+        assert_eq!(joined, expected);
+    }
+
+    fn sasl_config() -> HandshakeConfig {
+        HandshakeConfig {
+            request_caps: vec!["sasl".to_string()],
+            sasl_credentials: Some(SaslCredentials {
+                account: "bot".to_string(),
+                password: "secret".to_string(),
+            }),
+            ..make_config()
+        }
+    }
+
+    fn start_sasl(machine: &mut HandshakeMachine) {
+        let _ = machine.start();
+        let _ = machine.feed(&MessageRef::parse(":server CAP * LS :sasl").unwrap());
+        let actions = machine.feed(&MessageRef::parse(":server CAP * ACK :sasl").unwrap());
+        assert_eq!(sent_lines(&actions), ["AUTHENTICATE PLAIN\r\n"]);
+        assert_eq!(machine.state(), &ConnectionState::Authenticating);
+    }
+
+    #[test]
+    fn test_sasl_mechs_fallback() {
+        let mut machine = HandshakeMachine::new(sasl_config());
+        start_sasl(&mut machine);
+
+        let mechs =
+            MessageRef::parse(":server 908 testbot EXTERNAL,PLAIN :are available SASL mechanisms")
+                .unwrap();
+        assert!(machine.feed(&mechs).is_empty());
+
+        let fail = MessageRef::parse(":server 904 testbot :SASL authentication failed").unwrap();
+        let actions = machine.feed(&fail);
+        assert_eq!(sent_lines(&actions), ["AUTHENTICATE EXTERNAL\r\n"]);
+        assert_eq!(machine.state(), &ConnectionState::Authenticating);
+
+        let ready = MessageRef::parse("AUTHENTICATE +").unwrap();
+        assert_eq!(sent_lines(&machine.feed(&ready)), ["AUTHENTICATE +\r\n"]);
+
+        let success = MessageRef::parse(":server 903 testbot :SASL successful").unwrap();
+        let actions = machine.feed(&success);
+        assert!(sent_lines(&actions)[0].starts_with("CAP END"));
+    }
+
+    #[test]
+    fn test_sasl_mechs_without_alternative() {
+        let mut machine = HandshakeMachine::new(sasl_config());
+        start_sasl(&mut machine);
+
+        // Only the already-rejected mechanism and one we cannot drive
+        let mechs =
+            MessageRef::parse(":server 908 testbot PLAIN,SCRAM-SHA-256 :are available").unwrap();
+        assert!(machine.feed(&mechs).is_empty());
+
+        let fail = MessageRef::parse(":server 904 testbot :SASL authentication failed").unwrap();
+        let actions = machine.feed(&fail);
+        assert!(matches!(
+            actions[0],
+            HandshakeAction::Error(HandshakeError::SaslFailed(_))
+        ));
+        assert_eq!(machine.state(), &ConnectionState::Registering);
+    }
 }
@@ -0,0 +1,166 @@
+//! Tracking of the client's own user modes.
+
+use crate::casemap::irc_eq;
+use crate::message::MessageRef;
+use crate::mode::{Mode, UserMode};
+use crate::response::umode::parse_user_mode_changes;
+use crate::response::{parse_umode_is, Response};
+
+/// Sans-IO model of the client's current user modes.
+///
+/// Seeded by `RPL_UMODEIS` (221) and kept up to date by MODE changes to the
+/// client's own nickname. NICK changes by the client are followed so later
+/// MODE lines still match.
+///
+/// # Example
+///
+/// ```
+/// use slirc_proto::mode::UserMode;
+/// use slirc_proto::state::UserModeState;
+/// use slirc_proto::MessageRef;
+///
+/// let mut modes = UserModeState::new("me");
+/// for raw in [":srv 221 me +iw", ":me MODE me :-w+x"] {
+///     modes.feed(&MessageRef::parse(raw).unwrap());
+/// }
+///
+/// assert!(modes.has(&UserMode::Invisible));
+/// assert!(!modes.has(&UserMode::Wallops));
+/// assert_eq!(modes.mode_string(), "+ix");
+/// ```
+#[derive(Clone, Debug)]
+pub struct UserModeState {
+    nick: String,
+    modes: Vec<UserMode>,
+}
+
+impl UserModeState {
+    /// Create an empty state for the client using `nick`.
+    #[must_use]
+    pub fn new<N: Into<String>>(nick: N) -> Self {
+        Self {
+            nick: nick.into(),
+            modes: Vec::new(),
+        }
+    }
+
+    /// Get the client's nickname, as last seen.
+    #[must_use]
+    pub fn nick(&self) -> &str {
+        &self.nick
+    }
+
+    /// Check whether a mode is set.
+    #[must_use]
+    pub fn has(&self, mode: &UserMode) -> bool {
+        self.modes.contains(mode)
+    }
+
+    /// Iterate over the set modes, in the order they were set.
+    pub fn modes(&self) -> impl Iterator<Item = &UserMode> {
+        self.modes.iter()
+    }
+
+    /// Get the set modes as a modestring such as `+iw`.
+    #[must_use]
+    pub fn mode_string(&self) -> String {
+        let mut s = String::from("+");
+        for mode in &self.modes {
+            s.push_str(&mode.to_string());
+        }
+        s
+    }
+
+    /// Feed an incoming message.
+    ///
+    /// Returns `true` if the message updated the client's modes or nickname.
+    pub fn feed(&mut self, msg: &MessageRef<'_>) -> bool {
+        let args = msg.args();
+        if msg.numeric_code().and_then(Response::from_code) == Some(Response::RPL_UMODEIS) {
+            self.modes.clear();
+            self.apply(parse_umode_is(args));
+            return true;
+        }
+
+        match msg.command_name() {
+            name if name.eq_ignore_ascii_case("MODE") => match args {
+                [target, modestring, params @ ..] if irc_eq(target, &self.nick) => {
+                    self.apply(parse_user_mode_changes(modestring, params));
+                    true
+                }
+                _ => false,
+            },
+            name if name.eq_ignore_ascii_case("NICK") => match (msg.source_nickname(), args) {
+                (Some(old), [new, ..]) if irc_eq(old, &self.nick) => {
+                    self.nick = new.to_string();
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn apply(&mut self, changes: Vec<Mode<UserMode>>) {
+        for change in changes {
+            match change {
+                Mode::Plus(mode, _) | Mode::NoPrefix(mode) => {
+                    if !self.modes.contains(&mode) {
+                        self.modes.push(mode);
+                    }
+                }
+                Mode::Minus(mode, _) => self.modes.retain(|m| *m != mode),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(state: &mut UserModeState, lines: &[&str]) {
+        for raw in lines {
+            state.feed(&MessageRef::parse(raw).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_umodeis_then_unset() {
+        let mut state = UserModeState::new("me");
+        feed_all(&mut state, &[":srv 221 me +iw"]);
+        assert_eq!(
+            state.modes().collect::<Vec<_>>(),
+            [&UserMode::Invisible, &UserMode::Wallops]
+        );
+
+        feed_all(&mut state, &[":me!u@h MODE me -i"]);
+        assert!(!state.has(&UserMode::Invisible));
+        assert!(state.has(&UserMode::Wallops));
+        assert_eq!(state.mode_string(), "+w");
+    }
+
+    #[test]
+    fn test_umodeis_replaces_modes() {
+        let mut state = UserModeState::new("me");
+        feed_all(&mut state, &[":me MODE me +ix", ":srv 221 me +w"]);
+        assert_eq!(state.mode_string(), "+w");
+    }
+
+    #[test]
+    fn test_follows_nick_and_ignores_others() {
+        let mut state = UserModeState::new("me");
+        feed_all(
+            &mut state,
+            &[
+                ":bob MODE bob +i",
+                ":alice MODE #rust +o bob",
+                ":me!u@h NICK :Me2",
+                ":Me2 MODE me2 +B",
+                ":other NICK someone",
+            ],
+        );
+        assert_eq!(state.nick(), "Me2");
+        assert_eq!(state.mode_string(), "+B");
+    }
+}
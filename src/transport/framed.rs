@@ -160,6 +160,20 @@ impl Transport {
         })
     }
 
+    /// Require every incoming line to be valid UTF-8.
+    ///
+    /// Call this once the server advertises `UTF8ONLY` in `RPL_ISUPPORT`;
+    /// otherwise invalid lines are decoded as Latin-1. WebSocket text frames
+    /// are always UTF-8, so this has no effect on WebSocket transports.
+    pub fn set_utf8_only(&mut self, utf8_only: bool) {
+        match self {
+            Transport::Tcp { framed } => framed.codec_mut().set_utf8_only(utf8_only),
+            Transport::Tls { framed } => framed.codec_mut().set_utf8_only(utf8_only),
+            Transport::ClientTls { framed } => framed.codec_mut().set_utf8_only(utf8_only),
+            Transport::WebSocket { .. } | Transport::WebSocketTls { .. } => {}
+        }
+    }
+
     /// Create a new WebSocket transport.
     #[cfg(feature = "tokio")]
     pub fn websocket(stream: WebSocketStream<TcpStream>) -> Self {
@@ -333,15 +347,13 @@ where
                     ));
                 }
 
-                let trimmed = text.trim_end_matches(&['\r', '\n'][..]);
-
-                for ch in trimmed.chars() {
-                    if crate::format::is_illegal_control_char(ch) {
-                        return Err(TransportReadError::Protocol(
-                            ProtocolError::IllegalControlChar(ch),
-                        ));
-                    }
+                // At most one line terminator; a bare CR is illegal
+                if let Some(ch) = crate::format::find_illegal_line_char(&text) {
+                    return Err(TransportReadError::Protocol(
+                        ProtocolError::IllegalControlChar(ch),
+                    ));
                 }
+                let trimmed = crate::format::strip_line_ending(&text);
 
                 return Ok(Some(trimmed.to_string()));
             }
@@ -373,13 +385,11 @@ async fn write_websocket_message<S>(stream: &mut WebSocketStream<S>, message: &M
 where
     S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
 {
-    use std::fmt::Write;
-    let mut msg = String::with_capacity(512);
-    write!(&mut msg, "{}", message).expect("fmt::Write to String cannot fail");
+    use crate::encode::IrcEncode;
 
-    // Trim trailing CRLF
-    let len = msg.trim_end_matches(&['\r', '\n'][..]).len();
-    msg.truncate(len);
+    // WebSocket frames delimit messages, so no trailing CRLF
+    let msg = String::from_utf8(message.to_bytes_no_crlf())
+        .expect("encoding a Message produces valid UTF-8");
 
     stream
         .send(WsMessage::Text(msg))
@@ -12,6 +12,8 @@
 //!   - [`Transport::websocket`] / [`Transport::websocket_tls`]: WebSocket connections
 //! - [`ZeroCopyTransport`]: Zero-allocation transport yielding borrowed [`MessageRef`] types
 //! - [`LendingStream`]: Trait for streams that yield borrowed data
+//! - [`RecordingTransport`]: Framed transport that records the raw byte stream
+//! - [`ReplayTransport`]: Read-only transport over a recorded byte stream
 //!
 //! # Usage
 //!
@@ -54,6 +56,8 @@
 mod error;
 mod framed;
 mod parts;
+mod recording;
+mod replay;
 mod zero_copy;
 
 // Re-export all public types
@@ -63,9 +67,11 @@ pub use parts::{
     TransportParts, TransportRead, TransportReadHalf, TransportStream, TransportWrite,
     TransportWriteHalf,
 };
+pub use recording::RecordingTransport;
+pub use replay::ReplayTransport;
 #[cfg(feature = "tokio")]
 pub use zero_copy::ZeroCopyWebSocketTransport;
-pub use zero_copy::{LendingStream, ZeroCopyTransport, ZeroCopyTransportEnum};
+pub use zero_copy::{LendingStream, ReadFailure, ZeroCopyTransport, ZeroCopyTransportEnum};
 
 /// Maximum IRC line length (8191 bytes as per modern IRC conventions).
 pub const MAX_IRC_LINE_LEN: usize = 8191;
@@ -77,6 +83,7 @@ pub const MAX_IRC_LINE_LEN: usize = 8191;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ProtocolError;
     use bytes::BytesMut;
     use std::io::Cursor;
     use std::pin::Pin;
@@ -181,6 +188,123 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_zero_copy_at_limit_truncation_suspected() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        // Exactly max_line_len bytes with no newline, then the peer closes
+        let data = vec![b'A'; 64];
+        let reader = MockReader::new(&data);
+        let mut transport = ZeroCopyTransport::with_max_line_len(reader, 64);
+
+        let suspected = Arc::new(AtomicUsize::new(0));
+        let hook_count = Arc::clone(&suspected);
+        transport.on_truncation_suspected(move |len| {
+            assert_eq!(len, 64);
+            hook_count.fetch_add(1, Ordering::SeqCst);
+        });
+
+        {
+            let result = transport.next().await;
+            assert!(result.is_some());
+            let unwrapped = result.unwrap();
+            match unwrapped {
+                Err(TransportReadError::Io(e)) => {
+                    assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+                }
+                other => panic!("Expected UnexpectedEof error, got {:?}", other),
+            }
+        }
+        assert_eq!(suspected.load(Ordering::SeqCst), 1);
+        assert_eq!(transport.last_failure(), Some(ReadFailure::ClosedMidLine));
+    }
+
+    #[tokio::test]
+    async fn test_zero_copy_overflow_vs_close() {
+        // Longer than one 4096-byte read, so the cap is hit before the newline
+        let mut data = vec![b'A'; 4100];
+        data.extend_from_slice(b"\r\nPING :ok\r\n");
+        let reader = MockReader::new(&data);
+        let mut transport = ZeroCopyTransport::with_max_line_len(reader, 64);
+
+        {
+            let result = transport.next().await;
+            assert!(result.is_some());
+            let unwrapped = result.unwrap();
+            assert!(matches!(
+                unwrapped,
+                Err(TransportReadError::Protocol(
+                    crate::error::ProtocolError::MessageTooLong { .. }
+                ))
+            ));
+        }
+        assert_eq!(transport.last_failure(), Some(ReadFailure::Overflow));
+
+        // Recovery clears the failure
+        {
+            let msg = transport.next().await.unwrap().unwrap();
+            assert_eq!(msg.args(), &["ok"]);
+        }
+        assert_eq!(transport.last_failure(), None);
+    }
+
+    #[tokio::test]
+    async fn test_zero_copy_failure_tracks_latest_error() {
+        let mut data = vec![b'A'; 4100];
+        data.extend_from_slice(b"\r\n\xff\xfe\r\nPRIVMSG #c :a\rb\r\n\r\nPING :ok\r\n");
+        let reader = MockReader::new(&data);
+        let mut transport = ZeroCopyTransport::with_max_line_len(reader, 64);
+
+        let expected = [
+            Some(ReadFailure::Overflow),
+            Some(ReadFailure::InvalidLine), // invalid UTF-8
+            Some(ReadFailure::InvalidLine), // bare CR
+            Some(ReadFailure::InvalidLine), // empty line fails to parse
+        ];
+        for failure in expected {
+            assert!(transport.next().await.unwrap().is_err());
+            assert_eq!(transport.last_failure(), failure);
+        }
+
+        {
+            let msg = transport.next().await.unwrap().unwrap();
+            assert_eq!(msg.args(), &["ok"]);
+        }
+        assert_eq!(transport.last_failure(), None);
+    }
+
+    /// Poll a transport through `LendingStream`, keeping each message's args.
+    async fn poll_args(
+        transport: &mut ZeroCopyTransport<MockReader>,
+    ) -> Option<Result<String, TransportReadError>> {
+        std::future::poll_fn(|cx| {
+            Pin::new(&mut *transport)
+                .poll_next(cx)
+                .map(|item| item.map(|r| r.map(|msg| msg.args().join(" "))))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_lending_stream_overflow_recovers() {
+        let mut data = vec![b'A'; 4100];
+        data.extend_from_slice(b"\r\n\xff\r\nPING :ok\r\n");
+        let reader = MockReader::new(&data);
+        let mut transport = ZeroCopyTransport::with_max_line_len(reader, 64);
+
+        assert!(matches!(
+            poll_args(&mut transport).await,
+            Some(Err(TransportReadError::Protocol(
+                crate::error::ProtocolError::MessageTooLong { .. }
+            )))
+        ));
+        assert!(matches!(poll_args(&mut transport).await, Some(Err(_))));
+        assert_eq!(poll_args(&mut transport).await.unwrap().unwrap(), "ok");
+        assert!(poll_args(&mut transport).await.is_none());
+        assert_eq!(transport.last_failure(), None);
+    }
+
     #[tokio::test]
     async fn test_zero_copy_with_buffer() {
         // Simulate upgrading from Transport with buffered data
@@ -252,6 +376,31 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_zero_copy_bare_cr_rejected() {
+        let reader = MockReader::new(b"PRIVMSG #c :a\rb\n");
+        let mut transport = ZeroCopyTransport::new(reader);
+
+        let result = transport.next().await;
+        assert!(result.is_some());
+        let unwrapped = result.unwrap();
+        match unwrapped {
+            Err(TransportReadError::Protocol(crate::error::ProtocolError::IllegalControlChar(
+                '\r',
+            ))) => {}
+            other => panic!("Expected IllegalControlChar('\\r') error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zero_copy_crlf_accepted() {
+        let reader = MockReader::new(b"PRIVMSG #c :a\r\n");
+        let mut transport = ZeroCopyTransport::new(reader);
+
+        let msg = transport.next().await.unwrap().unwrap();
+        assert_eq!(msg.args(), &["#c", "a"]);
+    }
+
     #[tokio::test]
     async fn test_zero_copy_fragmented() {
         // Simulate data arriving in small chunks
@@ -399,4 +548,68 @@ mod tests {
 
         tokio::join!(client, server);
     }
+
+    /// Connect a raw WebSocket pair over loopback TCP, skipping the HTTP handshake.
+    async fn websocket_pair() -> (
+        tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>,
+        tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>,
+    ) {
+        use tokio_tungstenite::tungstenite::protocol::Role;
+        use tokio_tungstenite::WebSocketStream;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = tokio::net::TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        (
+            WebSocketStream::from_raw_socket(server, Role::Server, None).await,
+            WebSocketStream::from_raw_socket(client, Role::Client, None).await,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_websocket_line_endings() {
+        use futures_util::SinkExt;
+        use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+        let cases: [(&str, Option<char>); 6] = [
+            ("PRIVMSG #c :a", None),
+            ("PRIVMSG #c :a\r\n", None),
+            ("PRIVMSG #c :a\rb", Some('\r')),
+            ("PRIVMSG #c :a\r", Some('\r')),
+            ("PRIVMSG #c :a\r\r\n", Some('\r')),
+            ("PRIVMSG #c :a\n\n", Some('\n')),
+        ];
+
+        let illegal_char = |result: Result<(), TransportReadError>| match result {
+            Ok(()) => None,
+            Err(TransportReadError::Protocol(ProtocolError::IllegalControlChar(ch))) => Some(ch),
+            Err(e) => panic!("unexpected error {:?}", e),
+        };
+
+        for (text, illegal) in cases {
+            let (server, mut client) = websocket_pair().await;
+            let mut transport = Transport::websocket(server);
+            client
+                .send(WsMessage::Text(text.to_string()))
+                .await
+                .unwrap();
+            let result = transport.read_message().await.map(|msg| {
+                assert_eq!(msg.unwrap().to_string(), "PRIVMSG #c :a\r\n");
+            });
+            assert_eq!(illegal_char(result), illegal, "{:?}", text);
+
+            let (server, mut client) = websocket_pair().await;
+            let mut transport = ZeroCopyWebSocketTransport::new(server);
+            client
+                .send(WsMessage::Text(text.to_string()))
+                .await
+                .unwrap();
+            let result = transport.next().await.unwrap().map(|msg| {
+                assert_eq!(msg.args(), ["#c", "a"]);
+            });
+            assert_eq!(illegal_char(result), illegal, "zero-copy {:?}", text);
+        }
+    }
 }
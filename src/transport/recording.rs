@@ -0,0 +1,237 @@
+//! Transport that records the raw byte stream of a session.
+
+use std::io::Write;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use anyhow::Result;
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_util::codec::Framed;
+
+use crate::error::ProtocolError;
+use crate::irc::IrcCodec;
+use crate::Message;
+
+use super::error::TransportReadError;
+
+/// IRC transport that tees every byte read and written to a pair of sinks.
+///
+/// Messages are framed exactly as by [`Transport`](super::Transport), but the
+/// bytes received from the peer are copied to the `received` sink and the
+/// bytes sent to it are copied to the `sent` sink, unmodified.
+///
+/// The sinks are written synchronously from inside the transport's poll
+/// methods, so they must not block: a blocking sink such as a `File` would
+/// stall the async executor on disk I/O. Record into memory with a
+/// `Vec<u8>` and persist it from async code, either at the end of the
+/// session via [`into_inner`](Self::into_inner) or periodically by draining
+/// [`received_mut`](Self::received_mut) and [`sent_mut`](Self::sent_mut).
+///
+/// # Example
+///
+/// ```ignore
+/// use slirc_proto::transport::RecordingTransport;
+///
+/// let stream = TcpStream::connect("irc.libera.chat:6667").await?;
+/// let mut transport = RecordingTransport::new(stream, Vec::new(), Vec::new())?;
+/// // ... run the session, then save what was received so far ...
+/// let chunk = std::mem::take(transport.received_mut());
+/// log_file.write_all(&chunk).await?;
+/// ```
+pub struct RecordingTransport<S, R, W> {
+    framed: Framed<Recorder<S, R, W>, IrcCodec>,
+}
+
+impl<S, R, W> RecordingTransport<S, R, W>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    R: Write + Unpin,
+    W: Write + Unpin,
+{
+    /// Wrap a stream, recording received bytes to `received` and sent bytes
+    /// to `sent`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the UTF-8 codec cannot be created (should not happen
+    /// in practice, but avoids panicking in library code).
+    pub fn new(stream: S, received: R, sent: W) -> Result<Self, ProtocolError> {
+        let codec = IrcCodec::new("utf-8")?;
+        let recorder = Recorder {
+            stream,
+            received,
+            sent,
+        };
+        Ok(Self {
+            framed: Framed::new(recorder, codec),
+        })
+    }
+
+    /// Get the sink holding the bytes received so far.
+    pub fn received(&self) -> &R {
+        &self.framed.get_ref().received
+    }
+
+    /// Get the sink holding the bytes sent so far.
+    pub fn sent(&self) -> &W {
+        &self.framed.get_ref().sent
+    }
+
+    /// Get mutable access to the received sink, e.g. to drain a buffer.
+    pub fn received_mut(&mut self) -> &mut R {
+        &mut self.framed.get_mut().received
+    }
+
+    /// Get mutable access to the sent sink, e.g. to drain a buffer.
+    pub fn sent_mut(&mut self) -> &mut W {
+        &mut self.framed.get_mut().sent
+    }
+
+    /// Consume the transport, returning the stream and both sinks.
+    ///
+    /// Bytes already read from the stream but not yet decoded are recorded
+    /// but discarded.
+    pub fn into_inner(self) -> (S, R, W) {
+        let recorder = self.framed.into_inner();
+        (recorder.stream, recorder.received, recorder.sent)
+    }
+
+    /// Read the next IRC message from the transport.
+    ///
+    /// Returns `Ok(None)` when the connection is closed.
+    pub async fn read_message(&mut self) -> Result<Option<Message>, TransportReadError> {
+        match self.framed.next().await {
+            Some(Ok(msg)) => Ok(Some(msg)),
+            Some(Err(e)) => Err(TransportReadError::from(e)),
+            None => Ok(None),
+        }
+    }
+
+    /// Write an IRC message to the transport.
+    pub async fn write_message(&mut self, message: &Message) -> Result<()> {
+        self.framed
+            .send(message.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+}
+
+/// Stream adapter that copies bytes passing through it into two sinks.
+struct Recorder<S, R, W> {
+    stream: S,
+    received: R,
+    sent: W,
+}
+
+impl<S, R, W> AsyncRead for Recorder<S, R, W>
+where
+    S: AsyncRead + Unpin,
+    R: Write + Unpin,
+    W: Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let start = buf.filled().len();
+        match Pin::new(&mut this.stream).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                this.received.write_all(&buf.filled()[start..])?;
+                Poll::Ready(Ok(()))
+            }
+            other => other,
+        }
+    }
+}
+
+impl<S, R, W> AsyncWrite for Recorder<S, R, W>
+where
+    S: AsyncWrite + Unpin,
+    R: Unpin,
+    W: Write + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        match Pin::new(&mut this.stream).poll_write(cx, buf) {
+            Poll::Ready(Ok(n)) => {
+                this.sent.write_all(&buf[..n])?;
+                Poll::Ready(Ok(n))
+            }
+            other => other,
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        this.sent.flush()?;
+        Pin::new(&mut this.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageRef;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn test_recorded_bytes_match_session() {
+        let (client, mut server) = tokio::io::duplex(1024);
+        let mut transport = RecordingTransport::new(client, Vec::new(), Vec::new()).unwrap();
+
+        let incoming: &[u8] = b":srv 001 me :Welcome\r\n@time=2024-01-01T00:00:00Z PING :srv\r\n";
+        server.write_all(incoming).await.unwrap();
+
+        let welcome = transport.read_message().await.unwrap().unwrap();
+        assert_eq!(welcome.prefix.unwrap().to_string(), "srv");
+        let ping = transport.read_message().await.unwrap().unwrap();
+        assert_eq!(ping.tags.unwrap().len(), 1);
+
+        transport
+            .write_message(&Message::pong("srv"))
+            .await
+            .unwrap();
+        transport
+            .write_message(&Message::join("#rust"))
+            .await
+            .unwrap();
+
+        let outgoing = b"PONG srv\r\nJOIN #rust\r\n";
+        let mut buf = vec![0; outgoing.len()];
+        server.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, outgoing);
+
+        assert_eq!(transport.received().as_slice(), incoming);
+        assert_eq!(transport.sent().as_slice(), outgoing);
+
+        // Draining the buffers leaves later traffic to be recorded afresh
+        let drained = std::mem::take(transport.received_mut());
+        assert_eq!(drained, incoming);
+        server.write_all(b"PING :again\r\n").await.unwrap();
+        transport.read_message().await.unwrap().unwrap();
+        assert_eq!(transport.received().as_slice(), b"PING :again\r\n");
+        let mut received = drained;
+        received.extend(std::mem::take(transport.received_mut()));
+
+        // The recorded stream replays through the parser
+        let (_, _, sent) = transport.into_inner();
+        let replayed: Vec<String> = std::str::from_utf8(&received)
+            .unwrap()
+            .split_inclusive("\r\n")
+            .map(|line| MessageRef::parse(line).unwrap().command_name().to_string())
+            .collect();
+        assert_eq!(replayed, ["001", "PING", "PING"]);
+        assert_eq!(sent, outgoing);
+    }
+}
@@ -0,0 +1,155 @@
+//! Transport that replays a recorded byte stream.
+
+use std::io::Cursor;
+use std::path::Path;
+
+use futures_util::StreamExt;
+use tokio_util::codec::Framed;
+
+use crate::error::ProtocolError;
+use crate::irc::IrcCodec;
+use crate::Message;
+
+use super::error::TransportReadError;
+use super::ZeroCopyTransport;
+
+/// Read-only IRC transport over a recorded byte stream.
+///
+/// Yields the messages in a captured session (for example the `received`
+/// log of a [`RecordingTransport`](super::RecordingTransport)) without a
+/// real socket, using the same framing as [`Transport`](super::Transport).
+/// Convert it with [`into_zero_copy`](Self::into_zero_copy) to exercise the
+/// [`ZeroCopyTransport`] path instead.
+///
+/// # Example
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use slirc_proto::transport::ReplayTransport;
+///
+/// let mut replay = ReplayTransport::new(&b"PING :a\r\nPING :b\r\n"[..]).unwrap();
+/// while let Some(msg) = replay.read_message().await.unwrap() {
+///     println!("{}", msg);
+/// }
+/// # }
+/// ```
+pub struct ReplayTransport {
+    framed: Framed<Cursor<Vec<u8>>, IrcCodec>,
+}
+
+impl ReplayTransport {
+    /// Create a transport replaying the given bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the UTF-8 codec cannot be created (should not happen
+    /// in practice, but avoids panicking in library code).
+    pub fn new<B: Into<Vec<u8>>>(bytes: B) -> Result<Self, ProtocolError> {
+        let codec = IrcCodec::new("utf-8")?;
+        Ok(Self {
+            framed: Framed::new(Cursor::new(bytes.into()), codec),
+        })
+    }
+
+    /// Create a transport replaying the contents of a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProtocolError::Io`] if the file cannot be read.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ProtocolError> {
+        Self::new(std::fs::read(path)?)
+    }
+
+    /// Read the next IRC message from the recording.
+    ///
+    /// Returns `Ok(None)` once the recording is exhausted.
+    pub async fn read_message(&mut self) -> Result<Option<Message>, TransportReadError> {
+        match self.framed.next().await {
+            Some(Ok(msg)) => Ok(Some(msg)),
+            Some(Err(e)) => Err(TransportReadError::from(e)),
+            None => Ok(None),
+        }
+    }
+
+    /// Convert into a [`ZeroCopyTransport`] over the remaining bytes.
+    ///
+    /// Bytes already buffered by the framed codec are carried over.
+    pub fn into_zero_copy(self) -> ZeroCopyTransport<Cursor<Vec<u8>>> {
+        let parts = self.framed.into_parts();
+        ZeroCopyTransport::with_buffer(parts.io, parts.read_buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::transport::RecordingTransport;
+    use tokio::io::AsyncWriteExt;
+
+    const SESSION: &[u8] = b":srv NOTICE * :*** Looking up your hostname\r\n\
+        :srv 001 me :Welcome\r\n\
+        @time=2024-01-01T00:00:00Z :alice!a@host PRIVMSG #rust :hello\r\n\
+        PING :srv\r\n";
+
+    #[tokio::test]
+    async fn test_replay_sequence() {
+        let mut replay = ReplayTransport::new(SESSION).unwrap();
+
+        let mut commands = Vec::new();
+        while let Some(msg) = replay.read_message().await.unwrap() {
+            commands.push(msg.command);
+        }
+        assert_eq!(commands.len(), 4);
+        assert!(matches!(commands[0], Command::NOTICE(ref t, _) if t == "*"));
+        assert!(matches!(commands[1], Command::Response(..)));
+        assert!(
+            matches!(commands[2], Command::PRIVMSG(ref t, ref m) if t == "#rust" && m == "hello")
+        );
+        assert_eq!(commands[3], Command::PING("srv".to_string(), None));
+    }
+
+    #[tokio::test]
+    async fn test_replay_zero_copy() {
+        let mut replay = ReplayTransport::new(SESSION).unwrap();
+        let first = replay.read_message().await.unwrap().unwrap();
+        assert_eq!(first.command.name(), "NOTICE");
+
+        let mut zero_copy = replay.into_zero_copy();
+        let mut names = Vec::new();
+        while let Some(msg) = zero_copy.next().await {
+            names.push(msg.unwrap().command_name().to_string());
+        }
+        assert_eq!(names, ["001", "PRIVMSG", "PING"]);
+    }
+
+    #[tokio::test]
+    async fn test_replay_recorded_session() {
+        let (client, mut server) = tokio::io::duplex(1024);
+        let mut recorder = RecordingTransport::new(client, Vec::new(), Vec::new()).unwrap();
+        server.write_all(SESSION).await.unwrap();
+        drop(server);
+
+        let mut live = Vec::new();
+        while let Some(msg) = recorder.read_message().await.unwrap() {
+            live.push(msg);
+        }
+
+        let (_, received, _) = recorder.into_inner();
+        let mut replay = ReplayTransport::new(received).unwrap();
+        let mut replayed = Vec::new();
+        while let Some(msg) = replay.read_message().await.unwrap() {
+            replayed.push(msg);
+        }
+        assert_eq!(replayed, live);
+    }
+
+    #[test]
+    fn test_from_missing_file() {
+        let err = ReplayTransport::from_file("/nonexistent/session.log")
+            .err()
+            .unwrap();
+        assert!(matches!(err, ProtocolError::Io(_)));
+    }
+}
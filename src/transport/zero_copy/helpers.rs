@@ -86,6 +86,7 @@ pub fn validate_irc_line_length(line: &[u8], _max_total_len: usize) -> Result<()
 /// Returns the validated string slice if valid, or an error if:
 /// - The slice is not valid UTF-8
 /// - The line contains illegal control characters (NUL, etc.)
+/// - The line contains a CR that is not part of the trailing CRLF
 pub fn validate_line(slice: &[u8]) -> Result<&str, TransportReadError> {
     let s = std::str::from_utf8(slice).map_err(|e| {
         TransportReadError::Protocol(ProtocolError::InvalidUtf8(format!(
@@ -95,16 +96,11 @@ pub fn validate_line(slice: &[u8]) -> Result<&str, TransportReadError> {
         )))
     })?;
 
-    // Trim CRLF for validation
-    let trimmed = s.trim_end_matches(['\r', '\n']);
-
-    // Check for NUL and other illegal control characters
-    for ch in trimmed.chars() {
-        if crate::format::is_illegal_control_char(ch) {
-            return Err(TransportReadError::Protocol(
-                ProtocolError::IllegalControlChar(ch),
-            ));
-        }
+    // Check for NUL, bare CR, and other illegal control characters
+    if let Some(ch) = crate::format::find_illegal_line_char(s) {
+        return Err(TransportReadError::Protocol(
+            ProtocolError::IllegalControlChar(ch),
+        ));
     }
 
     Ok(s)
@@ -38,7 +38,7 @@ pub mod websocket;
 
 // Re-export all public types
 pub use self::trait_def::LendingStream;
-pub use tcp::ZeroCopyTransport;
+pub use tcp::{ReadFailure, ZeroCopyTransport};
 pub use unified::ZeroCopyTransportEnum;
 
 #[cfg(feature = "tokio")]
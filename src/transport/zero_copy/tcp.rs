@@ -15,6 +15,26 @@ use super::super::MAX_IRC_LINE_LEN;
 use super::helpers::{find_crlf, validate_irc_line_length, validate_line};
 use super::trait_def::LendingStream;
 
+/// Why the most recent read from a [`ZeroCopyTransport`] failed.
+///
+/// Distinguishes a hard overflow (the peer sent more than `max_line_len`
+/// bytes without a newline) from the stream closing mid-line, a rejected
+/// line, and an I/O error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReadFailure {
+    /// A line exceeded `max_line_len` and was rejected with
+    /// [`ProtocolError::MessageTooLong`].
+    Overflow,
+    /// The stream closed while a line was still incomplete.
+    ClosedMidLine,
+    /// A complete line was rejected: invalid UTF-8, an illegal control
+    /// character, or a message that failed to parse.
+    InvalidLine,
+    /// Reading from the underlying stream failed.
+    Io,
+}
+
 /// Zero-copy transport that yields `MessageRef<'_>` without allocations.
 ///
 /// This transport maintains an internal buffer and parses messages directly
@@ -44,6 +64,13 @@ pub struct ZeroCopyTransport<S> {
     max_line_len: usize,
     /// Whether we are currently skipping bytes until a newline because of a buffer overflow
     skipping_overflow: bool,
+    /// How the most recent read failed, set on every error and cleared on the
+    /// next successful message
+    last_failure: Option<ReadFailure>,
+    /// Called when a partial line reaches exactly `max_line_len` bytes
+    truncation_hook: Option<Box<dyn FnMut(usize) + Send>>,
+    /// Whether the truncation hook already fired for the current partial line
+    truncation_reported: bool,
 }
 
 impl<S> ZeroCopyTransport<S> {
@@ -55,6 +82,9 @@ impl<S> ZeroCopyTransport<S> {
             consumed: 0,
             max_line_len: MAX_IRC_LINE_LEN,
             skipping_overflow: false,
+            last_failure: None,
+            truncation_hook: None,
+            truncation_reported: false,
         }
     }
 
@@ -69,6 +99,9 @@ impl<S> ZeroCopyTransport<S> {
             consumed: 0,
             max_line_len: MAX_IRC_LINE_LEN,
             skipping_overflow: false,
+            last_failure: None,
+            truncation_hook: None,
+            truncation_reported: false,
         }
     }
 
@@ -80,6 +113,9 @@ impl<S> ZeroCopyTransport<S> {
             consumed: 0,
             max_line_len: max_len,
             skipping_overflow: false,
+            last_failure: None,
+            truncation_hook: None,
+            truncation_reported: false,
         }
     }
 
@@ -88,6 +124,135 @@ impl<S> ZeroCopyTransport<S> {
         self.max_line_len = len;
     }
 
+    /// How the most recent read failed, if it did.
+    ///
+    /// Returns `None` after a message was read successfully.
+    pub fn last_failure(&self) -> Option<ReadFailure> {
+        self.last_failure
+    }
+
+    /// Register a hook called when truncation is suspected.
+    ///
+    /// Servers that truncate over-long lines deliver exactly `max_line_len`
+    /// bytes, so a partial line that reaches the cap without a newline is
+    /// suspicious even before it turns into a hard overflow. The hook
+    /// receives the buffered length and fires at most once per line; it is
+    /// intended for metrics, and does not change read behavior.
+    pub fn on_truncation_suspected<F>(&mut self, hook: F)
+    where
+        F: FnMut(usize) + Send + 'static,
+    {
+        self.truncation_hook = Some(Box::new(hook));
+    }
+
+    /// Fire the truncation hook if the partial line sits exactly at the cap.
+    fn check_truncation(&mut self) {
+        if self.buffer.len() == self.max_line_len && !self.truncation_reported {
+            self.truncation_reported = true;
+            if let Some(hook) = self.truncation_hook.as_mut() {
+                hook(self.buffer.len());
+            }
+        }
+    }
+
+    /// Take the next complete line from the buffer, validating it.
+    ///
+    /// Returns `Some(Ok(len))` for a valid line of `len` bytes, now marked as
+    /// consumed; `Some(Err(_))` for a rejected line or a buffer overflow; and
+    /// `None` if more data is needed. Over-long input is skipped up to the
+    /// next newline.
+    fn take_line(&mut self) -> Option<Result<usize, TransportReadError>> {
+        if self.skipping_overflow {
+            match find_crlf(&self.buffer) {
+                // Drop the rest of the over-long line and carry on
+                Some(newline_pos) => {
+                    self.buffer.advance(newline_pos + 1);
+                    self.skipping_overflow = false;
+                }
+                // No newline yet, discard everything to prevent buffer exhaustion
+                None => {
+                    let len = self.buffer.len();
+                    self.buffer.advance(len);
+                    return None;
+                }
+            }
+        }
+
+        if let Some(newline_pos) = find_crlf(&self.buffer) {
+            let line_len = newline_pos + 1;
+            self.truncation_reported = false;
+            // Consumed whether or not it is valid, so reading can continue
+            self.consumed = line_len;
+
+            let line_slice = &self.buffer[..line_len];
+
+            // Validate IRC-specific line lengths (tags vs body)
+            // This checks:
+            // - Client tag data ≤ 4094 bytes
+            // - Message body ≤ max_line_len bytes (including CRLF)
+            if let Err(e) = validate_irc_line_length(line_slice, self.max_line_len) {
+                self.last_failure = Some(ReadFailure::Overflow);
+                return Some(Err(e));
+            }
+
+            // Validate UTF-8 and control characters
+            if let Err(e) = validate_line(line_slice) {
+                self.last_failure = Some(ReadFailure::InvalidLine);
+                return Some(Err(e));
+            }
+
+            return Some(Ok(line_len));
+        }
+
+        self.check_truncation();
+
+        // Check if buffer is getting too large without a complete line
+        if self.buffer.len() > self.max_line_len {
+            self.skipping_overflow = true;
+            self.truncation_reported = false;
+            self.last_failure = Some(ReadFailure::Overflow);
+            return Some(Err(TransportReadError::Protocol(
+                ProtocolError::MessageTooLong {
+                    actual: self.buffer.len(),
+                    limit: self.max_line_len,
+                },
+            )));
+        }
+
+        None
+    }
+
+    /// Parse a line returned by [`take_line`](Self::take_line).
+    ///
+    /// The `&mut self` borrow prevents reading again while the returned
+    /// `MessageRef` is live, and buffer advancement is deferred until the
+    /// next read, so no unsafe code is needed.
+    fn parse_line(&mut self, line_len: usize) -> Result<MessageRef<'_>, TransportReadError> {
+        let Self {
+            buffer,
+            last_failure,
+            ..
+        } = self;
+        let line_str =
+            std::str::from_utf8(&buffer[..line_len]).expect("Already validated as UTF-8");
+
+        match MessageRef::parse(line_str) {
+            Ok(msg) => {
+                *last_failure = None;
+                Ok(msg)
+            }
+            Err(e) => {
+                *last_failure = Some(ReadFailure::InvalidLine);
+                Err(TransportReadError::Protocol(
+                    ProtocolError::InvalidMessage {
+                        string: line_str.to_string(),
+                        cause: e,
+                    },
+                ))
+            }
+        }
+    }
+
     /// Consume this transport and return its inner stream and buffer.
     ///
     /// This is useful for STARTTLS upgrade: extract the TCP stream,
@@ -168,81 +333,10 @@ impl<S: AsyncRead + Unpin> ZeroCopyTransport<S> {
         }
 
         loop {
-            // If we are in skipping mode, look for a newline to recover
-            if self.skipping_overflow {
-                if let Some(newline_pos) = find_crlf(&self.buffer) {
-                    // Found the end of the garbage line
-                    let line_len = newline_pos + 1;
-                    self.consumed = line_len;
-                    self.skipping_overflow = false;
-                    
-                    // We found the end of the bad line. We can now consume it and continue
-                    // to try to read the NEXT line in the same loop iteration.
-                    // We must advance manually here because we are 'continue'ing the loop
-                    // and bypassing the top-of-loop advance check (which only runs if we return).
-                    self.buffer.advance(self.consumed);
-                    self.consumed = 0;
-                    continue;
-                } else {
-                    // No newline yet, discard everything to prevent buffer exhaustion
-                    let len = self.buffer.len();
-                    self.buffer.advance(len);
-                    // Keep skipping_overflow = true, read more data
-                }
-            } else {
-                // Check if we have a complete line in the buffer
-                if let Some(newline_pos) = find_crlf(&self.buffer) {
-                    let line_len = newline_pos + 1;
-
-                    // Validate the line slice for UTF-8 first
-                    let line_slice = &self.buffer[..line_len];
-
-                    // Validate IRC-specific line lengths (tags vs body)
-                    // This checks:
-                    // - Client tag data ≤ 4094 bytes
-                    // - Message body ≤ max_line_len bytes (including CRLF)
-                    if let Err(e) = validate_irc_line_length(line_slice, self.max_line_len) {
-                        // Mark the line as consumed so we can continue reading
-                        self.consumed = line_len;
-                        return Some(Err(e));
-                    }
-
-                    // Validate UTF-8 and control characters
-                    match validate_line(line_slice) {
-                        Ok(line_str) => {
-                            // Mark this line as consumed (will be advanced on next call)
-                            self.consumed = line_len;
-
-                            // Parse the message - no unsafe needed here because:
-                            // - The `&mut self` borrow prevents calling `next()` again while MessageRef is live
-                            // - Buffer advancement is deferred until the next call to `next()`
-                            // - The returned MessageRef lifetime is tied to `self` via function signature
-                            match MessageRef::parse(line_str) {
-                                Ok(msg) => return Some(Ok(msg)),
-                                Err(e) => {
-                                    return Some(Err(TransportReadError::Protocol(
-                                        ProtocolError::InvalidMessage {
-                                            string: line_str.to_string(),
-                                            cause: e,
-                                        },
-                                    )))
-                                }
-                            }
-                        }
-                        Err(e) => return Some(Err(e)),
-                    }
-                }
-
-                // Check if buffer is getting too large without a complete line
-                if self.buffer.len() > self.max_line_len {
-                    self.skipping_overflow = true;
-                    return Some(Err(TransportReadError::Protocol(
-                        ProtocolError::MessageTooLong {
-                            actual: self.buffer.len(),
-                            limit: self.max_line_len,
-                        },
-                    )));
-                }
+            match self.take_line() {
+                Some(Ok(line_len)) => return Some(self.parse_line(line_len)),
+                Some(Err(e)) => return Some(Err(e)),
+                None => {}
             }
 
             // Need more data - read from stream
@@ -254,6 +348,7 @@ impl<S: AsyncRead + Unpin> ZeroCopyTransport<S> {
                         return None;
                     } else {
                         // Incomplete message at EOF
+                        self.last_failure = Some(ReadFailure::ClosedMidLine);
                         return Some(Err(TransportReadError::Io(std::io::Error::new(
                             std::io::ErrorKind::UnexpectedEof,
                             "Stream closed with incomplete message",
@@ -263,7 +358,10 @@ impl<S: AsyncRead + Unpin> ZeroCopyTransport<S> {
                 Ok(n) => {
                     self.buffer.extend_from_slice(&temp[..n]);
                 }
-                Err(e) => return Some(Err(TransportReadError::Io(e))),
+                Err(e) => {
+                    self.last_failure = Some(ReadFailure::Io);
+                    return Some(Err(TransportReadError::Io(e)));
+                }
             }
         }
     }
@@ -288,60 +386,12 @@ impl<S: AsyncRead + Unpin> LendingStream for ZeroCopyTransport<S> {
         }
 
         loop {
-            // Check if we have a complete line in the buffer
-            if let Some(newline_pos) = find_crlf(&self.buffer) {
-                let line_len = newline_pos + 1;
-
-                // Check line length limit
-                if line_len > self.max_line_len {
-                    return Poll::Ready(Some(Err(TransportReadError::Protocol(
-                        ProtocolError::MessageTooLong {
-                            actual: line_len,
-                            limit: self.max_line_len,
-                        },
-                    ))));
-                }
-
-                // Validate the line first (this borrows buffer temporarily)
-                {
-                    let line_slice = &self.buffer[..line_len];
-                    if let Err(e) = validate_line(line_slice) {
-                        return Poll::Ready(Some(Err(e)));
-                    }
-                }
-
-                // Mark this line as consumed and get long-lived reference
-                // We use get_mut() to get a &'a mut Self from Pin<&'a mut Self>
-                // This allows us to return a reference tied to 'a.
-                let this = self.get_mut();
-                this.consumed = line_len;
-
-                let line_str: &str = {
-                    let slice = &this.buffer[..line_len];
-                    std::str::from_utf8(slice).expect("Already validated as UTF-8")
-                };
-
-                match MessageRef::parse(line_str) {
-                    Ok(msg) => return Poll::Ready(Some(Ok(msg))),
-                    Err(e) => {
-                        return Poll::Ready(Some(Err(TransportReadError::Protocol(
-                            ProtocolError::InvalidMessage {
-                                string: line_str.to_string(),
-                                cause: e,
-                            },
-                        ))))
-                    }
+            match self.take_line() {
+                Some(Ok(line_len)) => {
+                    return Poll::Ready(Some(self.get_mut().parse_line(line_len)))
                 }
-            }
-
-            // Check if buffer is getting too large
-            if self.buffer.len() > self.max_line_len {
-                return Poll::Ready(Some(Err(TransportReadError::Protocol(
-                    ProtocolError::MessageTooLong {
-                        actual: self.buffer.len(),
-                        limit: self.max_line_len,
-                    },
-                ))));
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => {}
             }
 
             // Need more data - try to read from stream
@@ -357,6 +407,7 @@ impl<S: AsyncRead + Unpin> LendingStream for ZeroCopyTransport<S> {
                         if this.buffer.is_empty() {
                             return Poll::Ready(None);
                         } else {
+                            this.last_failure = Some(ReadFailure::ClosedMidLine);
                             return Poll::Ready(Some(Err(TransportReadError::Io(
                                 std::io::Error::new(
                                     std::io::ErrorKind::UnexpectedEof,
@@ -368,7 +419,10 @@ impl<S: AsyncRead + Unpin> LendingStream for ZeroCopyTransport<S> {
                     this.buffer.extend_from_slice(read_buf_slice.filled());
                     // Loop to check buffer again
                 }
-                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(TransportReadError::Io(e)))),
+                Poll::Ready(Err(e)) => {
+                    this.last_failure = Some(ReadFailure::Io);
+                    return Poll::Ready(Some(Err(TransportReadError::Io(e))));
+                }
                 Poll::Pending => return Poll::Pending,
             }
         }
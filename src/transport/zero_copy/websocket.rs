@@ -7,6 +7,7 @@ use bytes::{Buf, BytesMut};
 use futures_util::{SinkExt, Stream, StreamExt};
 use tokio_tungstenite::{tungstenite::Message as WsMessage, WebSocketStream};
 
+use crate::encode::IrcEncode;
 use crate::error::ProtocolError;
 use crate::message::MessageRef;
 use crate::Message;
@@ -113,9 +114,15 @@ where
             // Need more data - read from WebSocket
             match self.stream.next().await {
                 Some(Ok(WsMessage::Text(text))) => {
-                    // WebSocket IRC messages may or may not have CRLF
+                    // WebSocket IRC messages may or may not have CRLF, but
+                    // only one; a bare CR or embedded LF is illegal
+                    if let Some(ch) = crate::format::find_illegal_line_char(&text) {
+                        return Some(Err(TransportReadError::Protocol(
+                            ProtocolError::IllegalControlChar(ch),
+                        )));
+                    }
                     // Append the text, ensuring it ends with LF for our line parser
-                    let text = text.trim_end_matches(['\r', '\n']);
+                    let text = crate::format::strip_line_ending(&text);
                     self.buffer.extend_from_slice(text.as_bytes());
                     self.buffer.extend_from_slice(b"\n");
                 }
@@ -152,10 +159,10 @@ where
     /// This sends the message as a WebSocket text frame. The CRLF
     /// terminator is stripped since WebSocket uses frame boundaries.
     pub async fn write_message(&mut self, message: &Message) -> std::io::Result<()> {
-        let text = message.to_string();
-        let text = text.trim_end_matches(&['\r', '\n'][..]);
+        let text = String::from_utf8(message.to_bytes_no_crlf())
+            .expect("encoding a Message produces valid UTF-8");
         self.stream
-            .send(WsMessage::Text(text.to_string()))
+            .send(WsMessage::Text(text))
             .await
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
     }
@@ -165,13 +172,10 @@ where
     /// This is optimized for relay scenarios where you receive a `MessageRef`
     /// and want to forward it without allocating an owned `Message`.
     pub async fn write_message_ref(&mut self, message: &MessageRef<'_>) -> std::io::Result<()> {
-        use std::fmt::Write;
-        let mut buf = String::with_capacity(512);
-        write!(&mut buf, "{}", message).expect("fmt::Write to String cannot fail");
-        // Strip CRLF for WebSocket (uses frame boundaries)
-        let text = buf.trim_end_matches(&['\r', '\n'][..]);
+        let text = String::from_utf8(message.to_bytes_no_crlf())
+            .expect("encoding a MessageRef produces valid UTF-8");
         self.stream
-            .send(WsMessage::Text(text.to_string()))
+            .send(WsMessage::Text(text))
             .await
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
     }
@@ -255,7 +259,12 @@ where
             let this = self.as_mut().get_mut();
             match Pin::new(&mut this.stream).poll_next(cx) {
                 Poll::Ready(Some(Ok(WsMessage::Text(text)))) => {
-                    let text = text.trim_end_matches(['\r', '\n']);
+                    if let Some(ch) = crate::format::find_illegal_line_char(&text) {
+                        return Poll::Ready(Some(Err(TransportReadError::Protocol(
+                            ProtocolError::IllegalControlChar(ch),
+                        ))));
+                    }
+                    let text = crate::format::strip_line_ending(&text);
                     this.buffer.extend_from_slice(text.as_bytes());
                     this.buffer.extend_from_slice(b"\n");
                     // Loop to check buffer again
@@ -4,6 +4,8 @@
 //! working with IRC messages, including safe string truncation and
 //! length validation.
 
+use crate::casemap::CaseMapping;
+
 /// Maximum length for IRC tags section (per IRCv3 spec).
 pub const MAX_TAGS_LENGTH: usize = 8191;
 
@@ -170,11 +172,27 @@ impl<'a> Iterator for SplitMessage<'a> {
 /// assert!(wildcard_match("#channel[*]", "#CHANNEL{test}"));
 /// ```
 pub fn wildcard_match(pattern: &str, text: &str) -> bool {
-    use crate::casemap::irc_lower_char;
+    wildcard_match_with(pattern, text, CaseMapping::Rfc1459)
+}
 
-    // Convert both to IRC lowercase for case-insensitive matching
-    let pattern_lower: Vec<char> = pattern.chars().map(irc_lower_char).collect();
-    let text_lower: Vec<char> = text.chars().map(irc_lower_char).collect();
+/// Match a string against a wildcard pattern under the given case mapping.
+///
+/// Behaves like [`wildcard_match`], but folds case with `mapping`. Use
+/// [`CaseMapping::Ascii`] for hostnames, which have no IRC special chars.
+///
+/// # Examples
+///
+/// ```
+/// use slirc_proto::util::wildcard_match_with;
+/// use slirc_proto::CaseMapping;
+///
+/// assert!(wildcard_match_with("*.EXAMPLE.com", "host.example.com", CaseMapping::Ascii));
+/// assert!(!wildcard_match_with("nick[*]", "NICK{x}", CaseMapping::Ascii));
+/// ```
+pub fn wildcard_match_with(pattern: &str, text: &str, mapping: CaseMapping) -> bool {
+    // Convert both to lowercase for case-insensitive matching
+    let pattern_lower: Vec<char> = pattern.chars().map(|c| mapping.lower_char(c)).collect();
+    let text_lower: Vec<char> = text.chars().map(|c| mapping.lower_char(c)).collect();
 
     wildcard_match_impl(&pattern_lower, &text_lower)
 }
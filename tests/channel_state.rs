@@ -0,0 +1,78 @@
+//! Integration tests for `ChannelState` membership tracking.
+//!
+//! Drives a realistic join sequence followed by ongoing channel traffic.
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use slirc_proto::isupport::parse_params;
+use slirc_proto::state::ChannelState;
+use slirc_proto::MessageRef;
+
+fn feed_all(state: &mut ChannelState, lines: &[&str]) {
+    for raw in lines {
+        state.feed(&MessageRef::parse(raw).unwrap());
+    }
+}
+
+#[test]
+fn test_channel_lifecycle() {
+    let isupport = parse_params(&["PREFIX=(ohv)@%+", "CHANMODES=beI,k,l,imnst"]);
+    let mut state = ChannelState::new("#rust", isupport.prefix().unwrap());
+    state.set_chanmodes(isupport.chanmodes().unwrap());
+
+    // Join burst: our JOIN, topic, NAMES, creation time
+    feed_all(
+        &mut state,
+        &[
+            ":me!m@host JOIN #rust",
+            ":irc.test 332 me #rust :Welcome to #rust",
+            ":irc.test 333 me #rust alice!a@host 1700000000",
+            ":irc.test 353 me = #rust :@alice %bob +carol me",
+            ":irc.test 353 me = #rust :dave",
+            ":irc.test 366 me #rust :End of /NAMES list.",
+            ":irc.test 329 me #rust 1600000000",
+        ],
+    );
+
+    assert_eq!(state.member_count(), 5);
+    assert_eq!(state.topic(), Some("Welcome to #rust"));
+    assert_eq!(state.topic_setter(), Some("alice!a@host"));
+    assert_eq!(
+        state.topic_set_at(),
+        Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+    );
+    assert_eq!(
+        state.created_at(),
+        Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000))
+    );
+    assert_eq!(state.member("ALICE").unwrap().highest_prefix(), Some('@'));
+
+    // Ongoing traffic
+    feed_all(
+        &mut state,
+        &[
+            ":erin!e@host JOIN #rust",
+            ":alice!a@host MODE #rust +vo-h+l erin erin bob 50",
+            ":alice!a@host MODE #rust -v carol",
+            ":bob!b@host PART #rust :later",
+            ":alice!a@host KICK #rust dave :spam",
+            ":carol!c@host NICK Caroline",
+            ":alice!a@host QUIT :Quit: bye",
+            ":erin!e@host TOPIC #rust :New topic",
+            ":zed!z@host JOIN #other",
+        ],
+    );
+
+    let mut nicks: Vec<&str> = state.members().map(|m| m.nick.as_str()).collect();
+    nicks.sort_unstable();
+    assert_eq!(nicks, ["Caroline", "erin", "me"]);
+
+    assert_eq!(state.member("erin").unwrap().prefixes, "@+");
+    assert_eq!(state.member("caroline").unwrap().prefixes, "");
+    assert!(!state.is_member("carol"));
+    assert!(!state.is_member("zed"));
+
+    assert_eq!(state.topic(), Some("New topic"));
+    assert_eq!(state.topic_setter(), Some("erin!e@host"));
+    assert_eq!(state.topic_set_at(), None);
+}
@@ -0,0 +1,246 @@
+//! Integration tests for the high-level `IrcClient`.
+//!
+//! A scripted mock server drives a full handshake over a real TCP socket.
+
+#![cfg(feature = "tokio")]
+
+use slirc_proto::client::{ClientError, Event, IrcClient};
+use slirc_proto::state::{ConnectionState, HandshakeConfig, HandshakeError};
+use slirc_proto::{Message, Response, Transport};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+
+fn make_config() -> HandshakeConfig {
+    HandshakeConfig {
+        nickname: "testbot".to_string(),
+        username: "bot".to_string(),
+        realname: "Test Bot".to_string(),
+        password: None,
+        request_caps: vec!["multi-prefix".to_string()],
+        sasl_credentials: None,
+        post_register: vec![],
+        auto_join: vec![],
+    }
+}
+
+/// One side of the scripted mock server.
+struct MockServer {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl MockServer {
+    fn new(stream: TcpStream) -> Self {
+        let (read, write) = stream.into_split();
+        Self {
+            reader: BufReader::new(read),
+            writer: write,
+        }
+    }
+
+    async fn expect(&mut self, expected: &str) {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).await.unwrap();
+        assert_eq!(line.trim_end(), expected);
+    }
+
+    async fn send(&mut self, line: &str) {
+        self.writer
+            .write_all(format!("{}\r\n", line).as_bytes())
+            .await
+            .unwrap();
+    }
+}
+
+async fn setup() -> (IrcClient, TcpListener) {
+    setup_with(make_config()).await
+}
+
+async fn setup_with(config: HandshakeConfig) -> (IrcClient, TcpListener) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let client = IrcClient::new(Transport::tcp(stream).unwrap(), config);
+    (client, listener)
+}
+
+#[tokio::test]
+async fn test_full_handshake() {
+    let (mut client, listener) = setup().await;
+
+    let server = async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut server = MockServer::new(stream);
+
+        server.expect("CAP LS 302").await;
+        server.send(":irc.test CAP * LS :multi-prefix sasl").await;
+        server.expect("CAP REQ multi-prefix").await;
+        server.send(":irc.test CAP * ACK :multi-prefix").await;
+        server.expect("CAP END").await;
+        server.expect("NICK testbot").await;
+        server.expect("USER bot 0 * :Test Bot").await;
+
+        // PING mid-registration must be answered transparently
+        server.send("PING :cookie").await;
+        server.expect("PONG cookie").await;
+
+        server.send(":irc.test 001 testbot :Welcome").await;
+        server
+            .send(":irc.test 005 testbot NETWORK=TestNet CHANTYPES=# :are supported")
+            .await;
+        server
+            .send(":irc.test 005 testbot PREFIX=(ov)@+ NETWORK=Renamed :are supported")
+            .await;
+        server
+            .send(":irc.test 005 testbot -CHANTYPES :are supported")
+            .await;
+        server.send(":nick!u@h PRIVMSG testbot :hi there").await;
+
+        server.expect("JOIN #rust").await;
+        server.send("PING :again").await;
+        server.expect("PONG again").await;
+    };
+
+    let client_side = async {
+        client.connect().await.unwrap();
+        assert_eq!(client.state(), &ConnectionState::Connected);
+        assert!(client.handshake().enabled_caps().contains("multi-prefix"));
+
+        // Handshake traffic is surfaced, ending with the welcome and then
+        // the registration event
+        let mut last = None;
+        loop {
+            match client.next_event().await.unwrap() {
+                Some(Event::Registered) => break,
+                Some(event) => last = Some(event),
+                None => panic!("Connection closed before registration event"),
+            }
+        }
+        assert!(matches!(
+            last,
+            Some(Event::Numeric {
+                response: Response::RPL_WELCOME,
+                ..
+            })
+        ));
+
+        // Three ISUPPORT lines, then the PRIVMSG
+        let mut privmsg = None;
+        while privmsg.is_none() {
+            if let Some(Event::Privmsg { text, .. }) = client.next_event().await.unwrap() {
+                privmsg = Some(text);
+            }
+        }
+        assert_eq!(privmsg.as_deref(), Some("hi there"));
+
+        let isupport = client.isupport();
+        assert_eq!(isupport.network(), Some("Renamed"));
+        // Negated by the last line
+        assert_eq!(isupport.chantypes(), None);
+        assert!(isupport.prefix().is_some());
+
+        client.send(Message::join("#rust")).await.unwrap();
+
+        // PING is consumed; the connection then closes
+        assert!(client.next_event().await.unwrap().is_none());
+    };
+
+    tokio::join!(server, client_side);
+}
+
+#[tokio::test]
+async fn test_auto_join_after_motd() {
+    let config = HandshakeConfig {
+        request_caps: vec![],
+        auto_join: vec![
+            ("#rust".to_string(), None),
+            ("#secret".to_string(), Some("key".to_string())),
+            ("#extra".to_string(), None),
+        ],
+        ..make_config()
+    };
+    let (mut client, listener) = setup_with(config).await;
+
+    let server = async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut server = MockServer::new(stream);
+
+        server.expect("CAP LS 302").await;
+        server.send(":irc.test CAP * LS :").await;
+        server.expect("CAP END").await;
+        server.expect("NICK testbot").await;
+        server.expect("USER bot 0 * :Test Bot").await;
+
+        server.send(":irc.test 001 testbot :Welcome").await;
+        server
+            .send(":irc.test 005 testbot CHANLIMIT=#:2 :are supported")
+            .await;
+        server.send(":irc.test 375 testbot :- MOTD -").await;
+        server.send(":irc.test 376 testbot :End of MOTD").await;
+
+        server.expect("JOIN #secret,#rust key").await;
+    };
+
+    let client_side = async {
+        client.connect().await.unwrap();
+
+        let mut isupport_complete = false;
+        let skipped = loop {
+            match client.next_event().await.unwrap() {
+                Some(Event::IsupportComplete) => isupport_complete = true,
+                Some(Event::AutoJoinSkipped { channels }) => break channels,
+                Some(_) => {}
+                None => panic!("Connection closed before auto-join"),
+            }
+        };
+        assert!(isupport_complete);
+        assert_eq!(skipped, ["#extra"]);
+        assert!(client.handshake().is_settled());
+    };
+
+    tokio::join!(server, client_side);
+}
+
+#[tokio::test]
+async fn test_handshake_nickname_in_use() {
+    let (mut client, listener) = setup().await;
+
+    let server = async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut server = MockServer::new(stream);
+
+        server.expect("CAP LS 302").await;
+        server.send(":irc.test CAP * LS :").await;
+        server.expect("CAP END").await;
+        server.expect("NICK testbot").await;
+        server.expect("USER bot 0 * :Test Bot").await;
+        server
+            .send(":irc.test 433 * testbot :Nickname is already in use")
+            .await;
+    };
+
+    let (_, result) = tokio::join!(server, client.connect());
+    match result {
+        Err(ClientError::Handshake(HandshakeError::NicknameInUse(nick))) => {
+            assert_eq!(nick, "testbot");
+        }
+        other => panic!("Expected NicknameInUse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_handshake_connection_closed() {
+    let (mut client, listener) = setup().await;
+
+    let server = async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        drop(stream);
+    };
+
+    let (_, result) = tokio::join!(server, client.connect());
+    assert!(matches!(
+        result,
+        Err(ClientError::ClosedDuringRegistration) | Err(ClientError::Write(_))
+    ));
+}
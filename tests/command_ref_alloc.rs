@@ -0,0 +1,58 @@
+//! Verifies that `CommandRef::parse` does not allocate.
+//!
+//! Run with: `cargo test --test command_ref_alloc`
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use slirc_proto::{CommandRef, CommandRefEnum, MessageRef};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+#[test]
+fn parse_does_not_allocate() {
+    let lines = [
+        "@time=2024-01-01T00:00:00Z :alice!a@host PRIVMSG #rust :hello world",
+        ":bob!b@host NOTICE alice :hi",
+        ":alice!a@host JOIN #rust",
+        ":alice!a@host JOIN #rust alice :Alice",
+        ":alice!a@host PART #rust :bye",
+        "PING :irc.example.com",
+        ":srv PONG srv :token",
+        ":srv 001 me :Welcome",
+    ];
+    let messages: Vec<MessageRef<'_>> = lines
+        .iter()
+        .map(|line| MessageRef::parse(line).unwrap())
+        .collect();
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let mut privmsgs = 0;
+    for msg in &messages {
+        let parsed = CommandRef::parse(msg.command.name, &msg.command.args);
+        if let CommandRefEnum::Privmsg { text, .. } = parsed {
+            privmsgs += text.len();
+        }
+        let _ = msg.command.parsed();
+    }
+    let after = ALLOCATIONS.load(Ordering::SeqCst);
+
+    assert_eq!(privmsgs, "hello world".len());
+    assert_eq!(after - before, 0, "CommandRef::parse allocated");
+}
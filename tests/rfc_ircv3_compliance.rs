@@ -7,7 +7,7 @@
 //!
 //! Run with: `cargo test --test rfc_ircv3_compliance`
 
-use slirc_proto::{Command, Message, MessageRef};
+use slirc_proto::{Command, Message, MessageRef, ReadMarker, SilenceChange};
 
 // Note: Tag escaping tests have been moved to src/message/tags.rs
 
@@ -410,6 +410,18 @@ mod commands {
         }
     }
 
+    #[test]
+    fn test_join_zero() {
+        let msg: Message = "JOIN 0".parse().unwrap();
+        assert_eq!(msg.command, Command::JOIN("0".to_string(), None, None));
+        assert!(msg.command.is_join_zero());
+        assert_eq!(msg.to_string(), "JOIN 0\r\n");
+
+        let msg: Message = "JOIN #0".parse().unwrap();
+        assert!(!msg.command.is_join_zero());
+        assert!(!Command::PART("0".to_string(), None).is_join_zero());
+    }
+
     #[test]
     fn test_join_variations_roundtrip() {
         let test_cases = vec![
@@ -417,6 +429,7 @@ mod commands {
             "JOIN #channel key",
             ":nick!user@host JOIN #channel",
             "JOIN #channel1,#channel2 key1,key2",
+            "JOIN 0",
         ];
 
         for original in test_cases {
@@ -502,6 +515,167 @@ mod commands {
             assert_eq!(message, reparsed, "Round-trip failed for '{}'", original);
         }
     }
+
+    #[test]
+    fn test_monitor_subcommands() {
+        use slirc_proto::MonitorSubCommand;
+
+        let test_cases = vec![
+            (
+                "MONITOR + alice,bob",
+                MonitorSubCommand::Add,
+                Some("alice,bob"),
+            ),
+            ("MONITOR - alice", MonitorSubCommand::Remove, Some("alice")),
+            ("MONITOR C", MonitorSubCommand::Clear, None),
+            ("MONITOR L", MonitorSubCommand::List, None),
+            ("MONITOR S", MonitorSubCommand::Status, None),
+        ];
+
+        for (original, expected_sub, expected_targets) in test_cases {
+            let message: Message = original.parse().unwrap();
+            match &message.command {
+                Command::MONITOR(sub, targets) => {
+                    assert_eq!(*sub, expected_sub, "Subcommand mismatch for '{}'", original);
+                    assert_eq!(targets.as_deref(), expected_targets);
+                }
+                other => panic!("Expected MONITOR for '{}', got {:?}", original, other),
+            }
+            assert_eq!(message.to_string(), format!("{}\r\n", original));
+        }
+    }
+
+    #[test]
+    fn test_redact_roundtrip() {
+        let msg: Message = "REDACT #channel abc123 :Spam removed".parse().unwrap();
+        assert_eq!(
+            msg.command,
+            Command::REDACT {
+                channel: "#channel".to_string(),
+                msgid: "abc123".to_string(),
+                reason: Some("Spam removed".to_string()),
+            }
+        );
+        assert_eq!(msg.to_string(), "REDACT #channel abc123 :Spam removed\r\n");
+
+        let msg: Message = "REDACT nick abc123".parse().unwrap();
+        assert_eq!(msg, Message::redact("nick", "abc123"));
+        assert_eq!(msg.to_string(), "REDACT nick abc123\r\n");
+
+        let built = Message::redact_with_reason("#channel", "abc123", "oops");
+        let reparsed: Message = built.to_string().parse().unwrap();
+        assert_eq!(built, reparsed);
+    }
+
+    #[test]
+    fn test_markread_roundtrip() {
+        // Client update and server notification share the set form
+        let msg: Message = "MARKREAD #channel timestamp=2024-01-01T12:00:00.000Z"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            msg.command,
+            Command::MARKREAD {
+                target: "#channel".to_string(),
+                marker: ReadMarker::Timestamp("2024-01-01T12:00:00.000Z".to_string()),
+            }
+        );
+        assert_eq!(
+            msg,
+            Message::markread("#channel", Some("2024-01-01T12:00:00.000Z"))
+        );
+        assert_eq!(
+            msg.to_string(),
+            "MARKREAD #channel timestamp=2024-01-01T12:00:00.000Z\r\n"
+        );
+
+        // Query form
+        let msg: Message = "MARKREAD nick".parse().unwrap();
+        assert_eq!(msg, Message::markread("nick", None));
+        assert_eq!(msg.to_string(), "MARKREAD nick\r\n");
+
+        // Server reply when no marker is stored
+        let msg: Message = ":irc.example.com MARKREAD #channel *".parse().unwrap();
+        assert_eq!(
+            msg.command,
+            Command::MARKREAD {
+                target: "#channel".to_string(),
+                marker: ReadMarker::Unset,
+            }
+        );
+        assert_eq!(msg.to_string(), ":irc.example.com MARKREAD #channel *\r\n");
+
+        for msg in [
+            Message::markread("#channel", Some("2024-01-01T12:00:00.000Z")),
+            Message::markread("#channel", None),
+            Message::from(Command::MARKREAD {
+                target: "#channel".to_string(),
+                marker: ReadMarker::Unset,
+            }),
+        ] {
+            assert_eq!(
+                slirc_proto::encode::IrcEncode::to_bytes(&msg),
+                msg.to_string().into_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn test_markread_malformed_is_raw() {
+        let msg: Message = "MARKREAD #channel 2024-01-01T12:00:00.000Z"
+            .parse()
+            .unwrap();
+        assert!(matches!(msg.command, Command::Raw(..)));
+        let msg: Message = "MARKREAD".parse().unwrap();
+        assert!(matches!(msg.command, Command::Raw(..)));
+    }
+
+    #[test]
+    fn test_bouncer_vendor_command() {
+        let msg: Message = "bouncer LISTNETWORKS".parse().unwrap();
+        assert_eq!(
+            msg.command,
+            Command::Vendor {
+                name: "BOUNCER".to_string(),
+                args: vec!["LISTNETWORKS".to_string()],
+            }
+        );
+        assert_eq!(msg.command.name(), "BOUNCER");
+        assert_eq!(msg.command.raw_name(), Some("BOUNCER"));
+        assert_eq!(msg.to_string(), "BOUNCER LISTNETWORKS\r\n");
+
+        for name in slirc_proto::command::VENDOR_COMMANDS {
+            let cmd = Command::new(name, vec!["SUB"]).unwrap();
+            assert!(matches!(cmd, Command::Vendor { .. }), "{}", name);
+            assert_eq!(cmd.name(), *name);
+        }
+
+        let line =
+            "@batch=1 :irc.example.com BOUNCER NETWORK 42 :name=Libera Chat;state=connected\r\n";
+        let msg: Message = line.parse().unwrap();
+        let Command::Vendor { ref name, ref args } = msg.command else {
+            panic!("Expected vendor command, got {:?}", msg.command);
+        };
+        assert_eq!(name, "BOUNCER");
+        assert_eq!(args, &["NETWORK", "42", "name=Libera Chat;state=connected"]);
+        assert_eq!(msg.to_string(), line);
+        assert_eq!(
+            slirc_proto::encode::IrcEncode::to_bytes(&msg),
+            line.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_redact_missing_msgid_is_raw() {
+        let msg: Message = "REDACT #channel".parse().unwrap();
+        assert!(matches!(msg.command, Command::Raw(..)));
+    }
+
+    #[test]
+    fn test_monitor_unknown_subcommand_is_raw() {
+        let msg: Message = "MONITOR X alice".parse().unwrap();
+        assert!(matches!(msg.command, Command::Raw(..)));
+    }
 }
 
 // =============================================================================
@@ -780,6 +954,38 @@ mod operator_commands {
         }
     }
 
+    #[test]
+    fn test_silence() {
+        let msg: Message = "SILENCE +*!*@spam.example".parse().unwrap();
+        assert_eq!(
+            msg.command,
+            Command::SILENCE(vec![SilenceChange::Add("*!*@spam.example".to_string())])
+        );
+
+        let msg: Message = "SILENCE -*!*@spam.example".parse().unwrap();
+        assert_eq!(
+            msg.command,
+            Command::SILENCE(vec![SilenceChange::Remove("*!*@spam.example".to_string())])
+        );
+
+        let msg: Message = "SILENCE +a,-b".parse().unwrap();
+        assert_eq!(
+            msg.command,
+            Command::SILENCE(vec![
+                SilenceChange::Add("a".to_string()),
+                SilenceChange::Remove("b".to_string()),
+            ])
+        );
+        assert_eq!(msg.to_string(), "SILENCE +a,-b\r\n");
+
+        let msg: Message = "SILENCE".parse().unwrap();
+        assert_eq!(msg.command, Command::SILENCE(vec![]));
+
+        // Entries without a sign are not understood
+        let msg: Message = "SILENCE +a,b".parse().unwrap();
+        assert!(matches!(msg.command, Command::Raw(..)));
+    }
+
     #[test]
     fn test_operator_ban_commands_roundtrip() {
         let test_cases = vec![
@@ -791,6 +997,10 @@ mod operator_commands {
             "UNDLINE 192.168.1.0/24",
             "KNOCK #channel",
             "KNOCK #secretroom :Please let me in!",
+            "SILENCE +*!*@spam.example",
+            "SILENCE -*!*@spam.example",
+            "SILENCE +a,-b",
+            "SILENCE",
         ];
 
         for original in test_cases {
@@ -0,0 +1,56 @@
+//! Integration tests for `WhoCollector`.
+//!
+//! Drives a WHOX query for a channel from the request line to `RPL_ENDOFWHO`.
+
+use slirc_proto::response::{WhoCollector, WhoEntry};
+use slirc_proto::{Command, Message};
+
+#[test]
+fn test_whox_sequence() {
+    let fields = "%tcuhnfar,152";
+    let request = Message::from(Command::WHO(
+        Some("#rust".to_string()),
+        Some(fields.to_string()),
+    ));
+    assert_eq!(request.to_string(), "WHO #rust %tcuhnfar,152\r\n");
+
+    let mut who = WhoCollector::whox(fields);
+    let mut result = None;
+    for line in [
+        ":irc.test 354 me 152 #rust ali alice.example alice H@ alice_acct :Alice Smith",
+        ":irc.test 354 me 152 #rust ~bob bob.example bob G 0 :Bob",
+        ":irc.test PRIVMSG #rust :unrelated traffic",
+        ":irc.test 354 me 152 #rust truncated",
+        ":irc.test 352 me #rust ~carol carol.example irc.test carol H :0 Carol",
+        ":irc.test 315 me #rust :End of WHO list",
+    ] {
+        let msg: Message = line.parse().unwrap();
+        if let Some(done) = who.feed(&msg) {
+            assert!(result.is_none());
+            result = Some(done);
+        }
+    }
+
+    let (mask, entries) = result.expect("collector completes on 315");
+    assert_eq!(mask, "#rust");
+    let nicks: Vec<_> = entries.iter().filter_map(WhoEntry::nick).collect();
+    assert_eq!(nicks, ["alice", "bob", "carol"]);
+
+    let WhoEntry::Whox(alice) = &entries[0] else {
+        panic!("Expected a WHOX entry");
+    };
+    assert_eq!(alice.querytype.as_deref(), Some("152"));
+    assert_eq!(alice.user.as_deref(), Some("ali"));
+    assert_eq!(alice.host.as_deref(), Some("alice.example"));
+    assert_eq!(alice.flags.as_deref(), Some("H@"));
+    assert_eq!(alice.account.as_deref(), Some("alice_acct"));
+    assert_eq!(alice.realname.as_deref(), Some("Alice Smith"));
+
+    let WhoEntry::Whox(bob) = &entries[1] else {
+        panic!("Expected a WHOX entry");
+    };
+    assert_eq!(bob.account.as_deref(), Some("0"));
+
+    // A standard reply in the same stream is still decoded
+    assert!(matches!(&entries[2], WhoEntry::Who(carol) if carol.hopcount == 0));
+}